@@ -0,0 +1,104 @@
+//!
+//! Random soups played on a 128x64 SSD1306 OLED, driven by a Raspberry Pi
+//! Pico over I2C on GPIO 4 (SDA) and GPIO 5 (SCL).
+//!
+//! Both grids live in static memory and are stepped back and forth with
+//! [`Grid::step_into()`], so no grid is ever copied onto the stack. Each
+//! generation is packed straight into the display's page layout with
+//! [`to_packed_bits()`].
+//!
+//! To flash, hold BOOTSEL while plugging in the Pico, and run
+//! `cargo run --release` from this directory with `elf2uf2-rs` installed.
+//!
+
+#![no_std]
+#![no_main]
+
+use core::mem;
+
+use embedded_hal::delay::DelayNs;
+use lifeless::{
+    random::SplitMix64,
+    render::{to_packed_bits, BitLayout},
+    Grid,
+};
+use panic_halt as _;
+use rp_pico::{
+    entry,
+    hal::{
+        self,
+        fugit::RateExtU32,
+        rosc::{Enabled, RingOscillator},
+        Clock,
+    },
+    pac,
+};
+use ssd1306::{prelude::*, I2CDisplayInterface, Ssd1306};
+use static_cell::ConstStaticCell;
+
+const W: usize = 128;
+const H: usize = 64;
+/// The number of generations before a new soup is seeded.
+const GENERATIONS: u64 = 1000;
+
+static GRIDS: ConstStaticCell<[Grid<W, H>; 2]> = ConstStaticCell::new([Grid::new(), Grid::new()]);
+static FRAMEBUFFER: ConstStaticCell<[u8; W * H / 8]> = ConstStaticCell::new([0; W * H / 8]);
+
+#[entry]
+fn main() -> ! {
+    let mut pac = pac::Peripherals::take().unwrap();
+    let mut watchdog = hal::Watchdog::new(pac.WATCHDOG);
+    let clocks = hal::clocks::init_clocks_and_plls(
+        rp_pico::XOSC_CRYSTAL_FREQ,
+        pac.XOSC,
+        pac.CLOCKS,
+        pac.PLL_SYS,
+        pac.PLL_USB,
+        &mut pac.RESETS,
+        &mut watchdog,
+    )
+    .unwrap();
+    let mut timer = hal::Timer::new(pac.TIMER, &mut pac.RESETS, &clocks);
+
+    let sio = hal::Sio::new(pac.SIO);
+    let pins = rp_pico::Pins::new(
+        pac.IO_BANK0,
+        pac.PADS_BANK0,
+        sio.gpio_bank0,
+        &mut pac.RESETS,
+    );
+    let i2c = hal::I2C::i2c0(
+        pac.I2C0,
+        pins.gpio4.reconfigure(),
+        pins.gpio5.reconfigure(),
+        400.kHz(),
+        &mut pac.RESETS,
+        clocks.system_clock.freq(),
+    );
+
+    let interface = I2CDisplayInterface::new(i2c);
+    let mut display = Ssd1306::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+    display.init().unwrap();
+
+    let mut rng = SplitMix64::new(seed(&RingOscillator::new(pac.ROSC).initialize()));
+    let [mut grid, mut next] = GRIDS.take().each_mut();
+    let framebuffer = FRAMEBUFFER.take();
+
+    loop {
+        if grid.generation.is_multiple_of(GENERATIONS) || grid.population() == 0 {
+            grid.randomize(1.0 / 3.0, &mut rng);
+        }
+
+        to_packed_bits(grid, framebuffer, BitLayout::Pages);
+        display.draw(framebuffer).unwrap();
+
+        grid.step_into(next);
+        mem::swap(&mut grid, &mut next);
+        timer.delay_ms(20);
+    }
+}
+
+/// Returns a random seed from the jitter of the ring oscillator.
+fn seed(rosc: &RingOscillator<Enabled>) -> u64 {
+    (0..64).fold(0, |seed, _| seed << 1 | u64::from(rosc.get_random_bit()))
+}
@@ -1,70 +1,161 @@
+//!
+//! A minimal pattern editor and player in the terminal.
+//!
+//! Usage: `tui [FILE]`, where `FILE` is an RLE or plaintext (`.cells`)
+//! pattern to start with, centered in the grid and run under the rule given
+//! by its header.
+//!
+//! Keys:
+//! - arrows or `hjkl` move the cursor, and space toggles the cell under it.
+//! - enter plays or pauses, `n` steps one generation, and `+` and `-`
+//!   change the speed.
+//! - `[` and `]` pick a pattern from the lexicon, and `p` stamps it at the
+//!   cursor.
+//! - `w` saves the grid to `grid.rle`, and `r` reads it back at the cursor.
+//! - `c` clears the grid, and `q` or escape quits.
+//!
+
 use std::{
+    env,
     error::Error,
-    io::{self, Write},
+    fs, io,
+    time::{Duration, Instant},
 };
 
-use crossterm::{
-    cursor::MoveTo,
-    event::{self, Event, KeyCode},
-    execute,
-    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use lifeless::{
+    frontend::terminal::{Speed, Terminal},
+    lexicon,
+    pattern::Pattern,
+    rule::LifeLike,
+    runner,
+    sim::StepStats,
+    Coord, Grid, Simulation,
 };
-use lifeless::{Cell, Coord, Grid};
 
-const CLEAR_ALL: Clear = Clear(ClearType::All);
-const RESET_CUR: MoveTo = MoveTo(0, 0);
+const W: usize = 40;
+const H: usize = 22;
+const PATH: &str = "grid.rle";
 
 fn main() -> Result<(), Box<dyn Error>> {
-    terminal::enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, Clear(ClearType::All))?;
+    let mut sim = Simulation::new(Grid::<W, H>::new(), LifeLike::default());
+    if let Some(path) = env::args_os().nth(1) {
+        let input = runner::load(&fs::read_to_string(&path)?)?;
+        *sim.grid_mut() = centered(&input.pattern.as_pattern())
+            .ok_or_else(|| format!("{}: pattern does not fit in {W}x{H}", path.display()))?;
+        sim.set_rule(input.rule.unwrap_or_default());
+    }
+    sim.set_paused(true);
 
-    let mut grid = Grid::<24, 16>::new();
+    let mut terminal = Terminal::new(io::stdout());
+    terminal.enter()?;
 
-    grid[Coord(1, 2)] = Cell::Alive;
-    grid[Coord(2, 3)] = Cell::Alive;
-    grid[Coord(3, 1)] = Cell::Alive;
-    grid[Coord(3, 2)] = Cell::Alive;
-    grid[Coord(3, 3)] = Cell::Alive;
+    let result = run(&mut terminal, &mut sim);
+    terminal.leave()?;
+    result
+}
 
-    loop {
-        draw_grid(&mut stdout, &grid)?;
+/// Returns a new grid with `pattern` in its center, if it fits.
+fn centered(pattern: &Pattern) -> Option<Grid<W, H>> {
+    let Coord(width, height) = pattern.extents();
+    let mut grid = Grid::new();
+    grid.paste(
+        pattern,
+        Coord(W.checked_sub(width)? / 2, H.checked_sub(height)? / 2),
+    );
+    Some(grid)
+}
 
-        if let Event::Key(ev) = event::read()? {
-            match ev.code {
-                KeyCode::Esc | KeyCode::Char('q') => break,
-                KeyCode::Enter | KeyCode::Char(' ') => {
-                    grid = grid.step();
-                }
-                _ => {}
-            }
-        }
-    }
+fn run(
+    terminal: &mut Terminal<'_, io::Stdout, W, H>,
+    sim: &mut Simulation<W, H, LifeLike>,
+) -> Result<(), Box<dyn Error>> {
+    let entries = lexicon::entries().as_slice();
 
-    execute!(stdout, LeaveAlternateScreen)?;
-    terminal::disable_raw_mode()?;
+    let mut cursor = Coord(W / 2, H / 2);
+    let mut selected = 0;
+    let mut speed = Speed::default();
+    let mut stats = StepStats::default();
+    let mut last_step = Instant::now();
+    let mut message = String::new();
 
-    Ok(())
-}
+    loop {
+        let grid = sim.grid();
+        terminal.set_highlight(Some(cursor));
+        terminal.draw(grid)?;
+        terminal.status(&format!(
+            "{} {}/s | {} | gen {} | pop {} | +{} -{} | {} | {message}",
+            if sim.is_paused() { "paused" } else { "playing" },
+            speed.per_second(),
+            sim.rule(),
+            grid.generation,
+            grid.population(),
+            stats.births,
+            stats.deaths,
+            entries[selected].name,
+        ))?;
 
-fn draw_grid<const W: usize, const H: usize>(
-    writer: &mut impl Write,
-    grid: &Grid<W, H>,
-) -> Result<(), io::Error> {
-    execute!(writer, CLEAR_ALL, RESET_CUR)?;
+        let timeout = if sim.is_paused() {
+            Duration::MAX
+        } else {
+            speed.interval().saturating_sub(last_step.elapsed())
+        };
+        if !event::poll(timeout)? {
+            sim.step_observed(&mut |_: &Grid<W, H>, step| stats = step);
+            last_step = Instant::now();
+            continue;
+        }
 
-    for row in 0..H {
-        for col in 0..W {
-            write!(
-                writer,
-                "{}",
-                match grid[Coord(col, row)] {
-                    Cell::Alive => "██",
-                    Cell::Dead => "░░",
+        let ev = match event::read()? {
+            Event::Key(ev) if ev.kind != KeyEventKind::Release => ev,
+            Event::Resize(..) => {
+                terminal.invalidate();
+                continue;
+            }
+            _ => continue,
+        };
+        message.clear();
+
+        let Coord(x, y) = cursor;
+        match ev.code {
+            KeyCode::Esc | KeyCode::Char('q') => break,
+            KeyCode::Left | KeyCode::Char('h') => cursor = Coord(x.saturating_sub(1), y),
+            KeyCode::Right | KeyCode::Char('l') => cursor = Coord((x + 1).min(W - 1), y),
+            KeyCode::Up | KeyCode::Char('k') => cursor = Coord(x, y.saturating_sub(1)),
+            KeyCode::Down | KeyCode::Char('j') => cursor = Coord(x, (y + 1).min(H - 1)),
+            KeyCode::Char(' ') => sim.grid_mut()[cursor] = !sim.grid()[cursor],
+            KeyCode::Enter => {
+                sim.set_paused(!sim.is_paused());
+                last_step = Instant::now();
+            }
+            KeyCode::Char('n') => {
+                sim.step_observed(&mut |_: &Grid<W, H>, step| stats = step);
+            }
+            KeyCode::Char('+' | '=') => speed.faster(),
+            KeyCode::Char('-') => speed.slower(),
+            KeyCode::Char('[') => selected = selected.checked_sub(1).unwrap_or(entries.len() - 1),
+            KeyCode::Char(']') => selected = (selected + 1) % entries.len(),
+            KeyCode::Char('p') => sim.grid_mut().paste(&entries[selected].pattern, cursor),
+            KeyCode::Char('c') => *sim.grid_mut() = Grid::new(),
+            KeyCode::Char('w') => {
+                let mut rle = String::new();
+                sim.grid().write_rle(&mut rle, &sim.rule().to_string())?;
+                fs::write(PATH, rle)?;
+                message = format!("saved {PATH}");
+            }
+            KeyCode::Char('r') => match fs::read_to_string(PATH).map(|src| runner::load(&src)) {
+                Ok(Ok(input)) => {
+                    let mut grid = Grid::new();
+                    grid.paste(&input.pattern.as_pattern(), cursor);
+                    *sim.grid_mut() = grid;
+                    sim.set_rule(input.rule.unwrap_or_default());
+                    message = format!("loaded {PATH} at the cursor");
                 }
-            )?;
+                Ok(Err(err)) => message = format!("{PATH}: {err}"),
+                Err(err) => message = format!("{PATH}: {err}"),
+            },
+            _ => {}
         }
-        writeln!(writer, "\r")?;
     }
 
     Ok(())
@@ -0,0 +1,87 @@
+//!
+//! A 1024x1024 grid played in a desktop window at up to 60 frames per
+//! second, stepped by a `BandedGrid` with one band per available thread.
+//!
+//! Mouse:
+//! - the left button draws live cells, and the right button erases them.
+//!
+//! Keys:
+//! - space plays or pauses, and `n` steps one generation.
+//! - `r` fills the grid with a random soup, and `c` clears it.
+//! - escape quits.
+//!
+
+use std::{
+    error::Error,
+    num::NonZeroUsize,
+    thread,
+    time::{Duration, Instant},
+};
+
+use lifeless::{
+    banded::BandedGrid,
+    frontend::window::Window,
+    random::SplitMix64,
+    rule::{Life, Topology},
+    sim::timing::StepTimer,
+    Grid,
+};
+use minifb::{Key, KeyRepeat, Scale};
+
+const W: usize = 1024;
+const H: usize = 1024;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut window = Window::<W, H>::new("lifeless", Scale::X1)?;
+    let mut rng = SplitMix64::new(0x5eed);
+    let bands = thread::available_parallelism().unwrap_or(NonZeroUsize::MIN);
+    let mut grid = Box::new(Grid::<W, H>::new());
+    grid.randomize(1.0 / 3.0, &mut rng);
+    let mut banded = BandedGrid::from_grid(&grid, bands);
+
+    let mut playing = true;
+    let mut second = Instant::now();
+    let mut steps = 0;
+    let mut timer = StepTimer::new();
+
+    while window.is_open() {
+        let keys = window.inner();
+        if keys.is_key_down(Key::Escape) {
+            break;
+        }
+        if keys.is_key_pressed(Key::Space, KeyRepeat::No) {
+            playing = !playing;
+        }
+        let step = playing || keys.is_key_pressed(Key::N, KeyRepeat::Yes);
+        if keys.is_key_pressed(Key::R, KeyRepeat::No) {
+            grid.randomize(1.0 / 3.0, &mut rng);
+            banded = BandedGrid::from_grid(&grid, bands);
+        }
+        if keys.is_key_pressed(Key::C, KeyRepeat::No) {
+            banded = BandedGrid::new(bands);
+        }
+        if let Some((coord, cell)) = window.brush() {
+            banded[coord] = cell;
+        }
+
+        if step {
+            timer.time(|| banded.step_with(&Life, Topology::Bounded));
+            steps += 1;
+        }
+        *grid = Grid::from(&banded);
+        window.draw(&grid)?;
+
+        if second.elapsed() >= Duration::from_secs(1) {
+            let per_step = timer.average().unwrap_or_default();
+            window.inner_mut().set_title(&format!(
+                "lifeless | gen {} | pop {} | {steps} gen/s | {per_step:.1?}/gen",
+                grid.generation,
+                grid.population(),
+            ));
+            second = Instant::now();
+            steps = 0;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+/*
+ * C bindings for lifeless, exported with the `ffi_grid!` macro of the `ffi`
+ * feature.
+ *
+ * Every grid in a library has the same size, given by `lifeless_width` and
+ * `lifeless_height`. Handles are owned by the caller and must be freed with
+ * `lifeless_destroy`. Every function accepts a null handle, doing nothing.
+ */
+
+#ifndef LIFELESS_H
+#define LIFELESS_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct LifelessGrid LifelessGrid;
+
+/* Returns a new empty grid, to be freed with `lifeless_destroy`. */
+LifelessGrid *lifeless_create(void);
+
+/* Frees a grid returned by `lifeless_create`. */
+void lifeless_destroy(LifelessGrid *grid);
+
+/* Returns the width of grids, in cells. */
+size_t lifeless_width(void);
+
+/* Returns the height of grids, in cells. */
+size_t lifeless_height(void);
+
+/* Returns the generation of a grid. */
+uint64_t lifeless_generation(const LifelessGrid *grid);
+
+/* Steps a grid by `generations` generations. */
+void lifeless_step(LifelessGrid *grid, uint32_t generations);
+
+/* Returns whether a cell is alive, or `false` outside the grid. */
+bool lifeless_get_cell(const LifelessGrid *grid, size_t x, size_t y);
+
+/* Sets whether a cell is alive, doing nothing outside the grid. */
+void lifeless_set_cell(LifelessGrid *grid, size_t x, size_t y, bool alive);
+
+/*
+ * Writes a grid in the wire format to `buf` if it is at least as long as
+ * needed, returning the length needed.
+ */
+size_t lifeless_serialize(const LifelessGrid *grid, uint8_t *buf, size_t len);
+
+/*
+ * Replaces a grid with one in the wire format, returning whether the bytes
+ * were a valid grid of this size.
+ */
+bool lifeless_deserialize(LifelessGrid *grid, const uint8_t *bytes, size_t len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* LIFELESS_H */
@@ -0,0 +1,623 @@
+//!
+//! Analysis of finished soups and the objects in them.
+//!
+//! See:
+//! - [`census()`] to identify and count the objects on a grid.
+//! - [`classify()`] to find out how a single pattern behaves, and how fast
+//!   it moves if it is a spaceship.
+//!
+
+use core::fmt;
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    cell::Cell,
+    grid::Grid,
+    math::Coord,
+    pattern::{Pattern, PatternBuf},
+    sim::detect_cycle,
+};
+
+///
+/// The highest period considered when separating and classifying objects.
+///
+pub const MAX_PERIOD: usize = 64;
+
+///
+/// The result of a [`census()`]: the number of objects of each species.
+///
+/// Species are identified by their
+/// [apgcode](https://conwaylife.com/wiki/Apgcode), such as `xs4_33` for the
+/// block or `xq4_153` for the glider. Objects that could not be classified
+/// are counted under [`Census::PATHOLOGICAL`].
+///
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Census {
+    counts: BTreeMap<String, usize>,
+}
+
+impl Census {
+    /// The species of objects that could not be classified.
+    pub const PATHOLOGICAL: &'static str = "PATHOLOGICAL";
+
+    /// Returns the number of objects of the given species.
+    #[must_use]
+    pub fn get(&self, code: &str) -> usize {
+        self.counts.get(code).copied().unwrap_or(0)
+    }
+
+    /// Returns the total number of objects counted.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Adds the counts of another census to this one.
+    pub fn merge(&mut self, other: &Self) {
+        for (code, &count) in &other.counts {
+            *self.counts.entry(code.clone()).or_default() += count;
+        }
+    }
+
+    /// Returns an iterator over each species and its count, ordered by code.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.counts
+            .iter()
+            .map(|(code, &count)| (code.as_str(), count))
+    }
+}
+
+///
+/// How a pattern behaves when run on its own, as found by [`classify()`].
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Behavior {
+    /// The pattern dies out after `generations` generations.
+    Dies { generations: usize },
+    /// The pattern never changes.
+    StillLife,
+    /// The pattern returns to its initial state every `period` generations.
+    Oscillator { period: usize },
+    /// The pattern returns to its initial state translated, moving at
+    /// `velocity`.
+    Spaceship { velocity: Velocity },
+    /// The pattern does not return to its initial state, even translated,
+    /// within [`MAX_PERIOD`] generations.
+    Unstable,
+}
+
+///
+/// The velocity of a spaceship: it is translated by `(dx, dy)` cells every
+/// `period` generations.
+///
+/// Displays as its speed and direction, such as `c/4 diagonal` for the
+/// glider or `2c/5 orthogonal` for the 2c/5 spaceships.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Velocity {
+    pub dx: i64,
+    pub dy: i64,
+    pub period: usize,
+}
+
+impl Velocity {
+    /// Returns whether this velocity is along a row or column.
+    #[must_use]
+    pub fn is_orthogonal(&self) -> bool {
+        self.dx == 0 || self.dy == 0
+    }
+
+    /// Returns whether this velocity is along a diagonal.
+    #[must_use]
+    pub fn is_diagonal(&self) -> bool {
+        self.dx.abs() == self.dy.abs()
+    }
+}
+
+impl fmt::Display for Velocity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let distance = self.dx.unsigned_abs().max(self.dy.unsigned_abs());
+        let period = self.period as u64;
+        let divisor = gcd(distance, period);
+        let (distance, period) = (distance / divisor, period / divisor);
+
+        match distance {
+            1 => write!(f, "c")?,
+            _ => write!(f, "{distance}c")?,
+        }
+        if period != 1 {
+            write!(f, "/{period}")?;
+        }
+
+        if self.is_orthogonal() {
+            write!(f, " orthogonal")
+        } else if self.is_diagonal() {
+            write!(f, " diagonal")
+        } else {
+            write!(f, " oblique")
+        }
+    }
+}
+
+/// Returns the greatest common divisor of two numbers.
+fn gcd(a: u64, b: u64) -> u64 {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+///
+/// Run a pattern on its own on an unbounded plane for up to [`MAX_PERIOD`]
+/// generations, and report whether it is a still life, an oscillator and
+/// with which period, a spaceship and with which velocity, or none of them.
+///
+/// An empty pattern dies out after 0 generations.
+///
+#[must_use]
+pub fn classify(pattern: &Pattern<'_>) -> Behavior {
+    let initial = pattern
+        .cells()
+        .iter()
+        .map(|&coord| to_point(coord))
+        .collect::<Cells>();
+    if initial.is_empty() {
+        return Behavior::Dies { generations: 0 };
+    }
+
+    let (origin, _) = bounds(&initial);
+    let shape = normalize(&initial);
+
+    let mut current = initial;
+    for generation in 1..=MAX_PERIOD {
+        current = step(&current);
+        if current.is_empty() {
+            return Behavior::Dies {
+                generations: generation,
+            };
+        }
+        if normalize(&current) != shape {
+            continue;
+        }
+
+        let ((x, y), _) = bounds(&current);
+        return match ((x - origin.0, y - origin.1), generation) {
+            ((0, 0), 1) => Behavior::StillLife,
+            ((0, 0), period) => Behavior::Oscillator { period },
+            ((dx, dy), period) => Behavior::Spaceship {
+                velocity: Velocity { dx, dy, period },
+            },
+        };
+    }
+
+    Behavior::Unstable
+}
+
+///
+/// Separate the ash of a finished soup into objects, identify each of them,
+/// and count them by species.
+///
+/// Objects are found among the cells that are alive at some point during
+/// the next [`MAX_PERIOD`] generations: nearby groups of cells are treated
+/// as one object unless each of them runs independently of the others, so
+/// the separate parts of a pulsar are counted as one object, but two blocks
+/// one cell apart are not. Each object is then run in isolation on an
+/// unbounded plane to classify it as a still life, oscillator or spaceship.
+///
+/// The grid should have stabilized before taking a census, as objects that
+/// still interact with each other are counted as [`Census::PATHOLOGICAL`].
+///
+#[must_use]
+pub fn census<const W: usize, const H: usize>(grid: &Grid<W, H>) -> Census {
+    let mut union = grid.clone();
+    let mut next = grid.clone();
+    for _ in 1..MAX_PERIOD {
+        next = next.step();
+        for (cell, &next) in union
+            .cells
+            .iter_mut()
+            .flatten()
+            .zip(next.cells.iter().flatten())
+        {
+            if next == Cell::Alive {
+                *cell = Cell::Alive;
+            }
+        }
+    }
+
+    // each part is only the cells of a component which are alive initially
+    let components = union.components();
+    let parts = components
+        .iter()
+        .map(|(pattern, origin)| {
+            pattern
+                .as_pattern()
+                .cells()
+                .iter()
+                .map(|&coord| coord + *origin)
+                .filter(|&coord| grid[coord] == Cell::Alive)
+                .map(to_point)
+                .collect::<Cells>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut census = Census::default();
+    let mut count = |object: &Cells| {
+        let code = apgcode(object, MAX_PERIOD).unwrap_or_else(|| Census::PATHOLOGICAL.into());
+        *census.counts.entry(code).or_default() += 1;
+    };
+
+    // parts which can interact in a single generation are first grouped
+    // together, then split up again if they are independent objects.
+    for cluster in clusters(&union, &components) {
+        let parts = cluster
+            .iter()
+            .map(|&i| parts[i].clone())
+            .collect::<Vec<_>>();
+        if parts.len() > 1
+            && parts.iter().all(|part| apgcode(part, MAX_PERIOD).is_some())
+            && independent(&parts)
+        {
+            parts.iter().for_each(&mut count);
+        } else {
+            count(&parts.into_iter().flatten().collect());
+        }
+    }
+
+    census
+}
+
+/// A point on the unbounded plane used to run objects in isolation.
+type Point = (i64, i64);
+
+/// A finite set of live cells on the unbounded plane.
+type Cells = BTreeSet<Point>;
+
+fn to_point(coord: Coord) -> Point {
+    (coord.0 as i64, coord.1 as i64)
+}
+
+///
+/// Group the components of `grid` whose cells are at most 2 cells apart in
+/// either direction, returning the indices of the components in each group.
+///
+fn clusters<const W: usize, const H: usize>(
+    grid: &Grid<W, H>,
+    components: &[(PatternBuf, Coord)],
+) -> Vec<Vec<usize>> {
+    let mut labels = vec![usize::MAX; W * H];
+    for (i, (pattern, origin)) in components.iter().enumerate() {
+        for &coord in pattern.as_pattern().cells() {
+            let Coord(x, y) = coord + *origin;
+            labels[y * W + x] = i;
+        }
+    }
+
+    // a union-find forest over the components
+    let mut parents = (0..components.len()).collect::<Vec<_>>();
+    let root = |parents: &[usize], mut i: usize| {
+        while parents[i] != i {
+            i = parents[i];
+        }
+        i
+    };
+    for (y, x) in (0..H).flat_map(|y| (0..W).map(move |x| (y, x))) {
+        if grid.cells[y][x] == Cell::Dead {
+            continue;
+        }
+        for ny in y.saturating_sub(2)..(y + 3).min(H) {
+            for nx in x.saturating_sub(2)..(x + 3).min(W) {
+                let neighbor = labels[ny * W + nx];
+                if neighbor == usize::MAX {
+                    continue;
+                }
+                let (a, b) = (root(&parents, labels[y * W + x]), root(&parents, neighbor));
+                parents[a.max(b)] = a.min(b);
+            }
+        }
+    }
+
+    let mut clusters = BTreeMap::<usize, Vec<usize>>::new();
+    for i in 0..components.len() {
+        clusters.entry(root(&parents, i)).or_default().push(i);
+    }
+    clusters.into_values().collect()
+}
+
+///
+/// Returns whether running `parts` together is the same as running each of
+/// them on its own, for [`MAX_PERIOD`] generations.
+///
+fn independent(parts: &[Cells]) -> bool {
+    let mut whole = parts.iter().flatten().copied().collect::<Cells>();
+    let mut parts = parts.to_vec();
+
+    for _ in 0..MAX_PERIOD {
+        whole = step(&whole);
+        parts = parts.iter().map(step).collect();
+        if whole != parts.iter().flatten().copied().collect() {
+            return false;
+        }
+    }
+
+    true
+}
+
+///
+/// Calculate the apgcode of an object, or [`None`] if it does not return to
+/// its initial state (up to translation) within `max_period` generations.
+///
+fn apgcode(initial: &Cells, max_period: usize) -> Option<String> {
+    if initial.is_empty() {
+        return None;
+    }
+
+    // cycles of period p are found within 3p generations
+    let (start, period) = detect_cycle(initial, step, normalize, 3 * max_period as u64)?;
+    let period = usize::try_from(period).ok()?;
+    if start != 0 || period > max_period {
+        return None;
+    }
+
+    let mut phases = Vec::from([normalize(initial)]);
+    let mut current = initial.clone();
+    for _ in 1..period {
+        current = step(&current);
+        phases.push(normalize(&current));
+    }
+    let displaced = bounds(&step(&current)).0 != bounds(initial).0;
+
+    let population = initial.len();
+    let prefix = match (period, displaced) {
+        (1, _) => alloc::format!("xs{population}_"),
+        (_, false) => alloc::format!("xp{period}_"),
+        (_, true) => alloc::format!("xq{period}_"),
+    };
+
+    let wechsler = phases
+        .iter()
+        .flat_map(orientations)
+        .map(|cells| wechsler(&cells))
+        .min_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))?;
+
+    Some(prefix + &wechsler)
+}
+
+/// Calculate the next generation of a set of live cells.
+fn step(cells: &Cells) -> Cells {
+    let mut counts = BTreeMap::<Point, usize>::new();
+    for &(x, y) in cells {
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if (dx, dy) != (0, 0) {
+                    *counts.entry((x + dx, y + dy)).or_default() += 1;
+                }
+            }
+        }
+    }
+
+    counts
+        .into_iter()
+        .filter(|(point, count)| Cell::from(cells.contains(point)).next(*count) == Cell::Alive)
+        .map(|(point, _)| point)
+        .collect()
+}
+
+/// Returns the top-left and bottom-right corners of a non-empty set.
+fn bounds(cells: &Cells) -> (Point, Point) {
+    cells.iter().fold(
+        ((i64::MAX, i64::MAX), (i64::MIN, i64::MIN)),
+        |((x0, y0), (x1, y1)), &(x, y)| ((x0.min(x), y0.min(y)), (x1.max(x), y1.max(y))),
+    )
+}
+
+/// Translate a set so its top-left corner is at the origin.
+fn normalize(cells: &Cells) -> Cells {
+    let ((x0, y0), _) = bounds(cells);
+    cells.iter().map(|&(x, y)| (x - x0, y - y0)).collect()
+}
+
+/// Returns all 8 rotations and reflections of a set, normalized.
+fn orientations(cells: &Cells) -> impl Iterator<Item = Cells> + '_ {
+    let transforms: [fn(Point) -> Point; 8] = [
+        |(x, y)| (x, y),
+        |(x, y)| (-x, y),
+        |(x, y)| (x, -y),
+        |(x, y)| (-x, -y),
+        |(x, y)| (y, x),
+        |(x, y)| (-y, x),
+        |(x, y)| (y, -x),
+        |(x, y)| (-y, -x),
+    ];
+
+    transforms
+        .into_iter()
+        .map(|transform| normalize(&cells.iter().copied().map(transform).collect()))
+}
+
+///
+/// Encode a normalized set in
+/// [extended Wechsler format](https://conwaylife.com/wiki/Apgcode#Extended_Wechsler_format).
+///
+fn wechsler(cells: &Cells) -> String {
+    const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    let (_, (x1, y1)) = bounds(cells);
+    let mut code = String::new();
+
+    for strip in 0..=y1 / 5 {
+        if strip > 0 {
+            code.push('z');
+        }
+
+        let columns = (0..=x1)
+            .map(|x| {
+                (0..5)
+                    .filter(|row| cells.contains(&(x, strip * 5 + row)))
+                    .map(|row| 1 << row)
+                    .sum::<usize>()
+            })
+            .collect::<Vec<_>>();
+        let len = columns.iter().rposition(|&c| c != 0).map_or(0, |i| i + 1);
+
+        let mut columns = columns[..len].iter().peekable();
+        while let Some(&column) = columns.next() {
+            if column != 0 {
+                code.push(DIGITS[column].into());
+                continue;
+            }
+
+            let mut zeros = 1;
+            while zeros < 39 && columns.next_if_eq(&&0).is_some() {
+                zeros += 1;
+            }
+            match zeros {
+                1 => code.push('0'),
+                2 => code.push('w'),
+                3 => code.push('x'),
+                _ => {
+                    code.push('y');
+                    code.push(DIGITS[zeros - 4].into());
+                }
+            }
+        }
+    }
+
+    code
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::lexicon;
+
+    #[test]
+    fn apgcodes() {
+        for (name, expected) in [
+            ("block", "xs4_33"),
+            ("beehive", "xs6_696"),
+            ("loaf", "xs7_2596"),
+            ("boat", "xs5_253"),
+            ("eater 1", "xs7_178c"),
+            ("blinker", "xp2_7"),
+            ("toad", "xp2_7e"),
+            ("beacon", "xp2_318c"),
+            ("pulsar", "xp3_co9nas0san9oczgoldlo0oldlogz1047210127401"),
+            ("pentadecathlon", "xp15_4r4z4r4"),
+            ("glider", "xq4_153"),
+            ("lwss", "xq4_6frc"),
+            ("mwss", "xq4_27dee6"),
+            ("hwss", "xq4_27deee6"),
+        ] {
+            let cells = lexicon::lookup(name).unwrap().pattern.cells();
+            let code = apgcode(
+                &cells.iter().map(|&coord| to_point(coord)).collect(),
+                MAX_PERIOD,
+            );
+            assert_eq!(code.as_deref(), Some(expected), "{name}");
+        }
+
+        let r_pentomino = lexicon::lookup("r-pentomino").unwrap().pattern.cells();
+        let r_pentomino = r_pentomino.iter().map(|&coord| to_point(coord));
+        assert_eq!(apgcode(&r_pentomino.collect(), MAX_PERIOD), None);
+
+        // periods up to and including the limit are found
+        let pentadecathlon = lexicon::lookup("pentadecathlon").unwrap().pattern.cells();
+        let pentadecathlon = pentadecathlon
+            .iter()
+            .map(|&coord| to_point(coord))
+            .collect();
+        assert_eq!(
+            apgcode(&pentadecathlon, 15).as_deref(),
+            Some("xp15_4r4z4r4")
+        );
+        assert_eq!(apgcode(&pentadecathlon, 14), None);
+    }
+
+    #[test]
+    fn classify() {
+        for (name, expected) in [
+            ("block", Behavior::StillLife),
+            ("eater 1", Behavior::StillLife),
+            ("blinker", Behavior::Oscillator { period: 2 }),
+            ("pulsar", Behavior::Oscillator { period: 3 }),
+            ("pentadecathlon", Behavior::Oscillator { period: 15 }),
+            ("r-pentomino", Behavior::Unstable),
+        ] {
+            let pattern = lexicon::lookup(name).unwrap().pattern;
+            assert_eq!(super::classify(&pattern), expected, "{name}");
+        }
+
+        let domino = Pattern::new(&[Coord(0, 0), Coord(1, 0)]);
+        assert_eq!(super::classify(&domino), Behavior::Dies { generations: 1 });
+        assert_eq!(
+            super::classify(&Pattern::new(&[])),
+            Behavior::Dies { generations: 0 }
+        );
+    }
+
+    #[test]
+    fn spaceships() {
+        for (name, expected) in [
+            ("glider", "c/4 diagonal"),
+            ("lwss", "c/2 orthogonal"),
+            ("hwss", "c/2 orthogonal"),
+        ] {
+            let pattern = lexicon::lookup(name).unwrap().pattern;
+            let Behavior::Spaceship { velocity } = super::classify(&pattern) else {
+                panic!("{name} is not a spaceship");
+            };
+            assert_eq!(velocity.to_string(), expected, "{name}");
+        }
+
+        let glider = lexicon::lookup("glider").unwrap().pattern;
+        let Behavior::Spaceship { velocity } = super::classify(&glider) else {
+            unreachable!();
+        };
+        assert_eq!(
+            (velocity.dx.abs(), velocity.dy.abs(), velocity.period),
+            (1, 1, 4)
+        );
+        assert!(velocity.is_diagonal() && !velocity.is_orthogonal());
+
+        for (velocity, expected) in [
+            ((2, 0, 5), "2c/5 orthogonal"),
+            ((0, -2, 4), "c/2 orthogonal"),
+            ((2, 1, 6), "c/3 oblique"),
+            ((3, 3, 3), "c diagonal"),
+        ] {
+            let (dx, dy, period) = velocity;
+            assert_eq!(Velocity { dx, dy, period }.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn census_ash() {
+        let mut grid = Grid::<32, 32>::new();
+        for (name, at) in [
+            ("block", Coord(1, 1)),
+            ("block", Coord(4, 1)),
+            ("blinker", Coord(1, 8)),
+            ("pulsar", Coord(12, 12)),
+        ] {
+            grid.paste(&lexicon::lookup(name).unwrap().pattern, at);
+        }
+
+        let census = census(&grid);
+        assert_eq!(census.get("xs4_33"), 2);
+        assert_eq!(census.get("xp2_7"), 1);
+        assert_eq!(
+            census.get("xp3_co9nas0san9oczgoldlo0oldlogz1047210127401"),
+            1
+        );
+        assert_eq!(census.total(), 4);
+    }
+}
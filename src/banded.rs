@@ -0,0 +1,301 @@
+//!
+//! Multi-threaded stepping, with the grid split into horizontal bands.
+//!
+//! See [`BandedGrid`].
+//!
+
+use core::{
+    mem,
+    num::NonZeroUsize,
+    ops::{Index, IndexMut},
+};
+use std::{thread, vec, vec::Vec};
+
+use crate::{
+    cell::Cell,
+    grid::Grid,
+    math::Coord,
+    padded::step_row,
+    rule::{Rule, Topology},
+};
+
+///
+/// A grid split into horizontal bands of rows, each stepped on its own thread.
+///
+/// Every band keeps its own rows, with a ghost row above and below and a
+/// ghost cell at either end of each row, double-buffered so that stepping
+/// does not allocate. Before each step, the bands exchange their edge rows
+/// into each other's ghost rows according to the [`Topology`], so cells at
+/// the seams between bands step exactly like any other cell.
+///
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BandedGrid<const W: usize, const H: usize> {
+    bands: Vec<Band>,
+    /// The first and last rows of every band, gathered while exchanging.
+    edges: Vec<Cell>,
+    pub generation: u64,
+}
+
+/// A band of consecutive rows, surrounded by ghost cells.
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Band {
+    /// The row of the grid this band starts at.
+    start: usize,
+    /// The number of rows in this band, not counting its ghost rows.
+    rows: usize,
+    cells: Vec<Cell>,
+    next: Vec<Cell>,
+}
+
+impl<const W: usize, const H: usize> BandedGrid<W, H> {
+    /// The distance between the starts of consecutive rows, in cells.
+    const STRIDE: usize = W + 2;
+
+    ///
+    /// Construct a new [`BandedGrid`] with all [`Cell::Dead`] cells, split
+    /// into `bands` bands of nearly equal height.
+    ///
+    /// There are never more bands than rows.
+    ///
+    #[must_use]
+    pub fn new(bands: NonZeroUsize) -> Self {
+        let count = bands.get().min(H).max(1);
+        let bands = (0..count)
+            .map(|i| {
+                let [start, end] = [i, i + 1].map(|i| i * H / count);
+                let len = (end - start + 2) * Self::STRIDE;
+                Band {
+                    start,
+                    rows: end - start,
+                    cells: vec![Cell::Dead; len],
+                    next: vec![Cell::Dead; len],
+                }
+            })
+            .collect();
+
+        Self {
+            bands,
+            edges: vec![Cell::Dead; 2 * count * W],
+            generation: 0,
+        }
+    }
+
+    ///
+    /// Construct a new [`BandedGrid`] with all [`Cell::Dead`] cells, with one
+    /// band for each thread the system can run in parallel.
+    ///
+    #[must_use]
+    pub fn with_available_parallelism() -> Self {
+        Self::new(thread::available_parallelism().unwrap_or(NonZeroUsize::MIN))
+    }
+
+    /// Construct a new [`BandedGrid`] from `grid`, split into `bands` bands.
+    #[must_use]
+    pub fn from_grid(grid: &Grid<W, H>, bands: NonZeroUsize) -> Self {
+        let mut banded = Self::new(bands);
+        for band in &mut banded.bands {
+            let rows = &grid.cells[band.start..band.start + band.rows];
+            for (y, row) in rows.iter().enumerate() {
+                band.cells[(y + 1) * Self::STRIDE + 1..][..W].copy_from_slice(row);
+            }
+        }
+        banded.generation = grid.generation;
+        banded
+    }
+
+    /// Returns the number of bands, which is the number of threads used.
+    #[must_use]
+    pub fn bands(&self) -> usize {
+        self.bands.len()
+    }
+
+    ///
+    /// Returns row `y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is out of bounds.
+    ///
+    #[must_use]
+    pub fn row(&self, y: usize) -> &[Cell] {
+        let (band, index) = self.locate(Coord(0, y));
+        &self.bands[band].cells[index..][..W]
+    }
+
+    ///
+    /// Returns the number of bytes this grid takes up, including the buffers
+    /// of all of its bands.
+    ///
+    #[must_use]
+    pub fn memory_footprint(&self) -> usize {
+        let bands = self.bands.iter().map(|band| {
+            size_of::<Band>() + (band.cells.capacity() + band.next.capacity()) * size_of::<Cell>()
+        });
+        size_of::<Self>() + bands.sum::<usize>() + self.edges.capacity() * size_of::<Cell>()
+    }
+
+    ///
+    /// Calculates the next generation of this grid in place according to
+    /// `rule`, with the edges of the grid behaving according to `topology`.
+    ///
+    /// Every band but the first is stepped on a thread of its own, while the
+    /// first is stepped on the calling thread.
+    ///
+    pub fn step_with(&mut self, rule: &(impl Rule + Sync), topology: Topology) {
+        self.exchange(topology);
+
+        thread::scope(|scope| {
+            let (first, rest) = self.bands.split_first_mut().expect("there is a band");
+            for band in rest {
+                scope.spawn(move || band.step::<W>(rule, topology));
+            }
+            first.step::<W>(rule, topology);
+        });
+
+        self.generation += 1;
+    }
+
+    ///
+    /// Fills the ghost rows of every band from the edge rows of its
+    /// neighbors, according to `topology`.
+    ///
+    fn exchange(&mut self, topology: Topology) {
+        let stride = Self::STRIDE;
+        for (i, band) in self.bands.iter().enumerate() {
+            let [first, last] = [1, band.rows].map(|y| &band.cells[y * stride + 1..][..W]);
+            self.edges[2 * i * W..][..W].copy_from_slice(first);
+            self.edges[(2 * i + 1) * W..][..W].copy_from_slice(last);
+        }
+
+        let count = self.bands.len();
+        let wraps = topology == Topology::Torus;
+        for (i, band) in self.bands.iter_mut().enumerate() {
+            let above = (i > 0 || wraps).then(|| (i + count - 1) % count);
+            let below = (i + 1 < count || wraps).then(|| (i + 1) % count);
+
+            let ghost = &mut band.cells[1..][..W];
+            match above {
+                Some(j) => ghost.copy_from_slice(&self.edges[(2 * j + 1) * W..][..W]),
+                None => ghost.fill(Cell::Dead),
+            }
+            let ghost = &mut band.cells[(band.rows + 1) * stride + 1..][..W];
+            match below {
+                Some(j) => ghost.copy_from_slice(&self.edges[2 * j * W..][..W]),
+                None => ghost.fill(Cell::Dead),
+            }
+        }
+    }
+
+    /// Returns the band containing a cell and the cell's index in it.
+    fn locate(&self, Coord(x, y): Coord) -> (usize, usize) {
+        assert!(x < W && y < H, "coordinate out of bounds");
+        let band = self.bands.partition_point(|band| band.start <= y) - 1;
+        let y = y - self.bands[band].start;
+        (band, (y + 1) * Self::STRIDE + x + 1)
+    }
+}
+
+impl Band {
+    ///
+    /// Calculates the next generation of this band, whose ghost rows have
+    /// already been exchanged, with rows `W` cells wide.
+    ///
+    fn step<const W: usize>(&mut self, rule: &impl Rule, topology: Topology) {
+        let stride = W + 2;
+        for row in self.cells.chunks_exact_mut(stride) {
+            match topology {
+                Topology::Bounded => {
+                    row[0] = Cell::Dead;
+                    row[W + 1] = Cell::Dead;
+                }
+                Topology::Torus => {
+                    row[0] = row[W];
+                    row[W + 1] = row[1];
+                }
+            }
+        }
+
+        for y in 1..=self.rows {
+            let rows = [y - 1, y, y + 1].map(|y| &self.cells[y * stride..][..stride]);
+            step_row(rule, rows, &mut self.next[y * stride + 1..][..W]);
+        }
+
+        mem::swap(&mut self.cells, &mut self.next);
+    }
+}
+
+impl<const W: usize, const H: usize> Index<Coord> for BandedGrid<W, H> {
+    type Output = Cell;
+
+    fn index(&self, index: Coord) -> &Self::Output {
+        let (band, index) = self.locate(index);
+        &self.bands[band].cells[index]
+    }
+}
+
+impl<const W: usize, const H: usize> IndexMut<Coord> for BandedGrid<W, H> {
+    fn index_mut(&mut self, index: Coord) -> &mut Self::Output {
+        let (band, index) = self.locate(index);
+        &mut self.bands[band].cells[index]
+    }
+}
+
+impl<const W: usize, const H: usize> From<&BandedGrid<W, H>> for Grid<W, H> {
+    fn from(banded: &BandedGrid<W, H>) -> Self {
+        Self {
+            cells: core::array::from_fn(|y| banded.row(y).try_into().expect("row has W cells")),
+            generation: banded.generation,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{random::SplitMix64, rule::LifeLike};
+
+    #[test]
+    fn step_with() {
+        let mut rng = SplitMix64::new(5);
+        let mut grid = Grid::<21, 11>::new();
+        grid.randomize(0.4, &mut rng);
+
+        for bands in [1, 2, 3, 4, 11, 64] {
+            let bands = NonZeroUsize::new(bands).unwrap();
+            let mut expected = grid.clone();
+            let mut banded = BandedGrid::from_grid(&grid, bands);
+            assert!(banded.bands() <= 11);
+
+            for topology in [Topology::Torus, Topology::Bounded, Topology::Torus] {
+                expected = expected.step_on(&LifeLike::HIGH_LIFE, topology);
+                banded.step_with(&LifeLike::HIGH_LIFE, topology);
+                assert_eq!(Grid::from(&banded).cells, expected.cells);
+            }
+            assert_eq!(banded.generation, 3);
+        }
+
+        let mut grid = Grid::<1, 5>::new();
+        grid[Coord(0, 0)] = Cell::Alive;
+        let mut banded = BandedGrid::from_grid(&grid, NonZeroUsize::new(2).unwrap());
+        for topology in [Topology::Torus, Topology::Bounded, Topology::Torus] {
+            grid = grid.step_on(&LifeLike::HIGH_LIFE, topology);
+            banded.step_with(&LifeLike::HIGH_LIFE, topology);
+            assert_eq!(Grid::from(&banded).cells, grid.cells);
+        }
+    }
+
+    #[test]
+    fn index() {
+        let mut banded = BandedGrid::<4, 5>::new(NonZeroUsize::new(3).unwrap());
+        banded[Coord(3, 4)] = Cell::Alive;
+        banded[Coord(0, 2)] = Cell::Alive;
+
+        let grid = Grid::from(&banded);
+        assert_eq!(grid.population(), 2);
+        assert_eq!(grid[Coord(3, 4)], Cell::Alive);
+        assert_eq!(
+            banded.row(2),
+            [Cell::Alive, Cell::Dead, Cell::Dead, Cell::Dead]
+        );
+    }
+}
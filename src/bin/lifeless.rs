@@ -0,0 +1,119 @@
+use std::{
+    env,
+    error::Error,
+    fs,
+    io::{self, Write},
+    process::ExitCode,
+};
+
+use lifeless::{
+    pattern::Pattern,
+    rule::Topology,
+    runner::{self, Options},
+};
+
+const USAGE: &str = "\
+usage: lifeless [options] <pattern.rle | pattern.cells>
+
+Runs a pattern and writes the result as RLE, with a summary on stderr.
+
+options:
+  -g, --generations <n>  generations to run for [default: 100]
+  -r, --rule <rule>      rule in B/S notation [default: the pattern's, or B3/S23]
+  -t, --torus            wrap around the edges of the board
+  -s, --size <n>         board size: 64, 128, 256 or 512 [default: 256]
+  -o, --output <path>    file to write the result to [default: stdout]
+";
+
+struct Args {
+    path: String,
+    generations: u64,
+    rule: Option<String>,
+    topology: Topology,
+    size: usize,
+    output: Option<String>,
+}
+
+fn parse_args() -> Result<Args, Box<dyn Error>> {
+    let mut args = env::args().skip(1);
+    let mut parsed = Args {
+        path: String::new(),
+        generations: 100,
+        rule: None,
+        topology: Topology::Bounded,
+        size: 256,
+        output: None,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for {arg}"))
+        };
+        match arg.as_str() {
+            "-g" | "--generations" => parsed.generations = value()?.parse()?,
+            "-r" | "--rule" => parsed.rule = Some(value()?),
+            "-t" | "--torus" => parsed.topology = Topology::Torus,
+            "-s" | "--size" => parsed.size = value()?.parse()?,
+            "-o" | "--output" => parsed.output = Some(value()?),
+            "-h" | "--help" => return Err(USAGE.into()),
+            _ if arg.starts_with('-') => return Err(format!("unknown option {arg}").into()),
+            _ => parsed.path = arg,
+        }
+    }
+
+    if parsed.path.is_empty() {
+        return Err(USAGE.into());
+    }
+    Ok(parsed)
+}
+
+fn run<const N: usize>(
+    pattern: &Pattern,
+    options: &Options,
+    args: &Args,
+) -> Result<(), Box<dyn Error>> {
+    let (grid, summary) = runner::run::<N, N>(pattern, options)
+        .ok_or_else(|| format!("pattern does not fit on a {N} by {N} board"))?;
+
+    let mut rle = String::new();
+    grid.write_rle(&mut rle, &options.rule.to_string())?;
+    match &args.output {
+        Some(path) => fs::write(path, rle)?,
+        None => io::stdout().write_all(rle.as_bytes())?,
+    }
+
+    eprint!("{summary}");
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let result = parse_args().and_then(|args| {
+        let input = runner::load(&fs::read_to_string(&args.path)?)?;
+        let options = Options {
+            rule: match &args.rule {
+                Some(rule) => rule.parse()?,
+                None => input.rule.unwrap_or_default(),
+            },
+            topology: args.topology,
+            generations: args.generations,
+        };
+
+        let pattern = input.pattern.as_pattern();
+        match args.size {
+            64 => run::<64>(&pattern, &options, &args),
+            128 => run::<128>(&pattern, &options, &args),
+            256 => run::<256>(&pattern, &options, &args),
+            512 => run::<512>(&pattern, &options, &args),
+            size => Err(format!("unsupported board size {size}").into()),
+        }
+    });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}", err.to_string().trim_end());
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use core::ops::Not;
+use core::{
+    fmt::{self, Write},
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not},
+};
 
 ///
 /// A cell on a grid.
@@ -11,6 +14,212 @@ pub enum Cell {
     Dead = 0,
 }
 
+///
+/// The state of a cell on a grid.
+///
+/// Rules count neighbors and grids count their population by whether cells
+/// are alive, but states can carry any extra data, such as the age of a
+/// cell or the number of states of a multi-state rule.
+///
+pub trait CellState: Copy {
+    /// The state that new grids are filled with.
+    const DEAD: Self;
+
+    /// Returns whether this cell counts as alive.
+    fn is_alive(self) -> bool;
+}
+
+impl CellState for Cell {
+    const DEAD: Self = Self::Dead;
+
+    fn is_alive(self) -> bool {
+        self == Self::Alive
+    }
+}
+
+///
+/// A cell of a multi-state rule, such as a Golly rule table or tree, in one
+/// of up to 256 states.
+///
+/// State 0 is dead, and every other state counts as alive.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct State(pub u8);
+
+impl State {
+    /// Returns whether this cell is alive or dead, ignoring its state.
+    #[must_use]
+    pub const fn cell(self) -> Cell {
+        if self.0 == 0 {
+            Cell::Dead
+        } else {
+            Cell::Alive
+        }
+    }
+}
+
+impl CellState for State {
+    const DEAD: Self = Self(0);
+
+    fn is_alive(self) -> bool {
+        self.0 != 0
+    }
+}
+
+impl From<Cell> for State {
+    /// Converts [`Cell::Dead`] to state 0 and [`Cell::Alive`] to state 1.
+    fn from(cell: Cell) -> Self {
+        Self(cell as u8)
+    }
+}
+
+impl Cell {
+    ///
+    /// Calculate the state of this cell in the next generation, given its
+    /// number of live neighbors.
+    ///
+    /// Rules are in accordance to
+    /// [the Wiki page](https://www.wikiwand.com/en/Conway's_Game_of_Life).
+    ///
+    #[must_use]
+    pub(crate) fn next(self, alive_neighbors: usize) -> Self {
+        match alive_neighbors {
+            0 | 1 | 4.. => Self::Dead,
+            2 => self,
+            3 => Self::Alive,
+        }
+    }
+}
+
+impl Cell {
+    /// Returns the character for this cell in `charset`.
+    #[must_use]
+    pub const fn to_char(self, charset: Charset) -> char {
+        match self {
+            Self::Alive => charset.alive,
+            Self::Dead => charset.dead,
+        }
+    }
+
+    ///
+    /// Returns the cell for `c` in `charset`, or `None` if `c` is not in
+    /// `charset`.
+    ///
+    #[must_use]
+    pub const fn from_char(c: char, charset: Charset) -> Option<Self> {
+        if c == charset.alive {
+            Some(Self::Alive)
+        } else if c == charset.dead {
+            Some(Self::Dead)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Cell {
+    /// Displays cells in the default [`Charset`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char(self.to_char(Charset::default()))
+    }
+}
+
+///
+/// Implements a bitwise operator for cells, and for cells with `bool`s, with
+/// live cells as `true` and dead cells as `false`.
+///
+macro_rules! bit_op {
+    ($($op:ident::$f:ident, $assign:ident::$assign_f:ident;)*) => {$(
+        impl $op<bool> for Cell {
+            type Output = Self;
+
+            fn $f(self, rhs: bool) -> Self::Output {
+                Self::from(bool::from(self).$f(rhs))
+            }
+        }
+
+        impl $op for Cell {
+            type Output = Self;
+
+            fn $f(self, rhs: Self) -> Self::Output {
+                self.$f(bool::from(rhs))
+            }
+        }
+
+        impl $assign<bool> for Cell {
+            fn $assign_f(&mut self, rhs: bool) {
+                *self = self.$f(rhs);
+            }
+        }
+
+        impl $assign for Cell {
+            fn $assign_f(&mut self, rhs: Self) {
+                *self = self.$f(rhs);
+            }
+        }
+    )*};
+}
+
+bit_op! {
+    BitAnd::bitand, BitAndAssign::bitand_assign;
+    BitOr::bitor, BitOrAssign::bitor_assign;
+    BitXor::bitxor, BitXorAssign::bitxor_assign;
+}
+
+///
+/// The characters for live and dead cells in text.
+///
+/// The default is [`Charset::HASH`], which is used by all text formats of
+/// this crate other than plaintext patterns.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Charset {
+    pub alive: char,
+    pub dead: char,
+}
+
+impl Charset {
+    /// `#` for live cells and `.` for dead cells.
+    pub const HASH: Self = Self {
+        alive: '#',
+        dead: '.',
+    };
+    /// `O` for live cells and `.` for dead cells, as in plaintext patterns.
+    pub const PLAINTEXT: Self = Self {
+        alive: 'O',
+        dead: '.',
+    };
+    /// `█` for live cells and `░` for dead cells.
+    pub const BLOCKS: Self = Self {
+        alive: '█',
+        dead: '░',
+    };
+}
+
+impl Default for Charset {
+    fn default() -> Self {
+        Self::HASH
+    }
+}
+
+impl From<bool> for Cell {
+    /// Converts `true` to [`Cell::Alive`] and `false` to [`Cell::Dead`].
+    fn from(alive: bool) -> Self {
+        if alive {
+            Self::Alive
+        } else {
+            Self::Dead
+        }
+    }
+}
+
+impl From<Cell> for bool {
+    /// Returns whether `cell` is [`Cell::Alive`].
+    fn from(cell: Cell) -> Self {
+        cell == Cell::Alive
+    }
+}
+
 impl Not for Cell {
     type Output = Self;
 
@@ -21,3 +230,36 @@ impl Not for Cell {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn chars() {
+        for charset in [Charset::HASH, Charset::PLAINTEXT, Charset::BLOCKS] {
+            for cell in [Cell::Alive, Cell::Dead] {
+                assert_eq!(Cell::from_char(cell.to_char(charset), charset), Some(cell));
+            }
+        }
+        assert_eq!(Cell::from_char('O', Charset::HASH), None);
+        assert_eq!(Cell::Alive.to_char(Charset::default()), '#');
+    }
+
+    #[test]
+    fn bit_ops() {
+        use Cell::{Alive, Dead};
+
+        assert_eq!(Alive & Dead, Dead);
+        assert_eq!(Alive | Dead, Alive);
+        assert_eq!(Alive ^ Alive, Dead);
+        assert_eq!(Dead ^ true, Alive);
+
+        let mut cell = Dead;
+        cell |= Alive;
+        cell &= true;
+        assert_eq!(cell, Alive);
+        cell ^= Alive;
+        assert_eq!(cell, Dead);
+    }
+}
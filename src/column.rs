@@ -0,0 +1,157 @@
+//!
+//! A column-major grid layout, for column-oriented displays.
+//!
+//! See [`ColumnGrid`].
+//!
+
+use core::ops::{Index, IndexMut};
+
+use crate::{
+    cell::Cell,
+    grid::Grid,
+    math::Coord,
+    rule::{Life, Rule, Topology},
+};
+
+///
+/// A grid of `W` by `H` cells stored column by column, rather than row by
+/// row like a [`Grid`].
+///
+/// Many OLED controllers and DMA engines expect pixels column by column, so
+/// stepping a grid in this layout saves transposing it every frame. Packing
+/// [`ColumnGrid::as_transposed()`] with
+/// [`BitLayout::RowMajorLsbFirst`](crate::render::BitLayout::RowMajorLsbFirst)
+/// gives each column as whole bytes with the top pixel in the least
+/// significant bit.
+///
+/// The cells are stored as a `Grid<H, W>` with the axes swapped. Rules only
+/// count live neighbors, which does not depend on the layout, so that grid
+/// is stepped directly.
+///
+#[derive(Clone, Debug, Default)]
+pub struct ColumnGrid<const W: usize, const H: usize> {
+    transposed: Grid<H, W>,
+}
+
+impl<const W: usize, const H: usize> ColumnGrid<W, H> {
+    /// Construct a new [`ColumnGrid`] with all [`Cell::Dead`] cells.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            transposed: Grid::new(),
+        }
+    }
+
+    /// Returns the generation of this grid.
+    #[must_use]
+    pub const fn generation(&self) -> u64 {
+        self.transposed.generation
+    }
+
+    /// Returns the columns of this grid, from left to right.
+    #[must_use]
+    pub const fn columns(&self) -> &[[Cell; H]; W] {
+        &self.transposed.cells
+    }
+
+    /// Returns the columns of this grid mutably, from left to right.
+    #[must_use]
+    pub fn columns_mut(&mut self) -> &mut [[Cell; H]; W] {
+        &mut self.transposed.cells
+    }
+
+    ///
+    /// Returns this grid's cells as a grid with the axes swapped, whose rows
+    /// are this grid's columns.
+    ///
+    #[must_use]
+    pub const fn as_transposed(&self) -> &Grid<H, W> {
+        &self.transposed
+    }
+
+    /// Calculates the next generation of this grid.
+    #[must_use]
+    pub fn step(&self) -> Self {
+        self.step_with(&Life)
+    }
+
+    /// Calculates the next generation of this grid according to `rule`.
+    #[must_use]
+    pub fn step_with(&self, rule: &impl Rule) -> Self {
+        Self {
+            transposed: self.transposed.step_with(rule),
+        }
+    }
+
+    ///
+    /// Calculates the next generation of this grid according to `rule`, with
+    /// the edges of the grid behaving according to `topology`.
+    ///
+    #[must_use]
+    pub fn step_on(&self, rule: &impl Rule, topology: Topology) -> Self {
+        Self {
+            transposed: self.transposed.step_on(rule, topology),
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Index<Coord> for ColumnGrid<W, H> {
+    type Output = Cell;
+
+    fn index(&self, Coord(x, y): Coord) -> &Self::Output {
+        &self.transposed[Coord(y, x)]
+    }
+}
+
+impl<const W: usize, const H: usize> IndexMut<Coord> for ColumnGrid<W, H> {
+    fn index_mut(&mut self, Coord(x, y): Coord) -> &mut Self::Output {
+        &mut self.transposed[Coord(y, x)]
+    }
+}
+
+impl<const W: usize, const H: usize> From<&Grid<W, H>> for ColumnGrid<W, H> {
+    fn from(grid: &Grid<W, H>) -> Self {
+        Self {
+            transposed: grid.transpose(),
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> From<&ColumnGrid<W, H>> for Grid<W, H> {
+    fn from(columns: &ColumnGrid<W, H>) -> Self {
+        columns.transposed.transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{random::SplitMix64, rule::LifeLike};
+
+    #[test]
+    fn step() {
+        let mut rng = SplitMix64::new(7);
+        let mut grid = Grid::<12, 7>::new();
+        grid.randomize(0.4, &mut rng);
+
+        let mut columns = ColumnGrid::from(&grid);
+        assert_eq!(columns.columns()[3][5], grid[Coord(3, 5)]);
+        for topology in [Topology::Bounded, Topology::Torus] {
+            grid = grid.step_on(&LifeLike::HIGH_LIFE, topology);
+            columns = columns.step_on(&LifeLike::HIGH_LIFE, topology);
+            assert_eq!(Grid::from(&columns).cells, grid.cells);
+        }
+        assert_eq!(columns.step().generation(), 3);
+
+        columns[Coord(11, 0)] = Cell::Alive;
+        assert_eq!(columns.columns()[11][0], Cell::Alive);
+        let mut grid = Grid::<1, 5>::new();
+        grid[Coord(0, 0)] = Cell::Alive;
+        let mut columns = ColumnGrid::from(&grid);
+        for topology in [Topology::Torus, Topology::Bounded, Topology::Torus] {
+            grid = grid.step_on(&LifeLike::HIGH_LIFE, topology);
+            columns = columns.step_on(&LifeLike::HIGH_LIFE, topology);
+            assert_eq!(Grid::from(&columns).cells, grid.cells);
+        }
+    }
+}
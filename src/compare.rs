@@ -0,0 +1,210 @@
+//!
+//! Running several engines or rules side by side, to find where they
+//! diverge.
+//!
+//! See [`Engine`], [`Stepped`] and [`lockstep()`].
+//!
+
+#[cfg(feature = "std")]
+use crate::banded::BandedGrid;
+#[cfg(feature = "alloc")]
+use crate::padded::PaddedGrid;
+use crate::{
+    column::ColumnGrid,
+    grid::Grid,
+    math::Coord,
+    rule::{Rule, Topology},
+};
+
+///
+/// Something that steps a `W` by `H` grid, such as one of the grid layouts
+/// under a rule.
+///
+pub trait Engine<const W: usize, const H: usize> {
+    /// Steps the grid by one generation.
+    fn step(&mut self);
+
+    /// Returns the current grid.
+    fn to_grid(&self) -> Grid<W, H>;
+}
+
+///
+/// A grid layout stepped under `rule`, with the edges of the grid behaving
+/// according to `topology`.
+///
+/// This is the [`Engine`] for each of the grid layouts in this crate.
+///
+#[derive(Clone, Debug)]
+pub struct Stepped<G, R> {
+    pub grid: G,
+    pub rule: R,
+    pub topology: Topology,
+}
+
+impl<G, R> Stepped<G, R> {
+    /// Construct a new [`Stepped`] engine.
+    #[must_use]
+    pub const fn new(grid: G, rule: R, topology: Topology) -> Self {
+        Self {
+            grid,
+            rule,
+            topology,
+        }
+    }
+}
+
+impl<const W: usize, const H: usize, R: Rule> Engine<W, H> for Stepped<Grid<W, H>, R> {
+    fn step(&mut self) {
+        self.grid = self.grid.step_on(&self.rule, self.topology);
+    }
+
+    fn to_grid(&self) -> Grid<W, H> {
+        self.grid.clone()
+    }
+}
+
+impl<const W: usize, const H: usize, R: Rule> Engine<W, H> for Stepped<ColumnGrid<W, H>, R> {
+    fn step(&mut self) {
+        self.grid = self.grid.step_on(&self.rule, self.topology);
+    }
+
+    fn to_grid(&self) -> Grid<W, H> {
+        Grid::from(&self.grid)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const W: usize, const H: usize, R: Rule> Engine<W, H> for Stepped<PaddedGrid<W, H>, R> {
+    fn step(&mut self) {
+        self.grid.step_with(&self.rule, self.topology);
+    }
+
+    fn to_grid(&self) -> Grid<W, H> {
+        Grid::from(&self.grid)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const W: usize, const H: usize, R: Rule + Sync> Engine<W, H> for Stepped<BandedGrid<W, H>, R> {
+    fn step(&mut self) {
+        self.grid.step_with(&self.rule, self.topology);
+    }
+
+    fn to_grid(&self) -> Grid<W, H> {
+        Grid::from(&self.grid)
+    }
+}
+
+///
+/// Where and when an engine first diverged from the first engine, as found
+/// by [`lockstep()`].
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Divergence {
+    /// The number of generations stepped before the grids differed.
+    pub generation: u64,
+    /// The index of the engine that differed from the first one.
+    pub engine: usize,
+    /// The first differing cell, in row-major order.
+    pub coord: Coord,
+    /// The number of differing cells.
+    pub cells: usize,
+}
+
+///
+/// Steps every engine in `engines` in lockstep for up to `max_gens`
+/// generations, comparing each against the first after every step.
+///
+/// Returns the first [`Divergence`] found, or [`None`] if all engines
+/// agreed throughout. The engines are compared before stepping too, so
+/// engines that start from different grids diverge at generation 0.
+///
+pub fn lockstep<const W: usize, const H: usize>(
+    engines: &mut [&mut dyn Engine<W, H>],
+    max_gens: u64,
+) -> Option<Divergence> {
+    for generation in 0..=max_gens {
+        if generation > 0 {
+            for engine in engines.iter_mut() {
+                engine.step();
+            }
+        }
+
+        let (first, rest) = engines.split_first()?;
+        let expected = first.to_grid();
+        for (i, engine) in rest.iter().enumerate() {
+            let grid = engine.to_grid();
+            let mut diff = expected.diff(&grid);
+            if let Some((coord, _)) = diff.next() {
+                return Some(Divergence {
+                    generation,
+                    engine: i + 1,
+                    coord,
+                    cells: diff.count() + 1,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        cell::Cell,
+        random::SplitMix64,
+        rule::{Life, LifeLike},
+    };
+
+    #[test]
+    fn lockstep() {
+        let mut rng = SplitMix64::new(8);
+        let mut grid = Grid::<10, 9>::new();
+        grid.randomize(0.4, &mut rng);
+
+        let mut naive = Stepped::new(grid.clone(), Life, Topology::Torus);
+        let mut columns = Stepped::new(ColumnGrid::from(&grid), Life, Topology::Torus);
+        assert_eq!(super::lockstep(&mut [&mut naive, &mut columns], 20), None);
+        assert_eq!(naive.grid.generation, 20);
+
+        // HighLife only differs from Life by births on 6 neighbors
+        let mut grid = Grid::<10, 9>::new();
+        for coord in [
+            Coord(1, 1),
+            Coord(2, 1),
+            Coord(3, 1),
+            Coord(1, 2),
+            Coord(2, 3),
+            Coord(3, 3),
+        ] {
+            grid[coord] = Cell::Alive;
+        }
+        let mut life = Stepped::new(grid.clone(), LifeLike::CONWAY, Topology::Bounded);
+        let mut high_life = Stepped::new(grid, LifeLike::HIGH_LIFE, Topology::Bounded);
+        let divergence = super::lockstep(&mut [&mut life, &mut high_life], 20).unwrap();
+        assert_eq!(divergence.generation, 1);
+        assert_eq!(divergence.engine, 1);
+        assert_eq!(divergence.coord, Coord(2, 2));
+        assert_eq!(divergence.cells, 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn lockstep_engines() {
+        use core::num::NonZeroUsize;
+
+        let mut rng = SplitMix64::new(9);
+        let mut grid = Grid::<12, 11>::new();
+        grid.randomize(0.4, &mut rng);
+
+        let topology = Topology::Bounded;
+        let bands = NonZeroUsize::new(3).unwrap();
+        let mut naive = Stepped::new(grid.clone(), Life, topology);
+        let mut padded = Stepped::new(PaddedGrid::from(&grid), Life, topology);
+        let mut banded = Stepped::new(BandedGrid::from_grid(&grid, bands), Life, topology);
+        let engines: &mut [&mut dyn Engine<12, 11>] = &mut [&mut naive, &mut padded, &mut banded];
+        assert_eq!(super::lockstep(engines, 16), None);
+    }
+}
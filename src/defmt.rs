@@ -0,0 +1,41 @@
+//!
+//! [`defmt`] support for [`Cell`], [`Coord`] and [`Grid`].
+//!
+//! - [`Cell`]s are formatted as `#` or `.`.
+//! - [`Coord`]s are formatted as `(x, y)`.
+//! - [`Grid`]s are formatted as a one-line summary of their extents,
+//!   generation and population, as whole grids are too large to log.
+//!
+
+use ::defmt::{write, Format, Formatter};
+
+use crate::{
+    cell::{Cell, Charset},
+    grid::Grid,
+    math::Coord,
+};
+
+impl Format for Cell {
+    fn format(&self, f: Formatter<'_>) {
+        write!(f, "{=char}", self.to_char(Charset::default()));
+    }
+}
+
+impl Format for Coord {
+    fn format(&self, f: Formatter<'_>) {
+        write!(f, "({=usize}, {=usize})", self.0, self.1);
+    }
+}
+
+impl<const W: usize, const H: usize> Format for Grid<W, H> {
+    fn format(&self, f: Formatter<'_>) {
+        write!(
+            f,
+            "Grid {=usize}x{=usize} generation {=u64} population {=usize}",
+            W,
+            H,
+            self.generation,
+            self.population()
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//!
+//! Distances between cells, and distance transforms of grids.
+//!
+//! See [`Metric`] and [`Grid::distance_transform()`].
+//!
+
+use crate::{cell::CellState, grid::Grid, math::Coord};
+
+///
+/// A way of measuring the distance between two cells.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Metric {
+    /// The number of king moves between the cells, counting diagonals as 1.
+    #[default]
+    Chebyshev,
+    /// The number of orthogonal moves between the cells.
+    Manhattan,
+}
+
+impl Metric {
+    /// Returns the distance between `a` and `b`.
+    #[must_use]
+    pub const fn distance(self, Coord(ax, ay): Coord, Coord(bx, by): Coord) -> usize {
+        let (dx, dy) = (ax.abs_diff(bx), ay.abs_diff(by));
+        match self {
+            Self::Chebyshev => {
+                if dx > dy {
+                    dx
+                } else {
+                    dy
+                }
+            }
+            Self::Manhattan => dx + dy,
+        }
+    }
+}
+
+impl<const W: usize, const H: usize, C: CellState> Grid<W, H, C> {
+    ///
+    /// Writes the distance from every cell to the nearest live cell into
+    /// `out`, measured with `metric`.
+    ///
+    /// Live cells are at distance 0. If there are no live cells, every
+    /// distance is [`usize::MAX`].
+    ///
+    /// This takes two passes over the grid, rather than searching around
+    /// every cell.
+    ///
+    pub fn distance_transform(&self, metric: Metric, out: &mut [[usize; W]; H]) {
+        let diagonals = metric == Metric::Chebyshev;
+        let step = |distance: usize| distance.saturating_add(1);
+
+        // forward pass, from the top-left neighbors
+        for y in 0..H {
+            for x in 0..W {
+                let mut distance = if self.cells[y][x].is_alive() {
+                    0
+                } else {
+                    usize::MAX
+                };
+                if x > 0 {
+                    distance = distance.min(step(out[y][x - 1]));
+                }
+                if y > 0 {
+                    distance = distance.min(step(out[y - 1][x]));
+                    if diagonals && x > 0 {
+                        distance = distance.min(step(out[y - 1][x - 1]));
+                    }
+                    if diagonals && x + 1 < W {
+                        distance = distance.min(step(out[y - 1][x + 1]));
+                    }
+                }
+                out[y][x] = distance;
+            }
+        }
+
+        // backward pass, from the bottom-right neighbors
+        for y in (0..H).rev() {
+            for x in (0..W).rev() {
+                let mut distance = out[y][x];
+                if x + 1 < W {
+                    distance = distance.min(step(out[y][x + 1]));
+                }
+                if y + 1 < H {
+                    distance = distance.min(step(out[y + 1][x]));
+                    if diagonals && x + 1 < W {
+                        distance = distance.min(step(out[y + 1][x + 1]));
+                    }
+                    if diagonals && x > 0 {
+                        distance = distance.min(step(out[y + 1][x - 1]));
+                    }
+                }
+                out[y][x] = distance;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::SplitMix64;
+
+    #[test]
+    fn distance_transform() {
+        let mut rng = SplitMix64::new(3);
+        let mut grid = Grid::<9, 7>::new();
+        let mut out = [[0; 9]; 7];
+        grid.distance_transform(Metric::Manhattan, &mut out);
+        assert!(out.iter().flatten().all(|&distance| distance == usize::MAX));
+
+        grid.randomize(0.1, &mut rng);
+        let live: [Coord; 63] = core::array::from_fn(|i| Coord(i % 9, i / 9));
+        let live = live.iter().filter(|&&coord| grid[coord].is_alive());
+
+        for metric in [Metric::Chebyshev, Metric::Manhattan] {
+            grid.distance_transform(metric, &mut out);
+            for (y, row) in out.iter().enumerate() {
+                for (x, &distance) in row.iter().enumerate() {
+                    let expected = live
+                        .clone()
+                        .map(|&coord| metric.distance(coord, Coord(x, y)))
+                        .min();
+                    assert_eq!(Some(distance), expected);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,264 @@
+//!
+//! C bindings for embedding grids in C firmware, game engines, and other
+//! languages with a C FFI.
+//!
+//! C has no generics, so a grid of a given size is exported with
+//! [`ffi_grid!`](crate::ffi_grid), once per library:
+//!
+//! ```
+//! lifeless::ffi_grid!(64, 48);
+//! ```
+//!
+//! The exported functions are declared in `include/lifeless.h`. Handles
+//! are owned by the caller, and must be freed with `lifeless_destroy`.
+//! Every function accepts a null handle, doing nothing.
+//!
+
+#![allow(unsafe_code)]
+
+///
+/// Export a grid of a fixed size through `extern "C"` functions, as
+/// declared in `include/lifeless.h`.
+///
+/// See the [module documentation](crate::ffi).
+///
+#[macro_export]
+macro_rules! ffi_grid {
+    ($w:expr, $h:expr) => {
+        /// Returns a new empty grid, to be freed with `lifeless_destroy`.
+        #[no_mangle]
+        pub extern "C" fn lifeless_create() -> *mut $crate::Grid<{ $w }, { $h }> {
+            $crate::ffi::__private::create()
+        }
+
+        /// Frees a grid returned by `lifeless_create`.
+        ///
+        /// # Safety
+        ///
+        /// `grid` must be null or returned by `lifeless_create`, and not
+        /// used after this call.
+        #[no_mangle]
+        pub unsafe extern "C" fn lifeless_destroy(grid: *mut $crate::Grid<{ $w }, { $h }>) {
+            unsafe { $crate::ffi::__private::destroy(grid) }
+        }
+
+        /// Returns the width of grids, in cells.
+        #[no_mangle]
+        pub extern "C" fn lifeless_width() -> usize {
+            $w
+        }
+
+        /// Returns the height of grids, in cells.
+        #[no_mangle]
+        pub extern "C" fn lifeless_height() -> usize {
+            $h
+        }
+
+        /// Returns the generation of a grid.
+        ///
+        /// # Safety
+        ///
+        /// `grid` must be null or a live grid returned by `lifeless_create`.
+        #[no_mangle]
+        pub unsafe extern "C" fn lifeless_generation(
+            grid: *const $crate::Grid<{ $w }, { $h }>,
+        ) -> u64 {
+            unsafe { grid.as_ref() }.map_or(0, |grid| grid.generation)
+        }
+
+        /// Steps a grid by `generations` generations.
+        ///
+        /// # Safety
+        ///
+        /// `grid` must be null or a live grid returned by `lifeless_create`.
+        #[no_mangle]
+        pub unsafe extern "C" fn lifeless_step(
+            grid: *mut $crate::Grid<{ $w }, { $h }>,
+            generations: u32,
+        ) {
+            if let Some(grid) = unsafe { grid.as_mut() } {
+                $crate::ffi::__private::step(grid, generations);
+            }
+        }
+
+        /// Returns whether a cell is alive, or `false` outside the grid.
+        ///
+        /// # Safety
+        ///
+        /// `grid` must be null or a live grid returned by `lifeless_create`.
+        #[no_mangle]
+        pub unsafe extern "C" fn lifeless_get_cell(
+            grid: *const $crate::Grid<{ $w }, { $h }>,
+            x: usize,
+            y: usize,
+        ) -> bool {
+            unsafe { grid.as_ref() }
+                .and_then(|grid| grid.get($crate::Coord(x, y)))
+                .is_some_and(|&cell| cell.into())
+        }
+
+        /// Sets whether a cell is alive, doing nothing outside the grid.
+        ///
+        /// # Safety
+        ///
+        /// `grid` must be null or a live grid returned by `lifeless_create`.
+        #[no_mangle]
+        pub unsafe extern "C" fn lifeless_set_cell(
+            grid: *mut $crate::Grid<{ $w }, { $h }>,
+            x: usize,
+            y: usize,
+            alive: bool,
+        ) {
+            if let Some(cell) =
+                unsafe { grid.as_mut() }.and_then(|grid| grid.get_mut($crate::Coord(x, y)))
+            {
+                *cell = alive.into();
+            }
+        }
+
+        /// Writes a grid in the wire format to `buf` if it is at least as
+        /// long as needed, returning the length needed.
+        ///
+        /// # Safety
+        ///
+        /// `grid` must be null or a live grid returned by `lifeless_create`,
+        /// and `buf` must be null or valid for writes of `len` bytes.
+        #[no_mangle]
+        pub unsafe extern "C" fn lifeless_serialize(
+            grid: *const $crate::Grid<{ $w }, { $h }>,
+            buf: *mut u8,
+            len: usize,
+        ) -> usize {
+            unsafe { $crate::ffi::__private::serialize(grid, buf, len) }
+        }
+
+        /// Replaces a grid with one in the wire format, returning whether
+        /// the bytes were a valid grid of this size.
+        ///
+        /// # Safety
+        ///
+        /// `grid` must be null or a live grid returned by `lifeless_create`,
+        /// and `bytes` must be null or valid for reads of `len` bytes.
+        #[no_mangle]
+        pub unsafe extern "C" fn lifeless_deserialize(
+            grid: *mut $crate::Grid<{ $w }, { $h }>,
+            bytes: *const u8,
+            len: usize,
+        ) -> bool {
+            unsafe { $crate::ffi::__private::deserialize(grid, bytes, len) }
+        }
+    };
+}
+
+#[doc(hidden)]
+pub mod __private {
+    use alloc::boxed::Box;
+    use core::slice;
+
+    use crate::grid::Grid;
+
+    pub fn create<const W: usize, const H: usize>() -> *mut Grid<W, H> {
+        Box::into_raw(Box::new(Grid::new()))
+    }
+
+    ///
+    /// # Safety
+    ///
+    /// `grid` must be null or returned by [`create()`], and not used after.
+    ///
+    pub unsafe fn destroy<const W: usize, const H: usize>(grid: *mut Grid<W, H>) {
+        if !grid.is_null() {
+            drop(unsafe { Box::from_raw(grid) });
+        }
+    }
+
+    pub fn step<const W: usize, const H: usize>(grid: &mut Grid<W, H>, generations: u32) {
+        for _ in 0..generations {
+            *grid = grid.step();
+        }
+    }
+
+    ///
+    /// # Safety
+    ///
+    /// `grid` must be null or valid for reads, and `buf` must be null or
+    /// valid for writes of `len` bytes.
+    ///
+    pub unsafe fn serialize<const W: usize, const H: usize>(
+        grid: *const Grid<W, H>,
+        buf: *mut u8,
+        len: usize,
+    ) -> usize {
+        let needed = Grid::<W, H>::ENCODED_LEN;
+        if let Some(grid) = unsafe { grid.as_ref() } {
+            if !buf.is_null() && len >= needed {
+                let buf = unsafe { slice::from_raw_parts_mut(buf, len) };
+                let _ = grid.to_bytes(buf);
+            }
+        }
+        needed
+    }
+
+    ///
+    /// # Safety
+    ///
+    /// `grid` must be null or valid for writes, and `bytes` must be null or
+    /// valid for reads of `len` bytes.
+    ///
+    pub unsafe fn deserialize<const W: usize, const H: usize>(
+        grid: *mut Grid<W, H>,
+        bytes: *const u8,
+        len: usize,
+    ) -> bool {
+        let Some(grid) = (unsafe { grid.as_mut() }) else {
+            return false;
+        };
+        if bytes.is_null() {
+            return false;
+        }
+
+        match Grid::from_bytes(unsafe { slice::from_raw_parts(bytes, len) }) {
+            Ok(bytes) => {
+                *grid = bytes;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::ptr;
+
+    crate::ffi_grid!(4, 3);
+
+    #[test]
+    fn ffi() {
+        let grid = lifeless_create();
+        assert_eq!((lifeless_width(), lifeless_height()), (4, 3));
+
+        unsafe {
+            for x in 0..3 {
+                lifeless_set_cell(grid, x, 1, true);
+            }
+            lifeless_set_cell(grid, 9, 9, true);
+
+            let mut buf = [0; 32];
+            let len = lifeless_serialize(grid, buf.as_mut_ptr(), buf.len());
+            assert_eq!(lifeless_serialize(grid, ptr::null_mut(), 0), len);
+
+            lifeless_step(grid, 1);
+            assert!(lifeless_get_cell(grid, 1, 0) && !lifeless_get_cell(grid, 0, 1));
+            assert_eq!(lifeless_generation(grid), 1);
+
+            assert!(lifeless_deserialize(grid, buf.as_ptr(), len));
+            assert!(!lifeless_deserialize(grid, buf.as_ptr(), len - 1));
+            assert!(lifeless_get_cell(grid, 0, 1) && lifeless_generation(grid) == 0);
+
+            lifeless_destroy(grid);
+            lifeless_destroy(ptr::null_mut());
+            assert!(!lifeless_get_cell(ptr::null(), 0, 0));
+        }
+    }
+}
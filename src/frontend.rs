@@ -0,0 +1,12 @@
+//!
+//! Ready-made frontends for running grids interactively.
+//!
+//! See:
+//! - `terminal` for terminals, with the `crossterm` feature.
+//! - `window` for desktop windows, with the `minifb` feature.
+//!
+
+#[cfg(feature = "crossterm")]
+pub mod terminal;
+#[cfg(feature = "minifb")]
+pub mod window;
@@ -0,0 +1,303 @@
+//!
+//! A terminal frontend built on [`crossterm`].
+//!
+//! See [`Terminal`] and [`Speed`].
+//!
+
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    queue,
+    style::{Attribute, Print, SetAttribute},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use crate::{cell::Cell, grid::Grid, math::Coord};
+
+///
+/// A terminal that grids are drawn onto with differential redraws: after
+/// the first frame, only the cells that changed since the previous frame
+/// are written, so that playback does not flicker on slow terminals.
+///
+/// Cells are drawn with one glyph each, `██` and `░░` by default. Glyphs
+/// are assumed to take one column per character. One cell can be
+/// highlighted in reverse video, such as for an editing cursor, and a
+/// status line can be shown below the grid.
+///
+#[derive(Debug)]
+pub struct Terminal<'a, T: Write, const W: usize, const H: usize> {
+    writer: T,
+    alive: &'a str,
+    dead: &'a str,
+    prev: Option<Grid<W, H>>,
+    highlight: Option<Coord>,
+    /// The highlighted cell as of the last frame.
+    prev_highlight: Option<Coord>,
+    entered: bool,
+}
+
+impl<'a, T: Write, const W: usize, const H: usize> Terminal<'a, T, W, H> {
+    /// Construct a new [`Terminal`] writing to `writer`.
+    #[must_use]
+    pub const fn new(writer: T) -> Self {
+        Self {
+            writer,
+            alive: "██",
+            dead: "░░",
+            prev: None,
+            highlight: None,
+            prev_highlight: None,
+            entered: false,
+        }
+    }
+
+    ///
+    /// Draws live cells with `alive` and dead cells with `dead`, which
+    /// should take the same number of columns.
+    ///
+    #[must_use]
+    pub fn glyphs(mut self, alive: &'a str, dead: &'a str) -> Self {
+        self.alive = alive;
+        self.dead = dead;
+        self.prev = None;
+        self
+    }
+
+    ///
+    /// Switch to raw mode and the alternate screen, hiding the cursor.
+    ///
+    /// The terminal is restored by [`Self::leave()`], or when this is
+    /// dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while writing or changing modes.
+    ///
+    pub fn enter(&mut self) -> io::Result<()> {
+        terminal::enable_raw_mode()?;
+        self.entered = true;
+        self.prev = None;
+        queue!(
+            self.writer,
+            EnterAlternateScreen,
+            Hide,
+            Clear(ClearType::All)
+        )?;
+        self.writer.flush()
+    }
+
+    ///
+    /// Restore the terminal switched by [`Self::enter()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while writing or changing modes.
+    ///
+    pub fn leave(&mut self) -> io::Result<()> {
+        if !self.entered {
+            return Ok(());
+        }
+        self.entered = false;
+        queue!(self.writer, Show, LeaveAlternateScreen)?;
+        self.writer.flush()?;
+        terminal::disable_raw_mode()
+    }
+
+    ///
+    /// Draw `grid`, only writing the cells that changed since the last
+    /// frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while writing.
+    ///
+    pub fn draw(&mut self, grid: &Grid<W, H>) -> io::Result<()> {
+        let width = self.alive.chars().count().max(self.dead.chars().count());
+        let moved = self.highlight != self.prev_highlight;
+        let mut position = None;
+
+        for y in 0..H {
+            for x in 0..W {
+                let coord = Coord(x, y);
+                let cell = grid[coord];
+                let highlighted = self.highlight == Some(coord);
+                let unchanged = self.prev.as_ref().is_some_and(|prev| prev[coord] == cell);
+                if unchanged && !(moved && (highlighted || self.prev_highlight == Some(coord))) {
+                    continue;
+                }
+
+                if position != Some(coord) {
+                    let column = u16::try_from(x * width).unwrap_or(u16::MAX);
+                    let row = u16::try_from(y).unwrap_or(u16::MAX);
+                    queue!(self.writer, MoveTo(column, row))?;
+                }
+                let glyph = match cell {
+                    Cell::Alive => self.alive,
+                    Cell::Dead => self.dead,
+                };
+                if highlighted {
+                    queue!(
+                        self.writer,
+                        SetAttribute(Attribute::Reverse),
+                        Print(glyph),
+                        SetAttribute(Attribute::NoReverse)
+                    )?;
+                } else {
+                    queue!(self.writer, Print(glyph))?;
+                }
+                position = Some(Coord(x + 1, y));
+            }
+        }
+
+        self.prev = Some(grid.clone());
+        self.prev_highlight = self.highlight;
+        self.writer.flush()
+    }
+
+    /// Highlights the cell at `coord` from the next frame on, or none.
+    pub fn set_highlight(&mut self, coord: Option<Coord>) {
+        self.highlight = coord;
+    }
+
+    ///
+    /// Show `text` on the status line below the grid, replacing any
+    /// previous status.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while writing.
+    ///
+    pub fn status(&mut self, text: &str) -> io::Result<()> {
+        let row = u16::try_from(H).unwrap_or(u16::MAX);
+        queue!(
+            self.writer,
+            MoveTo(0, row),
+            Clear(ClearType::UntilNewLine),
+            Print(text)
+        )?;
+        self.writer.flush()
+    }
+
+    /// Forces the next frame to be drawn in full, such as after a resize.
+    pub fn invalidate(&mut self) {
+        self.prev = None;
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn writer_mut(&mut self) -> &mut T {
+        &mut self.writer
+    }
+}
+
+impl<T: Write, const W: usize, const H: usize> Drop for Terminal<'_, T, W, H> {
+    fn drop(&mut self) {
+        let _ = self.leave();
+    }
+}
+
+/// The speeds of [`Speed`], in generations per second.
+const SPEEDS: [u32; 10] = [1, 2, 5, 10, 20, 30, 60, 120, 240, 480];
+
+///
+/// The playback speed of a running simulation, stepped up and down through
+/// a range of 1 to 480 generations per second.
+///
+/// The default speed is 10 generations per second.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Speed {
+    level: usize,
+}
+
+impl Speed {
+    /// Returns the number of generations per second.
+    #[must_use]
+    pub const fn per_second(self) -> u32 {
+        SPEEDS[self.level]
+    }
+
+    /// Returns the interval between generations.
+    #[must_use]
+    pub fn interval(self) -> Duration {
+        Duration::from_secs(1) / self.per_second()
+    }
+
+    /// Steps up to the next faster speed, if any.
+    pub fn faster(&mut self) {
+        self.level = (self.level + 1).min(SPEEDS.len() - 1);
+    }
+
+    /// Steps down to the next slower speed, if any.
+    pub fn slower(&mut self) {
+        self.level = self.level.saturating_sub(1);
+    }
+}
+
+impl Default for Speed {
+    fn default() -> Self {
+        Self { level: 3 }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn draw() {
+        let mut grid = Grid::<3, 2>::new();
+        grid[Coord(1, 0)] = Cell::Alive;
+
+        let mut terminal = Terminal::new(Vec::new()).glyphs("#", ".");
+        terminal.draw(&grid).unwrap();
+        assert_eq!(terminal.writer_mut(), b"\x1b[1;1H.#.\x1b[2;1H...");
+
+        terminal.writer_mut().clear();
+        grid[Coord(1, 0)] = Cell::Dead;
+        grid[Coord(2, 0)] = Cell::Alive;
+        grid[Coord(0, 1)] = Cell::Alive;
+        terminal.draw(&grid).unwrap();
+        assert_eq!(terminal.writer_mut(), b"\x1b[1;2H.#\x1b[2;1H#");
+
+        terminal.writer_mut().clear();
+        terminal.draw(&grid).unwrap();
+        assert!(terminal.writer_mut().is_empty());
+    }
+
+    #[test]
+    fn highlight() {
+        let mut terminal = Terminal::new(Vec::new()).glyphs("#", ".");
+        let grid = Grid::<3, 1>::new();
+        terminal.set_highlight(Some(Coord(1, 0)));
+        terminal.draw(&grid).unwrap();
+        assert_eq!(terminal.writer_mut(), b"\x1b[1;1H.\x1b[7m.\x1b[27m.");
+
+        terminal.writer_mut().clear();
+        terminal.set_highlight(Some(Coord(2, 0)));
+        terminal.draw(&grid).unwrap();
+        assert_eq!(terminal.writer_mut(), b"\x1b[1;2H.\x1b[7m.\x1b[27m");
+
+        terminal.writer_mut().clear();
+        terminal.status("gen 0").unwrap();
+        assert_eq!(terminal.writer_mut(), b"\x1b[2;1H\x1b[Kgen 0");
+    }
+
+    #[test]
+    fn speed() {
+        let mut speed = Speed::default();
+        assert_eq!(speed.interval(), Duration::from_millis(100));
+
+        speed.faster();
+        assert_eq!(speed.per_second(), 20);
+        for _ in 0..20 {
+            speed.slower();
+        }
+        assert_eq!(speed.per_second(), 1);
+    }
+}
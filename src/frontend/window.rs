@@ -0,0 +1,114 @@
+//!
+//! A desktop window frontend built on [`minifb`].
+//!
+//! See [`Window`].
+//!
+
+use std::{vec, vec::Vec};
+
+use minifb::{MouseButton, MouseMode, Scale, WindowOptions};
+
+use crate::{cell::Cell, grid::Grid, io::Palette, math::Coord, render::color::ColorPolicy};
+
+///
+/// A window that grids are drawn into with one pixel per cell, optionally
+/// scaled up, and that can be edited with the mouse.
+///
+/// Frames are paced to 60 per second by default.
+///
+pub struct Window<const W: usize, const H: usize, P = Palette> {
+    window: minifb::Window,
+    buf: Vec<u32>,
+    palette: P,
+}
+
+impl<const W: usize, const H: usize> Window<W, H> {
+    ///
+    /// Open a new [`Window`] titled `title`, with each cell drawn as a
+    /// square of `scale` pixels.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while opening the window.
+    ///
+    pub fn new(title: &str, scale: Scale) -> minifb::Result<Self> {
+        let options = WindowOptions {
+            scale,
+            ..WindowOptions::default()
+        };
+        let mut window = minifb::Window::new(title, W, H, options)?;
+        window.set_target_fps(60);
+
+        Ok(Self {
+            window,
+            buf: vec![0; W * H],
+            palette: Palette::default(),
+        })
+    }
+}
+
+impl<const W: usize, const H: usize, P: ColorPolicy> Window<W, H, P> {
+    /// Colors cells by `palette`.
+    #[must_use]
+    pub fn palette<Q: ColorPolicy>(self, palette: Q) -> Window<W, H, Q> {
+        Window {
+            window: self.window,
+            buf: self.buf,
+            palette,
+        }
+    }
+
+    /// Returns whether the window is still open.
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.window.is_open()
+    }
+
+    ///
+    /// Draw `grid` into the window, and process its input events, waiting
+    /// for the next frame if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while updating the window.
+    ///
+    pub fn draw(&mut self, grid: &Grid<W, H>) -> minifb::Result<()> {
+        for (i, pixel) in self.buf.iter_mut().enumerate() {
+            let coord = Coord(i % W, i / W);
+            let [r, g, b] = self.palette.color(coord, grid[coord]);
+            *pixel = u32::from_be_bytes([0, r, g, b]);
+        }
+
+        self.window.update_with_buffer(&self.buf, W, H)
+    }
+
+    ///
+    /// Returns the cell under the mouse cursor, and the state to paint it
+    /// with, if a mouse button is held: [`Cell::Alive`] for the left
+    /// button, and [`Cell::Dead`] for the right button.
+    ///
+    #[must_use]
+    pub fn brush(&self) -> Option<(Coord, Cell)> {
+        let cell = if self.window.get_mouse_down(MouseButton::Left) {
+            Cell::Alive
+        } else if self.window.get_mouse_down(MouseButton::Right) {
+            Cell::Dead
+        } else {
+            return None;
+        };
+        let (x, y) = self.window.get_mouse_pos(MouseMode::Discard)?;
+
+        Some((Coord(x as usize, y as usize), cell)).filter(|&(Coord(x, y), _)| x < W && y < H)
+    }
+
+    /// Returns the underlying window, such as for reading the keyboard.
+    #[must_use]
+    pub const fn inner(&self) -> &minifb::Window {
+        &self.window
+    }
+
+    /// Returns the underlying window mutably, such as for setting its title.
+    pub fn inner_mut(&mut self) -> &mut minifb::Window {
+        &mut self.window
+    }
+}
@@ -1,31 +1,209 @@
 use core::{
-    array,
+    array, fmt,
     ops::{Index, IndexMut},
 };
 
-use crate::{cell::Cell, math::Coord};
+use crate::{
+    cell::{Cell, CellState, Charset},
+    distance::Metric,
+    math::Coord,
+    pattern::Orientation,
+    rule::{Life, Rule},
+};
 
 ///
 /// The Game of Life cell grid.
 ///
+/// Cells are [`Cell`]s by default, but can be any [`CellState`], such as
+/// for rules with more than two states.
+///
 /// [`Coord`]-based indices are in the range `(0..W, 0..H)`.
 ///
 #[derive(Clone, Debug)]
-pub struct Grid<const W: usize, const H: usize> {
-    pub cells: [[Cell; W]; H],
+pub struct Grid<const W: usize, const H: usize, C = Cell> {
+    pub cells: [[C; W]; H],
     pub generation: u64,
 }
 
-impl<const W: usize, const H: usize> Grid<W, H> {
-    /// Construct a new [`Coord`] with all [`Cell::Dead`] cells.
+///
+/// Runs of equal cells in a row of a [`Grid`], as `(cell, length)` pairs.
+///
+/// Obtained by calling [`Grid::row_runs()`] or [`Grid::runs()`].
+///
+#[derive(Clone, Debug)]
+pub struct Runs<'a> {
+    row: &'a [Cell],
+}
+
+///
+/// An endless iterator over successive generations of a [`Grid`], starting
+/// with the grid itself.
+///
+/// Obtained by calling [`Grid::generations()`] or
+/// [`Grid::generations_with()`].
+///
+#[derive(Clone, Debug)]
+pub struct Generations<const W: usize, const H: usize, R = Life> {
+    grid: Grid<W, H>,
+    rule: R,
+}
+
+///
+/// A [`Grid`] displayed with custom glyphs, one line per row.
+///
+/// Obtained by calling [`Grid::display_with()`].
+///
+#[derive(Clone, Copy, Debug)]
+pub struct GridDisplay<'a, const W: usize, const H: usize, A, D> {
+    grid: &'a Grid<W, H>,
+    alive: A,
+    dead: D,
+    line_ending: &'a str,
+}
+
+///
+/// One of the four edges of a [`Grid`].
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Edge {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+impl Edge {
+    /// All edges, clockwise from [`Edge::Top`].
+    pub const ALL: [Self; 4] = [Self::Top, Self::Right, Self::Bottom, Self::Left];
+}
+
+impl<const W: usize, const H: usize, C: CellState> Grid<W, H, C> {
+    /// Construct a new [`Grid`] with all [`CellState::DEAD`] cells.
     #[must_use]
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self {
-            cells: [[Cell::Dead; W]; H],
+            cells: [[C::DEAD; W]; H],
             generation: 0,
         }
     }
 
+    /// Returns the number of live cells.
+    #[must_use]
+    pub fn population(&self) -> usize {
+        self.cells
+            .iter()
+            .flatten()
+            .filter(|&&cell| cell.is_alive())
+            .count()
+    }
+
+    ///
+    /// Returns the live cell nearest to `from` along with its Chebyshev
+    /// distance, or [`None`] if there are no live cells.
+    ///
+    /// Cells are searched in the order of [`Coord::spiral()`], so only cells
+    /// up to the nearest live cell's distance are visited. Ties are broken in
+    /// row-major order.
+    ///
+    #[must_use]
+    pub fn nearest_live(&self, from: Coord) -> Option<(Coord, usize)> {
+        from.spiral(Coord(W, H))
+            .find(|&coord| self[coord].is_alive())
+            .map(|coord| (coord, Metric::Chebyshev.distance(from, coord)))
+    }
+
+    ///
+    /// Sets the cells on the line from `a` to `b` to `cell`, skipping any
+    /// that lie outside of this grid.
+    ///
+    /// See [`math::line()`](crate::math::line()).
+    ///
+    pub fn draw_line(&mut self, a: Coord, b: Coord, cell: C) {
+        for Coord(x, y) in crate::math::line(a, b) {
+            if let Some(target) = self.cells.get_mut(y).and_then(|row| row.get_mut(x)) {
+                *target = cell;
+            }
+        }
+    }
+
+    ///
+    /// Kills every cell of this grid where `mask` is dead, leaving the rest
+    /// untouched.
+    ///
+    /// Masking after every step confines a pattern to the live cells of
+    /// `mask`, giving arenas of any shape, such as disks or rings.
+    ///
+    pub fn mask<M: CellState>(&mut self, mask: &Grid<W, H, M>) {
+        let masks = mask.cells.iter().flatten();
+        for (cell, mask) in self.cells.iter_mut().flatten().zip(masks) {
+            if !mask.is_alive() {
+                *cell = C::DEAD;
+            }
+        }
+    }
+
+    ///
+    /// Returns the number of bytes this grid takes up.
+    ///
+    /// A grid stores its cells inline and never allocates, so this is known
+    /// at compile time and only depends on `W`, `H` and `C`.
+    ///
+    #[must_use]
+    pub const fn memory_footprint(&self) -> usize {
+        size_of::<Self>()
+    }
+
+    ///
+    /// Returns an iterator over every `KW` by `KH` window of this grid, in
+    /// row-major order, along with the top-left corner of each.
+    ///
+    /// Like [`slice::windows()`], windows overlap, and only windows lying
+    /// entirely inside of the grid are returned.
+    ///
+    pub fn windows<const KW: usize, const KH: usize>(
+        &self,
+    ) -> impl Iterator<Item = (Coord, [[C; KW]; KH])> + '_ {
+        const { assert!(KW > 0 && KH > 0, "windows must not be empty") };
+        let [columns, rows] = [(W + 1).saturating_sub(KW), (H + 1).saturating_sub(KH)];
+        (0..rows).flat_map(move |y| {
+            (0..columns).map(move |x| {
+                let window = array::from_fn(|dy| array::from_fn(|dx| self.cells[y + dy][x + dx]));
+                (Coord(x, y), window)
+            })
+        })
+    }
+
+    ///
+    /// Returns an iterator over the `CW` by `CH` chunks of this grid, in
+    /// row-major order, along with the top-left corner of each.
+    ///
+    /// Like [`slice::chunks()`], chunks do not overlap and cover the whole
+    /// grid. Chunks along the right and bottom edges are padded with
+    /// [`CellState::DEAD`] cells if the grid's extents are not multiples of
+    /// theirs.
+    ///
+    pub fn chunks<const CW: usize, const CH: usize>(
+        &self,
+    ) -> impl Iterator<Item = (Coord, [[C; CW]; CH])> + '_ {
+        const { assert!(CW > 0 && CH > 0, "chunks must not be empty") };
+        (0..H.div_ceil(CH)).flat_map(move |cy| {
+            (0..W.div_ceil(CW)).map(move |cx| {
+                let Coord(x, y) = Coord(cx * CW, cy * CH);
+                let chunk = array::from_fn(|dy| {
+                    array::from_fn(|dx| {
+                        let row = self.cells.get(y + dy);
+                        row.and_then(|row| row.get(x + dx))
+                            .copied()
+                            .unwrap_or(C::DEAD)
+                    })
+                });
+                (Coord(x, y), chunk)
+            })
+        })
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
     ///
     /// Calculate the state of this cell in the next generation.
     ///
@@ -34,47 +212,469 @@ impl<const W: usize, const H: usize> Grid<W, H> {
     ///
     #[must_use]
     pub fn state_next(&self, coord: Coord) -> Cell {
-        match coord
-            .neighbors(Coord(W, H))
-            .filter(|&coord| self[coord] == Cell::Alive)
-            .count()
-        {
-            0 | 1 | 4.. => Cell::Dead,
-            2 => self[coord],
-            3 => Cell::Alive,
-        }
+        self[coord].next(
+            coord
+                .neighbors(Coord(W, H))
+                .filter(|&coord| self[coord] == Cell::Alive)
+                .count(),
+        )
     }
 
     /// Calculates the next generation of this grid.
     #[must_use]
     pub fn step(&self) -> Self {
+        self.step_with(&Life)
+    }
+
+    ///
+    /// Calculates the next generation of this grid into `next`.
+    ///
+    /// See [`Grid::step_with_into()`].
+    ///
+    pub fn step_into(&self, next: &mut Self) {
+        self.step_with_into(&Life, next);
+    }
+
+    ///
+    /// Calculates the state of this grid `n` generations ahead into
+    /// `scratch`, using `spare` as well.
+    ///
+    /// See [`Grid::peek_with()`].
+    ///
+    pub fn peek(&self, n: u64, scratch: &mut Self, spare: &mut Self) {
+        self.peek_with(&Life, n, scratch, spare);
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Returns the runs of equal cells in row `y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is out of bounds.
+    ///
+    #[must_use]
+    pub fn row_runs(&self, y: usize) -> Runs<'_> {
+        Runs {
+            row: &self.cells[y],
+        }
+    }
+
+    /// Returns the runs of equal cells in each row, from top to bottom.
+    pub fn runs(&self) -> impl Iterator<Item = Runs<'_>> {
+        self.cells.iter().map(|row| Runs { row })
+    }
+
+    ///
+    /// Returns the cells which differ between this grid and `other`, along
+    /// with their state in `other`, in row-major order.
+    ///
+    /// Applying the result to this grid with [`Grid::apply_diff()`] makes
+    /// its cells equal to those of `other`.
+    ///
+    pub fn diff<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = (Coord, Cell)> + 'a {
+        (0..H)
+            .flat_map(|y| (0..W).map(move |x| Coord(x, y)))
+            .filter(|&coord| self[coord] != other[coord])
+            .map(|coord| (coord, other[coord]))
+    }
+
+    ///
+    /// Set each cell in `diff` to its given state.
+    ///
+    /// The generation is left unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coordinate is out of bounds.
+    ///
+    pub fn apply_diff(&mut self, diff: impl IntoIterator<Item = (Coord, Cell)>) {
+        for (coord, cell) in diff {
+            self[coord] = cell;
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Returns a 64-bit fingerprint of this grid's cells.
+    ///
+    /// This is the [FNV-1a](https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function)
+    /// hash of the cells in row-major order, one byte each. It ignores the
+    /// generation, and is stable across platforms and versions of this
+    /// crate.
+    ///
+    #[must_use]
+    pub fn fingerprint(&self) -> u64 {
+        self.cells
+            .iter()
+            .flatten()
+            .fold(FNV_OFFSET, |hash, &cell| fnv(hash, cell as u64))
+    }
+
+    ///
+    /// Returns a fingerprint of this grid's live cells that does not change
+    /// when they are moved around the grid.
+    ///
+    /// This hashes the extents of the bounding box of the live cells and the
+    /// cells inside of it, so a spaceship has the same canonical fingerprint
+    /// every period, wherever it has moved to.
+    ///
+    #[must_use]
+    pub fn canonical_fingerprint(&self) -> u64 {
+        self.oriented_fingerprint(Orientation::Identity)
+    }
+
+    ///
+    /// Returns a fingerprint of this grid's live cells like
+    /// [`Grid::canonical_fingerprint()`], which also does not change when
+    /// they are rotated or reflected.
+    ///
+    #[must_use]
+    pub fn canonical_fingerprint_any_orientation(&self) -> u64 {
+        Orientation::ALL
+            .into_iter()
+            .map(|orientation| self.oriented_fingerprint(orientation))
+            .min()
+            .expect("there are orientations")
+    }
+
+    ///
+    /// Returns the top-left corner and the extents of the bounding box of the
+    /// live cells, or [`None`] if there are none.
+    ///
+    #[must_use]
+    pub fn bounding_box(&self) -> Option<(Coord, Coord)> {
+        let rows = |y: &usize| self.cells[*y].contains(&Cell::Alive);
+        let columns = |x: &usize| self.cells.iter().any(|row| row[*x] == Cell::Alive);
+
+        let top = (0..H).find(rows)?;
+        let bottom = (0..H).rfind(rows)?;
+        let left = (0..W).find(columns)?;
+        let right = (0..W).rfind(columns)?;
+        Some((Coord(left, top), Coord(right - left + 1, bottom - top + 1)))
+    }
+
+    ///
+    /// Returns whether this grid's live cells are the same as `other`'s, up
+    /// to moving, rotating and reflecting them.
+    ///
+    /// The grids may be of different sizes, and their generations are
+    /// ignored.
+    ///
+    #[must_use]
+    pub fn equivalent_to<const W2: usize, const H2: usize>(&self, other: &Grid<W2, H2>) -> bool {
+        let ((corner, extents), (other_corner, other_extents)) =
+            match (self.bounding_box(), other.bounding_box()) {
+                (Some(bounds), Some(other_bounds)) => (bounds, other_bounds),
+                (bounds, other_bounds) => return bounds.is_none() && other_bounds.is_none(),
+            };
+
+        Orientation::ALL
+            .into_iter()
+            .filter(|orientation| orientation.extents(extents) == other_extents)
+            .any(|orientation| {
+                (0..extents.1)
+                    .flat_map(|y| (0..extents.0).map(move |x| Coord(x, y)))
+                    .all(|coord| {
+                        let oriented = orientation.transform(coord, extents);
+                        self[corner + coord] == other[other_corner + oriented]
+                    })
+            })
+    }
+
+    ///
+    /// Returns the first live cell along `edge`, from left to right or top
+    /// to bottom, or [`None`] if there are none.
+    ///
+    #[must_use]
+    pub fn first_on_edge(&self, edge: Edge) -> Option<Coord> {
+        if W == 0 || H == 0 {
+            return None;
+        }
+
+        let (start, step, len) = match edge {
+            Edge::Top => (Coord(0, 0), Coord(1, 0), W),
+            Edge::Right => (Coord(W - 1, 0), Coord(0, 1), H),
+            Edge::Bottom => (Coord(0, H - 1), Coord(1, 0), W),
+            Edge::Left => (Coord(0, 0), Coord(0, 1), H),
+        };
+        (0..len)
+            .map(|i| start + Coord(step.0 * i, step.1 * i))
+            .find(|&coord| self[coord] == Cell::Alive)
+    }
+
+    /// Hashes the live cells' bounding box transformed into `orientation`.
+    fn oriented_fingerprint(&self, orientation: Orientation) -> u64 {
+        let Some((corner, extents)) = self.bounding_box() else {
+            return FNV_OFFSET;
+        };
+
+        let oriented = orientation.extents(extents);
+        let inverse = orientation.inverse();
+        let hash = fnv(fnv(FNV_OFFSET, oriented.0 as u64), oriented.1 as u64);
+        (0..oriented.1)
+            .flat_map(|y| (0..oriented.0).map(move |x| Coord(x, y)))
+            .map(|coord| self[corner + inverse.transform(coord, oriented)])
+            .fold(hash, |hash, cell| fnv(hash, cell as u64))
+    }
+}
+
+/// The initial state of an FNV-1a hash.
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Feeds `value` into an FNV-1a hash.
+const fn fnv(hash: u64, value: u64) -> u64 {
+    (hash ^ value).wrapping_mul(0x0000_0100_0000_01b3)
+}
+
+impl<const W: usize, const H: usize, C: Copy> Grid<W, H, C> {
+    /// Returns this grid with its X and Y axes swapped.
+    #[must_use]
+    pub fn transpose(&self) -> Grid<H, W, C> {
+        Grid {
+            cells: core::array::from_fn(|x| core::array::from_fn(|y| self.cells[y][x])),
+            generation: self.generation,
+        }
+    }
+
+    ///
+    /// Returns this grid shifted cyclically by `(dx, dy)`, with cells moved
+    /// off one edge coming back in on the opposite edge, as on a torus.
+    ///
+    /// Positive offsets shift right and down.
+    ///
+    #[must_use]
+    pub fn roll(&self, dx: isize, dy: isize) -> Self {
+        // reduce first, so that offsets near `isize::MIN` cannot overflow
+        let reduce = |d: isize, n: usize| d.rem_euclid(n.max(1) as isize) as usize;
+        let (dx, dy) = (reduce(dx, W), reduce(dy, H));
         Self {
-            cells: array::from_fn(|y| array::from_fn(|x| self.state_next(Coord(x, y)))),
-            generation: self.generation + 1,
+            cells: array::from_fn(|y| {
+                let row = &self.cells[(y + H - dy) % H];
+                array::from_fn(|x| row[(x + W - dx) % W])
+            }),
+            generation: self.generation,
+        }
+    }
+
+    ///
+    /// Returns this grid with `right` placed to its right, keeping this
+    /// grid's generation.
+    ///
+    /// `W2` is usually inferred from the result, and must be the sum of the
+    /// widths of the grids, which is checked at compile time.
+    ///
+    #[must_use]
+    pub fn hconcat<const R: usize, const W2: usize>(
+        &self,
+        right: &Grid<R, H, C>,
+    ) -> Grid<W2, H, C> {
+        const { assert!(W + R == W2, "W2 must be the sum of the widths") };
+        Grid {
+            cells: core::array::from_fn(|y| {
+                core::array::from_fn(|x| match x.checked_sub(W) {
+                    None => self.cells[y][x],
+                    Some(x) => right.cells[y][x],
+                })
+            }),
+            generation: self.generation,
+        }
+    }
+
+    ///
+    /// Returns this grid with `below` placed below it, keeping this grid's
+    /// generation.
+    ///
+    /// `H2` is usually inferred from the result, and must be the sum of the
+    /// heights of the grids, which is checked at compile time.
+    ///
+    #[must_use]
+    pub fn vconcat<const B: usize, const H2: usize>(
+        &self,
+        below: &Grid<W, B, C>,
+    ) -> Grid<W, H2, C> {
+        const { assert!(H + B == H2, "H2 must be the sum of the heights") };
+        Grid {
+            cells: core::array::from_fn(|y| match y.checked_sub(H) {
+                None => self.cells[y],
+                Some(y) => below.cells[y],
+            }),
+            generation: self.generation,
+        }
+    }
+
+    ///
+    /// Builds a grid out of `N` by `M` tiles, given row by row, keeping the
+    /// generation of the top-left tile.
+    ///
+    /// `W2` and `H2` are usually inferred from the result, and must be `N`
+    /// times the width and `M` times the height of a tile, which is checked
+    /// at compile time.
+    ///
+    #[must_use]
+    pub fn tile<const N: usize, const M: usize, const W2: usize, const H2: usize>(
+        tiles: &[[Self; N]; M],
+    ) -> Grid<W2, H2, C> {
+        const {
+            assert!(
+                W * N == W2 && H * M == H2,
+                "W2 and H2 must be multiples of the tiles"
+            )
+        };
+        Grid {
+            cells: core::array::from_fn(|y| {
+                core::array::from_fn(|x| tiles[y / H][x / W].cells[y % H][x % W])
+            }),
+            generation: tiles
+                .first()
+                .and_then(|row| row.first())
+                .map_or(0, |tile| tile.generation),
+        }
+    }
+}
+
+impl<const N: usize, C> Grid<N, N, C> {
+    /// Swaps the X and Y axes of this square grid in place.
+    pub fn transpose_in_place(&mut self) {
+        for y in 0..N {
+            let (above, below) = self.cells.split_at_mut(y + 1);
+            for (x, row) in (y + 1..N).zip(below) {
+                core::mem::swap(&mut above[y][x], &mut row[y]);
+            }
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    /// Returns an iterator over this grid and its successive generations.
+    #[must_use]
+    pub fn generations(&self) -> Generations<W, H> {
+        self.generations_with(Life)
+    }
+
+    /// Returns an iterator over this grid and its successive generations under `rule`.
+    #[must_use]
+    pub fn generations_with<R: Rule>(&self, rule: R) -> Generations<W, H, R> {
+        Generations {
+            grid: self.clone(),
+            rule,
+        }
+    }
+}
+
+impl<const W: usize, const H: usize, R: Rule> Iterator for Generations<W, H, R> {
+    type Item = Grid<W, H>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.grid.step_with(&self.rule);
+        Some(core::mem::replace(&mut self.grid, next))
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Returns a wrapper displaying live cells as `alive` and dead cells as
+    /// `dead`, such as `'█'` and `'·'`.
+    ///
+    #[must_use]
+    pub fn display_with<A: fmt::Display, D: fmt::Display>(
+        &self,
+        alive: A,
+        dead: D,
+    ) -> GridDisplay<'_, W, H, A, D> {
+        GridDisplay {
+            grid: self,
+            alive,
+            dead,
+            line_ending: "\n",
         }
     }
 }
 
-impl<const W: usize, const H: usize> Default for Grid<W, H> {
+impl<'a, const W: usize, const H: usize, A, D> GridDisplay<'a, W, H, A, D> {
+    /// Ends lines with `line_ending` instead of `"\n"`, such as `"\r\n"` for raw terminals.
+    #[must_use]
+    pub fn line_ending(self, line_ending: &'a str) -> Self {
+        Self {
+            line_ending,
+            ..self
+        }
+    }
+}
+
+impl<const W: usize, const H: usize, A: fmt::Display, D: fmt::Display> fmt::Display
+    for GridDisplay<'_, W, H, A, D>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.grid.cells {
+            for cell in row {
+                match cell {
+                    Cell::Alive => write!(f, "{}", self.alive)?,
+                    Cell::Dead => write!(f, "{}", self.dead)?,
+                }
+            }
+            f.write_str(self.line_ending)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const W: usize, const H: usize> fmt::Display for Grid<W, H> {
+    /// Displays cells in the default [`Charset`], one line per row.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Charset { alive, dead } = Charset::default();
+        self.display_with(alive, dead).fmt(f)
+    }
+}
+
+impl<const W: usize, const H: usize, C: CellState> Default for Grid<W, H, C> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<const W: usize, const H: usize> Index<Coord> for Grid<W, H> {
-    type Output = Cell;
+impl<const W: usize, const H: usize, C> Grid<W, H, C> {
+    /// Returns the cell at `coord`, or [`None`] if it lies outside this grid.
+    #[must_use]
+    pub fn get(&self, coord: Coord) -> Option<&C> {
+        self.cells.get(coord.1)?.get(coord.0)
+    }
+
+    /// Returns the cell at `coord` mutably, or [`None`] if it lies outside
+    /// this grid.
+    #[must_use]
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut C> {
+        self.cells.get_mut(coord.1)?.get_mut(coord.0)
+    }
+}
+
+impl<const W: usize, const H: usize, C> Index<Coord> for Grid<W, H, C> {
+    type Output = C;
 
     fn index(&self, index: Coord) -> &Self::Output {
         &self.cells[index.1][index.0]
     }
 }
 
-impl<const W: usize, const H: usize> IndexMut<Coord> for Grid<W, H> {
+impl<const W: usize, const H: usize, C> IndexMut<Coord> for Grid<W, H, C> {
     fn index_mut(&mut self, index: Coord) -> &mut Self::Output {
         &mut self.cells[index.1][index.0]
     }
 }
 
+impl Iterator for Runs<'_> {
+    type Item = (Cell, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let &cell = self.row.first()?;
+        let len = self.row.iter().take_while(|&&other| other == cell).count();
+        self.row = &self.row[len..];
+        Some((cell, len))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -108,4 +708,363 @@ mod test {
         grid[Coord(0, 2)] = Cell::Alive;
         assert_eq!(next!(), Cell::Dead);
     }
+
+    #[test]
+    fn diff() {
+        let mut grid = Grid::<3, 3>::new();
+        grid[Coord(1, 0)] = Cell::Alive;
+        grid[Coord(1, 1)] = Cell::Alive;
+        grid[Coord(1, 2)] = Cell::Alive;
+
+        let next = grid.step();
+        assert!(grid.diff(&next).eq([
+            (Coord(1, 0), Cell::Dead),
+            (Coord(0, 1), Cell::Alive),
+            (Coord(2, 1), Cell::Alive),
+            (Coord(1, 2), Cell::Dead),
+        ]));
+
+        grid.apply_diff(grid.clone().diff(&next));
+        assert_eq!(grid.cells, next.cells);
+    }
+
+    #[test]
+    fn generations() {
+        let mut grid = Grid::<3, 3>::new();
+        grid[Coord(1, 0)] = Cell::Alive;
+        grid[Coord(1, 1)] = Cell::Alive;
+        grid[Coord(1, 2)] = Cell::Alive;
+
+        let mut generations = grid.generations();
+        assert_eq!(generations.next().unwrap().cells, grid.cells);
+        assert_eq!(generations.next().unwrap().cells, grid.step().cells);
+        assert_eq!(generations.next().unwrap().cells, grid.cells);
+        assert_eq!(generations.next().unwrap().generation, 3);
+    }
+
+    #[test]
+    fn fingerprint() {
+        let mut grid = Grid::<3, 3>::new();
+        grid[Coord(1, 0)] = Cell::Alive;
+        grid[Coord(1, 1)] = Cell::Alive;
+        grid[Coord(1, 2)] = Cell::Alive;
+        assert_eq!(Grid::<3, 3>::new().fingerprint(), 0xe604_823a_2490_29bf);
+
+        let period_two = grid.step().step();
+        assert_eq!(grid.fingerprint(), period_two.fingerprint());
+        assert_ne!(grid.fingerprint(), grid.step().fingerprint());
+    }
+
+    #[test]
+    fn canonical_fingerprint() {
+        // an L tromino, and the same tromino turned around
+        let mut grid = Grid::<6, 6>::new();
+        for coord in [Coord(1, 1), Coord(1, 2), Coord(2, 2)] {
+            grid[coord] = Cell::Alive;
+        }
+        let mut half_turn = Grid::<6, 6>::new();
+        for coord in [Coord(3, 4), Coord(4, 4), Coord(4, 5)] {
+            half_turn[coord] = Cell::Alive;
+        }
+        let mut quarter_turn = Grid::<6, 6>::new();
+        for coord in [Coord(4, 3), Coord(5, 3), Coord(4, 4)] {
+            quarter_turn[coord] = Cell::Alive;
+        }
+
+        assert_eq!(grid.bounding_box(), Some((Coord(1, 1), Coord(2, 2))));
+        assert_eq!(Grid::<6, 6>::new().bounding_box(), None);
+
+        let mut translated = Grid::<6, 6>::new();
+        for coord in [Coord(4, 3), Coord(4, 4), Coord(5, 4)] {
+            translated[coord] = Cell::Alive;
+        }
+        assert_eq!(
+            grid.canonical_fingerprint(),
+            translated.canonical_fingerprint()
+        );
+        assert_ne!(grid.fingerprint(), translated.fingerprint());
+        assert_ne!(
+            grid.canonical_fingerprint(),
+            half_turn.canonical_fingerprint()
+        );
+        assert_ne!(
+            grid.canonical_fingerprint(),
+            quarter_turn.canonical_fingerprint()
+        );
+
+        for other in [&translated, &half_turn, &quarter_turn] {
+            assert_eq!(
+                grid.canonical_fingerprint_any_orientation(),
+                other.canonical_fingerprint_any_orientation()
+            );
+        }
+        assert_ne!(
+            grid.canonical_fingerprint_any_orientation(),
+            Grid::<6, 6>::new().canonical_fingerprint_any_orientation()
+        );
+    }
+
+    #[test]
+    fn equivalent_to() {
+        // a glider, which is reflected and moved after 2 generations
+        let mut glider = Grid::<8, 8>::new();
+        for coord in [
+            Coord(1, 0),
+            Coord(2, 1),
+            Coord(0, 2),
+            Coord(1, 2),
+            Coord(2, 2),
+        ] {
+            glider[coord] = Cell::Alive;
+        }
+        assert!(glider.equivalent_to(&glider.step().step()));
+        assert!(glider.equivalent_to(&glider.step().step().step().step()));
+        assert!(!glider.equivalent_to(&glider.step()));
+
+        // the same glider, rotated, on a smaller grid
+        let mut rotated = Grid::<3, 3>::new();
+        for coord in [
+            Coord(0, 0),
+            Coord(1, 0),
+            Coord(2, 0),
+            Coord(0, 1),
+            Coord(1, 2),
+        ] {
+            rotated[coord] = Cell::Alive;
+        }
+        assert!(glider.equivalent_to(&rotated));
+        assert!(rotated.equivalent_to(&glider));
+
+        assert!(Grid::<2, 2>::new().equivalent_to(&Grid::<3, 3>::new()));
+        assert!(!Grid::<3, 3>::new().equivalent_to(&rotated));
+    }
+
+    #[test]
+    fn transpose() {
+        let mut grid = Grid::<3, 2>::new();
+        grid[Coord(2, 0)] = Cell::Alive;
+        grid[Coord(0, 1)] = Cell::Alive;
+        grid.generation = 5;
+
+        let transposed = grid.transpose();
+        assert_eq!(transposed[Coord(0, 2)], Cell::Alive);
+        assert_eq!(transposed[Coord(1, 0)], Cell::Alive);
+        assert_eq!(transposed.population(), 2);
+        assert_eq!(transposed.generation, 5);
+        assert_eq!(transposed.transpose().cells, grid.cells);
+
+        let mut square = Grid::<3, 3>::new();
+        for coord in [Coord(1, 0), Coord(2, 0), Coord(2, 1)] {
+            square[coord] = Cell::Alive;
+        }
+        let expected = square.transpose();
+        square.transpose_in_place();
+        assert_eq!(square.cells, expected.cells);
+        assert_eq!(square[Coord(0, 2)], Cell::Alive);
+    }
+
+    #[test]
+    fn nearest_live() {
+        let mut grid = Grid::<7, 5>::new();
+        assert_eq!(grid.nearest_live(Coord(3, 2)), None);
+
+        grid[Coord(6, 0)] = Cell::Alive;
+        grid[Coord(0, 4)] = Cell::Alive;
+        assert_eq!(grid.nearest_live(Coord(5, 1)), Some((Coord(6, 0), 1)));
+        assert_eq!(grid.nearest_live(Coord(2, 2)), Some((Coord(0, 4), 2)));
+        assert_eq!(grid.nearest_live(Coord(0, 4)), Some((Coord(0, 4), 0)));
+        assert_eq!(grid.nearest_live(Coord(3, 2)), Some((Coord(6, 0), 3)));
+        assert_eq!(grid.nearest_live(Coord(9, 0)), Some((Coord(6, 0), 3)));
+
+        for y in 0..5 {
+            for x in 0..7 {
+                let from = Coord(x, y);
+                let distance = grid.nearest_live(from).unwrap().1;
+                let expected = [Coord(6, 0), Coord(0, 4)]
+                    .map(|Coord(lx, ly)| x.abs_diff(lx).max(y.abs_diff(ly)))
+                    .into_iter()
+                    .min();
+                assert_eq!(Some(distance), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn draw_line() {
+        let mut grid = Grid::<5, 4>::new();
+        grid.draw_line(Coord(0, 0), Coord(7, 3), Cell::Alive);
+        assert_eq!(grid.population(), 5);
+        assert_eq!(grid[Coord(4, 2)], Cell::Alive);
+        grid.draw_line(Coord(2, 1), Coord(2, 1), Cell::Dead);
+        assert_eq!(grid.population(), 4);
+    }
+
+    #[test]
+    fn get() {
+        let mut grid = Grid::<4, 3>::new();
+        *grid.get_mut(Coord(3, 2)).unwrap() = Cell::Alive;
+        assert_eq!(grid.get(Coord(3, 2)), Some(&Cell::Alive));
+        assert_eq!(grid.get(Coord(0, 0)), Some(&Cell::Dead));
+        assert_eq!(grid.get(Coord(4, 0)), None);
+        assert_eq!(grid.get_mut(Coord(0, 3)), None);
+        assert_eq!(grid.get(Coord(usize::MAX, usize::MAX)), None);
+    }
+
+    #[test]
+    fn mask() {
+        let mut grid = Grid::<4, 3>::new();
+        for cell in grid.cells.iter_mut().flatten() {
+            *cell = Cell::Alive;
+        }
+
+        let mut ring = Grid::<4, 3>::new();
+        for coord in [Coord(0, 0), Coord(3, 0), Coord(1, 2), Coord(2, 1)] {
+            ring[coord] = Cell::Alive;
+        }
+
+        grid.mask(&ring);
+        assert_eq!(grid.cells, ring.cells);
+        grid.mask(&Grid::<4, 3>::new());
+        assert_eq!(grid.population(), 0);
+    }
+
+    #[test]
+    fn roll() {
+        let mut grid = Grid::<5, 4>::new();
+        grid[Coord(0, 0)] = Cell::Alive;
+        grid[Coord(4, 3)] = Cell::Alive;
+
+        let rolled = grid.roll(1, -1);
+        assert_eq!(rolled[Coord(1, 3)], Cell::Alive);
+        assert_eq!(rolled[Coord(0, 2)], Cell::Alive);
+        assert_eq!(rolled.population(), 2);
+        assert_eq!(grid.roll(5, 8).cells, grid.cells);
+        assert_eq!(rolled.roll(-1, 1).cells, grid.cells);
+        assert_eq!(
+            grid.roll(isize::MIN, isize::MAX).cells,
+            grid.roll(isize::MIN.rem_euclid(5), isize::MAX.rem_euclid(4))
+                .cells
+        );
+        assert_eq!(
+            Grid::<0, 0>::new().roll(isize::MIN, 1).cells,
+            [[Cell::Dead; 0]; 0]
+        );
+
+        // a glider on a torus moves by one cell diagonally every 4 generations
+        let mut glider = Grid::<6, 6>::new();
+        for coord in [
+            Coord(1, 0),
+            Coord(2, 1),
+            Coord(0, 2),
+            Coord(1, 2),
+            Coord(2, 2),
+        ] {
+            glider[coord] = Cell::Alive;
+        }
+        let mut stepped = glider.clone();
+        for _ in 0..8 {
+            stepped = stepped.step_on(&Life, crate::rule::Topology::Torus);
+        }
+        assert_eq!(stepped.cells, glider.roll(2, 2).cells);
+    }
+
+    #[test]
+    fn concat() {
+        extern crate std;
+        use std::format;
+
+        let mut a = Grid::<2, 2>::new();
+        a[Coord(0, 0)] = Cell::Alive;
+        a.generation = 3;
+        let mut b = Grid::<3, 2>::new();
+        b[Coord(2, 1)] = Cell::Alive;
+
+        let wide: Grid<5, 2> = a.hconcat(&b);
+        assert_eq!(format!("{wide}"), "#....\n....#\n");
+        assert_eq!(wide.generation, 3);
+
+        let tall: Grid<3, 4> = b.vconcat(&b);
+        assert_eq!(format!("{tall}"), "...\n..#\n...\n..#\n");
+
+        let tiled: Grid<4, 6> = Grid::tile(&[
+            [a.clone(), Grid::new()],
+            [Grid::new(), a.clone()],
+            [a.clone(), a],
+        ]);
+        assert_eq!(format!("{tiled}"), "#...\n....\n..#.\n....\n#.#.\n....\n");
+        assert_eq!(tiled.generation, 3);
+    }
+
+    #[test]
+    fn windows() {
+        let mut grid = Grid::<4, 3>::new();
+        grid[Coord(1, 1)] = Cell::Alive;
+        grid[Coord(3, 2)] = Cell::Alive;
+
+        let populations = grid.windows::<3, 2>().map(|(coord, window)| {
+            let population = window.iter().flatten().filter(|&&cell| cell == Cell::Alive);
+            (coord, population.count())
+        });
+        assert!(populations.eq([
+            (Coord(0, 0), 1),
+            (Coord(1, 0), 1),
+            (Coord(0, 1), 1),
+            (Coord(1, 1), 2),
+        ]));
+        assert_eq!(grid.windows::<3, 2>().count(), 4);
+        assert_eq!(grid.windows::<5, 1>().count(), 0);
+        assert_eq!(grid.windows::<1, 1>().count(), 12);
+    }
+
+    #[test]
+    fn chunks() {
+        let mut grid = Grid::<5, 3>::new();
+        grid[Coord(4, 2)] = Cell::Alive;
+
+        let mut chunks = grid.chunks::<2, 2>();
+        assert_eq!(chunks.next(), Some((Coord(0, 0), [[Cell::Dead; 2]; 2])));
+        let (coord, last) = chunks.last().unwrap();
+        assert_eq!(coord, Coord(4, 2));
+        assert_eq!(last, [[Cell::Alive, Cell::Dead], [Cell::Dead; 2]]);
+        assert_eq!(grid.chunks::<2, 2>().count(), 6);
+        assert_eq!(grid.chunks::<5, 3>().count(), 1);
+    }
+
+    #[test]
+    fn memory_footprint() {
+        // 64 one-byte cells and an 8-byte generation.
+        assert_eq!(Grid::<8, 8>::new().memory_footprint(), 72);
+        assert_eq!(
+            Grid::<8, 8, crate::rule::continuous::Level>::new().memory_footprint(),
+            136
+        );
+    }
+
+    #[test]
+    fn display() {
+        extern crate std;
+        use std::format;
+
+        let mut grid = Grid::<3, 2>::new();
+        grid[Coord(1, 0)] = Cell::Alive;
+        grid[Coord(2, 1)] = Cell::Alive;
+
+        assert_eq!(format!("{grid}"), ".#.\n..#\n");
+        assert_eq!(
+            format!("{}", grid.display_with("██", '·').line_ending("\r\n")),
+            "·██·\r\n··██\r\n"
+        );
+    }
+
+    #[test]
+    fn runs() {
+        let mut grid = Grid::<5, 2>::new();
+        grid[Coord(1, 0)] = Cell::Alive;
+        grid[Coord(2, 0)] = Cell::Alive;
+
+        assert!(grid
+            .row_runs(0)
+            .eq([(Cell::Dead, 1), (Cell::Alive, 2), (Cell::Dead, 2)]));
+        assert!(grid.runs().nth(1).unwrap().eq([(Cell::Dead, 5)]));
+    }
 }
@@ -0,0 +1,72 @@
+//!
+//! Reading and writing grids in various formats.
+//!
+//! See:
+//! - [`compress`] for compressed in-memory snapshots.
+//! - [`wire`] for the compact binary wire format.
+//! - `rle` and `plaintext` for the standard pattern formats, with the
+//!   `alloc` feature.
+//! - `json` for JSON, with the `json` feature.
+//! - `csv` for CSV statistics, with the `std` feature.
+//! - `pnm` for PBM and PGM images, with the `std` feature.
+//! - `png` for PNG images, with the `png` feature.
+//! - `gif` for animated GIF recordings, with the `gif` feature.
+//! - [`svg`] for SVG images.
+//!
+
+pub mod compress;
+#[cfg(feature = "std")]
+pub mod csv;
+#[cfg(feature = "gif")]
+pub mod gif;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "alloc")]
+pub mod plaintext;
+#[cfg(feature = "png")]
+pub mod png;
+#[cfg(feature = "std")]
+pub mod pnm;
+#[cfg(feature = "alloc")]
+pub mod rle;
+pub mod svg;
+pub mod wire;
+
+use core::fmt;
+
+///
+/// The colors of live and dead cells in an image, as RGB triples.
+///
+/// The default palette has black live cells on a white background.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Palette {
+    pub alive: [u8; 3],
+    pub dead: [u8; 3],
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            alive: [0x00; 3],
+            dead: [0xff; 3],
+        }
+    }
+}
+
+///
+/// An error encountered while parsing a text pattern format.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SyntaxError {
+    /// The 1-based line number the error was encountered on.
+    pub line: usize,
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: syntax error", self.line)
+    }
+}
+
+impl core::error::Error for SyntaxError {}
@@ -0,0 +1,257 @@
+//!
+//! Compressed in-memory snapshots of grids.
+//!
+//! A compressed grid consists of its generation as a little-endian `u64`,
+//! followed by its cells packed as in the [`wire`](super::wire) format and
+//! then run-length encoded with
+//! [PackBits](https://en.wikipedia.org/wiki/PackBits): a control byte `n`
+//! of 0 to 127 is followed by `n + 1` literal bytes, and one of 129 to 255
+//! by a single byte repeated `257 - n` times.
+//!
+//! Mostly empty or mostly full grids compress to a small fraction of
+//! [`Grid::ENCODED_LEN`], and no grid compresses to more than
+//! [`Grid::COMPRESSED_MAX_LEN`].
+//!
+//! See [`Grid::compress()`] and [`Grid::decompress()`].
+//!
+
+use super::wire::WireError;
+use crate::{cell::Cell, grid::Grid};
+
+/// The length of the header of a compressed grid, in bytes.
+pub const HEADER_LEN: usize = 8;
+
+/// The maximum number of bytes covered by one control byte.
+const MAX_RUN: usize = 128;
+
+/// The minimum number of repeated bytes encoded as a run.
+const MIN_RUN: usize = 3;
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    /// The number of bytes this grid's cells take up when packed.
+    const PACKED_LEN: usize = (W * H).div_ceil(8);
+
+    /// The maximum length of this grid when compressed, in bytes.
+    pub const COMPRESSED_MAX_LEN: usize =
+        HEADER_LEN + Self::PACKED_LEN + Self::PACKED_LEN.div_ceil(MAX_RUN);
+
+    ///
+    /// Compress this grid into `buf`, returning the compressed prefix of
+    /// it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WireError::BufferTooSmall`] if the compressed grid does
+    /// not fit in `buf`. A buffer of [`Self::COMPRESSED_MAX_LEN`] bytes is
+    /// always large enough.
+    ///
+    pub fn compress<'b>(&self, buf: &'b mut [u8]) -> Result<&'b mut [u8], WireError> {
+        let header = buf
+            .get_mut(..HEADER_LEN)
+            .ok_or(WireError::BufferTooSmall { needed: HEADER_LEN })?;
+        header.copy_from_slice(&self.generation.to_le_bytes());
+
+        let mut len = HEADER_LEN;
+        let mut push = |byte| {
+            *buf.get_mut(len)
+                .ok_or(WireError::BufferTooSmall { needed: len + 1 })? = byte;
+            len += 1;
+            Ok(())
+        };
+
+        // runs shorter than 3 bytes are left in literals, so that every
+        // repeat saves at least as many bytes as the literal it splits costs
+        let run = |i: usize| {
+            (i..Self::PACKED_LEN.min(i + MAX_RUN))
+                .take_while(|&j| self.packed_byte(j) == self.packed_byte(i))
+                .count()
+        };
+
+        let mut i = 0;
+        while i < Self::PACKED_LEN {
+            let count = run(i);
+            if count >= MIN_RUN {
+                push((257 - count) as u8)?;
+                push(self.packed_byte(i))?;
+                i += count;
+            } else {
+                let start = i;
+                i += count;
+                while i < Self::PACKED_LEN.min(start + MAX_RUN) && run(i) < MIN_RUN {
+                    i += 1;
+                }
+
+                push((i - start - 1) as u8)?;
+                for j in start..i {
+                    push(self.packed_byte(j))?;
+                }
+            }
+        }
+
+        Ok(&mut buf[..len])
+    }
+
+    ///
+    /// Decompress a grid compressed with [`Grid::compress()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WireError::BufferTooSmall`] if `bytes` is truncated, or
+    /// [`WireError::Malformed`] if it holds too many cells or trailing
+    /// bytes.
+    ///
+    pub fn decompress(bytes: &[u8]) -> Result<Self, WireError> {
+        let header = bytes
+            .get(..HEADER_LEN)
+            .ok_or(WireError::BufferTooSmall { needed: HEADER_LEN })?;
+
+        let mut grid = Self::new();
+        grid.generation = u64::from_le_bytes(header.try_into().expect("slice is 8 bytes"));
+
+        let mut pos = HEADER_LEN;
+        let mut i = 0;
+        while i < Self::PACKED_LEN {
+            let control = *bytes
+                .get(pos)
+                .ok_or(WireError::BufferTooSmall { needed: pos + 1 })?;
+            pos += 1;
+
+            let (count, literal) = match control {
+                0..=127 => (usize::from(control) + 1, true),
+                128 => continue,
+                _ => (257 - usize::from(control), false),
+            };
+            if i + count > Self::PACKED_LEN {
+                return Err(WireError::Malformed);
+            }
+
+            let needed = pos + if literal { count } else { 1 };
+            let data = bytes
+                .get(pos..needed)
+                .ok_or(WireError::BufferTooSmall { needed })?;
+            pos = needed;
+
+            for j in 0..count {
+                grid.set_packed_byte(i + j, data[if literal { j } else { 0 }]);
+            }
+            i += count;
+        }
+
+        if pos != bytes.len() {
+            return Err(WireError::Malformed);
+        }
+
+        Ok(grid)
+    }
+
+    /// Returns the `i`th byte of this grid's packed cells.
+    fn packed_byte(&self, i: usize) -> u8 {
+        (0..8)
+            .map(|bit| i * 8 + bit)
+            .take_while(|&index| index < W * H)
+            .fold(0, |byte, index| {
+                byte | (self.cells[index / W][index % W] as u8) << (index % 8)
+            })
+    }
+
+    /// Sets the `i`th byte of this grid's packed cells.
+    fn set_packed_byte(&mut self, i: usize, byte: u8) {
+        for index in (0..8)
+            .map(|bit| i * 8 + bit)
+            .take_while(|&index| index < W * H)
+        {
+            if byte & (1 << (index % 8)) != 0 {
+                self.cells[index / W][index % W] = Cell::Alive;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::Coord;
+
+    #[test]
+    fn round_trip() {
+        let mut grid = Grid::<64, 64>::new();
+        grid.generation = 1234;
+        for x in 10..20 {
+            grid[Coord(x, 3)] = Cell::Alive;
+        }
+        grid[Coord(63, 63)] = Cell::Alive;
+
+        let mut buf = [0; Grid::<64, 64>::COMPRESSED_MAX_LEN];
+        let bytes = grid.compress(&mut buf).unwrap();
+        assert!(bytes.len() < Grid::<64, 64>::ENCODED_LEN / 8);
+
+        let decompressed = Grid::<64, 64>::decompress(bytes).unwrap();
+        assert_eq!(decompressed.cells, grid.cells);
+        assert_eq!(decompressed.generation, 1234);
+    }
+
+    #[test]
+    fn incompressible() {
+        let mut grid = Grid::<40, 40>::new();
+        for (i, cell) in grid.cells.iter_mut().flatten().enumerate() {
+            if (i * i + i / 3) % 7 < 3 {
+                *cell = Cell::Alive;
+            }
+        }
+
+        let mut buf = [0; Grid::<40, 40>::COMPRESSED_MAX_LEN];
+        let bytes = grid.compress(&mut buf).unwrap();
+        assert_eq!(Grid::<40, 40>::decompress(bytes).unwrap().cells, grid.cells);
+    }
+
+    #[test]
+    fn worst_case() {
+        // pairs of repeated bytes between single bytes, as in A, B, B, C, D, D
+        let mut grid = Grid::<64, 64>::new();
+        for (i, cell) in grid.cells.iter_mut().flatten().enumerate() {
+            let byte = [0b1010_1010, 0, 0][i / 8 % 3] ^ (i / 24 % 2 * 0xff);
+            if byte & (1 << (i % 8)) != 0 {
+                *cell = Cell::Alive;
+            }
+        }
+
+        let mut buf = [0; Grid::<64, 64>::COMPRESSED_MAX_LEN];
+        let bytes = grid.compress(&mut buf).unwrap();
+        assert_eq!(Grid::<64, 64>::decompress(bytes).unwrap().cells, grid.cells);
+    }
+
+    #[test]
+    fn errors() {
+        let grid = Grid::<24, 1>::new();
+        let mut buf = [0; 9];
+        assert_eq!(
+            grid.compress(&mut buf),
+            Err(WireError::BufferTooSmall { needed: 10 })
+        );
+
+        let mut buf = [0; 10];
+        let bytes = grid.compress(&mut buf).unwrap();
+        assert_eq!(bytes[HEADER_LEN..], [254, 0]);
+
+        let mut buf = [0; 11];
+        let bytes = Grid::<16, 1>::new().compress(&mut buf).unwrap();
+        assert_eq!(bytes[HEADER_LEN..], [1, 0, 0]);
+
+        let mut bytes = [0; 10];
+        bytes[HEADER_LEN] = 254;
+        assert_eq!(
+            Grid::<16, 1>::decompress(&bytes).err(),
+            Some(WireError::Malformed)
+        );
+
+        bytes[HEADER_LEN] = 255;
+        assert_eq!(
+            Grid::<16, 1>::decompress(&bytes[..HEADER_LEN + 1]).err(),
+            Some(WireError::BufferTooSmall { needed: 10 })
+        );
+        assert_eq!(
+            Grid::<16, 1>::decompress(&[&bytes[..], &[0]].concat()).err(),
+            Some(WireError::Malformed)
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//!
+//! [CSV](https://www.rfc-editor.org/rfc/rfc4180) export of recorded
+//! per-generation statistics.
+//!
+//! See [`Recorder::write_csv()`] and [`write_csv()`].
+//!
+
+use std::io::{self, Write};
+
+use crate::sim::recorder::{Recorder, Sample};
+
+/// The header row written before the samples.
+pub const HEADER: &str = "generation,population,births,deaths,changed";
+
+impl<const N: usize> Recorder<N> {
+    ///
+    /// Write the remembered samples as CSV, from oldest to newest, with a
+    /// [`HEADER`] row.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while writing.
+    ///
+    pub fn write_csv(&self, writer: impl Write) -> io::Result<()> {
+        write_csv(self.samples(), writer)
+    }
+}
+
+///
+/// Write `samples` as CSV, one row per sample, with a [`HEADER`] row.
+///
+/// Rows end in `\n`, and no fields need quoting, so the output can be read
+/// by anything from spreadsheets to `pandas.read_csv()` and gnuplot.
+///
+/// # Errors
+///
+/// Returns any error encountered while writing.
+///
+pub fn write_csv<'a>(
+    samples: impl IntoIterator<Item = &'a Sample>,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    writeln!(writer, "{HEADER}")?;
+    for Sample { generation, stats } in samples {
+        writeln!(
+            writer,
+            "{generation},{},{},{},{}",
+            stats.population, stats.births, stats.deaths, stats.changed
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::{cell::Cell, grid::Grid, math::Coord, sim::Simulation};
+
+    #[test]
+    fn csv() {
+        // a blinker
+        let mut grid = Grid::<5, 5>::new();
+        for coord in [Coord(2, 1), Coord(2, 2), Coord(2, 3)] {
+            grid[coord] = Cell::Alive;
+        }
+
+        let mut recorder = Recorder::<4>::new();
+        recorder.record_grid(&grid);
+        Simulation::from(grid).run_for_observed(2, &mut recorder);
+
+        let mut out = Vec::new();
+        recorder.write_csv(&mut out).unwrap();
+        assert_eq!(
+            out,
+            b"generation,population,births,deaths,changed\n\
+              0,3,0,0,0\n\
+              1,3,2,2,4\n\
+              2,3,2,2,4\n"
+        );
+    }
+}
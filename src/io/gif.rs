@@ -0,0 +1,155 @@
+//!
+//! Animated GIF recordings of a run.
+//!
+//! See [`GifRecorder`].
+//!
+
+use std::{io::Write, vec::Vec};
+
+use gif::{Encoder, EncodingError, Frame, Repeat};
+
+use super::Palette;
+use crate::{cell::Cell, grid::Grid};
+
+///
+/// Records a run of a grid as an animated GIF.
+///
+/// The default recorder runs for 100 generations, recording every one of
+/// them at 4 pixels per cell and 10 frames per second, looping forever.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GifRecorder {
+    /// The number of generations to run for.
+    pub generations: u64,
+    /// The number of generations between frames; 1 records every generation.
+    pub step_by: u64,
+    /// The size of each cell, in pixels.
+    pub scale: u16,
+    /// The delay between frames, in hundredths of a second.
+    pub delay: u16,
+    pub palette: Palette,
+}
+
+impl Default for GifRecorder {
+    fn default() -> Self {
+        Self {
+            generations: 100,
+            step_by: 1,
+            scale: 4,
+            delay: 10,
+            palette: Palette::default(),
+        }
+    }
+}
+
+impl GifRecorder {
+    ///
+    /// Run `grid` for [`Self::generations`] generations, writing a frame
+    /// every [`Self::step_by`] generations, starting with `grid` itself.
+    ///
+    /// Returns the grid at the end of the run.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while encoding or writing the GIF,
+    /// including if the image would be too large, or if [`Self::scale`] or
+    /// [`Self::step_by`] is 0.
+    ///
+    pub fn record<const W: usize, const H: usize>(
+        &self,
+        grid: &Grid<W, H>,
+        writer: impl Write,
+    ) -> Result<Grid<W, H>, EncodingError> {
+        let invalid = |message| std::io::Error::new(std::io::ErrorKind::InvalidInput, message);
+        if self.scale == 0 || self.step_by == 0 {
+            return Err(invalid("scale and step must be non-zero").into());
+        }
+
+        let width = u16::try_from(W)
+            .ok()
+            .and_then(|w| w.checked_mul(self.scale))
+            .ok_or_else(|| invalid("image too large"))?;
+        let height = u16::try_from(H)
+            .ok()
+            .and_then(|h| h.checked_mul(self.scale))
+            .ok_or_else(|| invalid("image too large"))?;
+
+        let [dead, alive] = [self.palette.dead, self.palette.alive];
+        let mut encoder = Encoder::new(writer, width, height, &[dead, alive].concat())?;
+        encoder.set_repeat(Repeat::Infinite)?;
+
+        let mut grid = grid.clone();
+        let mut pixels = Vec::with_capacity(usize::from(width) * usize::from(height));
+        for generation in 0..=self.generations {
+            if generation > 0 {
+                grid = grid.step();
+            }
+            if generation % self.step_by != 0 {
+                continue;
+            }
+
+            pixels.clear();
+            for row in &grid.cells {
+                let start = pixels.len();
+                for &cell in row {
+                    let index = u8::from(cell == Cell::Alive);
+                    pixels.extend((0..self.scale).map(|_| index));
+                }
+                for _ in 1..self.scale {
+                    pixels.extend_from_within(start..start + usize::from(width));
+                }
+            }
+
+            encoder.write_frame(&Frame {
+                delay: self.delay,
+                width,
+                height,
+                buffer: pixels.as_slice().into(),
+                ..Frame::default()
+            })?;
+        }
+
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::vec;
+
+    use super::*;
+    use crate::math::Coord;
+
+    #[test]
+    fn record() {
+        let mut grid = Grid::<4, 4>::new();
+        for x in 0..3 {
+            grid[Coord(x, 1)] = Cell::Alive;
+        }
+
+        let recorder = GifRecorder {
+            generations: 5,
+            step_by: 2,
+            scale: 2,
+            ..GifRecorder::default()
+        };
+        let mut out = Vec::new();
+        let last = recorder.record(&grid, &mut out).unwrap();
+        assert_eq!(last.generation, 5);
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(out.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (8, 8));
+
+        let mut frames = vec![];
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            frames.push(frame.buffer.to_vec());
+        }
+        // generations 0, 2 and 4, when the blinker is horizontal.
+        assert_eq!(frames.len(), 3);
+        for frame in frames {
+            assert_eq!(frame[2 * 8..3 * 8], [1, 1, 1, 1, 1, 1, 0, 0]);
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//!
+//! JSON import and export.
+//!
+//! Grids are represented as an object of their extents, rule, generation,
+//! and a list of `[x, y]` coordinates of their live cells:
+//!
+//! ```json
+//! {"width":8,"height":8,"rule":"B3/S23","generation":0,"cells":[[1,0],[2,1]]}
+//! ```
+//!
+//! See [`Grid::to_json()`] and [`Grid::from_json()`], or
+//! [`Grid::to_json_with()`] and [`Grid::from_json_with()`] for other
+//! [`LifeLike`] rules.
+//!
+
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{cell::Cell, grid::Grid, math::Coord, rule::LifeLike};
+
+#[derive(Serialize, Deserialize)]
+struct Json<'a> {
+    width: usize,
+    height: usize,
+    #[serde(borrow, default = "default_rule")]
+    rule: Cow<'a, str>,
+    #[serde(default)]
+    generation: u64,
+    cells: Vec<Coord>,
+}
+
+fn default_rule() -> Cow<'static, str> {
+    LifeLike::default().to_string().into()
+}
+
+///
+/// An error encountered while importing a grid from JSON.
+///
+#[derive(Debug)]
+pub enum JsonError {
+    /// The JSON is malformed, or does not describe a grid.
+    Syntax(serde_json::Error),
+    /// The JSON describes a grid of other extents.
+    ExtentsMismatch { width: usize, height: usize },
+    /// The JSON describes a grid with a rule that is not [`LifeLike`], or is
+    /// not the one expected.
+    UnsupportedRule,
+    /// A live cell is outside of the grid.
+    OutOfBounds(Coord),
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax(err) => write!(f, "invalid JSON: {err}"),
+            Self::ExtentsMismatch { width, height } => {
+                write!(f, "mismatched extents, JSON grid is {width}x{height}")
+            }
+            Self::UnsupportedRule => write!(f, "unsupported rule"),
+            Self::OutOfBounds(coord) => write!(f, "cell {coord:?} out of bounds"),
+        }
+    }
+}
+
+impl core::error::Error for JsonError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Syntax(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for JsonError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Syntax(err)
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    /// Export this grid to JSON, under Conway's Game of Life.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        self.to_json_with(&LifeLike::CONWAY)
+    }
+
+    /// Export this grid to JSON, under `rule`.
+    #[must_use]
+    pub fn to_json_with(&self, rule: &LifeLike) -> String {
+        let json = Json {
+            width: W,
+            height: H,
+            rule: rule.to_string().into(),
+            generation: self.generation,
+            cells: (0..H)
+                .flat_map(|y| (0..W).map(move |x| Coord(x, y)))
+                .filter(|&coord| self[coord] == Cell::Alive)
+                .collect(),
+        };
+
+        serde_json::to_string(&json).expect("grids are always serializable")
+    }
+
+    ///
+    /// Import a grid from JSON.
+    ///
+    /// The `rule` and `generation` fields are optional, defaulting to
+    /// `B3/S23` and 0 respectively.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`JsonError`] if the JSON is malformed, or describes a grid
+    /// which does not fit this one or is not under Conway's Game of Life.
+    ///
+    pub fn from_json(json: &str) -> Result<Self, JsonError> {
+        match Self::from_json_with(json)? {
+            (grid, LifeLike::CONWAY) => Ok(grid),
+            _ => Err(JsonError::UnsupportedRule),
+        }
+    }
+
+    ///
+    /// Import a grid from JSON, along with the [`LifeLike`] rule it is
+    /// under.
+    ///
+    /// See [`Grid::from_json()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`JsonError`] if the JSON is malformed, or describes a grid
+    /// which does not fit this one or is not under a [`LifeLike`] rule.
+    ///
+    pub fn from_json_with(json: &str) -> Result<(Self, LifeLike), JsonError> {
+        let json = serde_json::from_str::<Json>(json)?;
+        if (json.width, json.height) != (W, H) {
+            return Err(JsonError::ExtentsMismatch {
+                width: json.width,
+                height: json.height,
+            });
+        }
+        let rule = json
+            .rule
+            .parse::<LifeLike>()
+            .map_err(|_| JsonError::UnsupportedRule)?;
+
+        let mut grid = Self::new();
+        grid.generation = json.generation;
+        for coord in json.cells {
+            if coord.0 >= W || coord.1 >= H {
+                return Err(JsonError::OutOfBounds(coord));
+            }
+            grid[coord] = Cell::Alive;
+        }
+
+        Ok((grid, rule))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut grid = Grid::<4, 3>::new();
+        grid[Coord(1, 0)] = Cell::Alive;
+        grid[Coord(3, 2)] = Cell::Alive;
+        grid.generation = 5;
+
+        let json = grid.to_json();
+        assert_eq!(
+            json,
+            r#"{"width":4,"height":3,"rule":"B3/S23","generation":5,"cells":[[1,0],[3,2]]}"#
+        );
+
+        let imported = Grid::<4, 3>::from_json(&json).unwrap();
+        assert_eq!(imported.cells, grid.cells);
+        assert_eq!(imported.generation, 5);
+
+        for rule in [LifeLike::HIGH_LIFE, LifeLike::new(0, 1 << 8)] {
+            let json = grid.to_json_with(&rule);
+            let (imported, imported_rule) = Grid::<4, 3>::from_json_with(&json).unwrap();
+            assert_eq!(imported.cells, grid.cells);
+            assert_eq!(imported_rule, rule);
+        }
+        let json = grid.to_json_with(&LifeLike::HIGH_LIFE);
+        assert!(json.contains(r#""rule":"B36/S23""#));
+
+        let json = r#"{"width":4,"height":3,"rule":"23/3","cells":[]}"#;
+        assert!(Grid::<4, 3>::from_json(json).is_ok());
+    }
+
+    #[test]
+    fn errors() {
+        let err = |json| Grid::<4, 3>::from_json(json).unwrap_err();
+
+        assert!(matches!(err("{"), JsonError::Syntax(_)));
+        assert!(matches!(
+            err(r#"{"width":3,"height":4,"cells":[]}"#),
+            JsonError::ExtentsMismatch {
+                width: 3,
+                height: 4
+            }
+        ));
+        assert!(matches!(
+            err(r#"{"width":4,"height":3,"rule":"B36/S23","cells":[]}"#),
+            JsonError::UnsupportedRule
+        ));
+        assert!(matches!(
+            Grid::<4, 3>::from_json_with(r#"{"width":4,"height":3,"rule":"W110","cells":[]}"#),
+            Err(JsonError::UnsupportedRule)
+        ));
+        assert!(matches!(
+            err(r#"{"width":4,"height":3,"cells":[[4,0]]}"#),
+            JsonError::OutOfBounds(Coord(4, 0))
+        ));
+    }
+}
@@ -0,0 +1,75 @@
+//!
+//! [Plaintext](https://conwaylife.com/wiki/Plaintext) patterns, as in
+//! `.cells` files.
+//!
+//! See [`PatternBuf::from_plaintext()`].
+//!
+
+use alloc::vec::Vec;
+
+use super::SyntaxError;
+use crate::{
+    cell::{Cell, Charset},
+    math::Coord,
+    pattern::PatternBuf,
+};
+
+impl PatternBuf {
+    ///
+    /// Parse a plaintext pattern, where `O` or `*` are live cells and `.`
+    /// are dead cells, ignoring `!` comment lines.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SyntaxError`] if a line contains any other character.
+    ///
+    pub fn from_plaintext(src: &str) -> Result<Self, SyntaxError> {
+        let mut cells = Vec::new();
+        let rows = src
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.starts_with('!'));
+
+        for (y, (i, row)) in rows.enumerate() {
+            for (x, c) in row.trim_end().chars().enumerate() {
+                match Cell::from_char(c, Charset::PLAINTEXT) {
+                    Some(Cell::Alive) => cells.push(Coord(x, y)),
+                    Some(Cell::Dead) => {}
+                    // Some plaintext files use `*` for live cells instead.
+                    None if c == '*' => cells.push(Coord(x, y)),
+                    None => return Err(SyntaxError { line: i + 1 }),
+                }
+            }
+        }
+
+        Ok(Self::new(cells))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_plaintext() {
+        let pattern = PatternBuf::from_plaintext("!Name: Glider\n.O\n..O\nOOO\n").unwrap();
+        assert_eq!(
+            pattern.into_cells(),
+            [
+                Coord(1, 0),
+                Coord(2, 1),
+                Coord(0, 2),
+                Coord(1, 2),
+                Coord(2, 2)
+            ]
+        );
+
+        let pattern = PatternBuf::from_plaintext("\n*.*").unwrap();
+        assert_eq!(pattern.as_pattern().extents(), Coord(3, 2));
+
+        assert_eq!(
+            PatternBuf::from_plaintext("!\n.O\nx"),
+            Err(SyntaxError { line: 3 })
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//!
+//! PNG image export.
+//!
+//! See [`Grid::save_png()`] and [`Grid::write_png()`].
+//!
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    vec::Vec,
+};
+
+use png::{BitDepth, ColorType, Encoder, EncodingError};
+
+use crate::{grid::Grid, math::Coord, render::color::ColorPolicy};
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Save this grid as a PNG image at `path`, with each cell drawn as a
+    /// `cell_size` by `cell_size` square, colored by `palette`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while encoding or writing the image,
+    /// including if `cell_size` is 0.
+    ///
+    pub fn save_png(
+        &self,
+        path: impl AsRef<Path>,
+        cell_size: u32,
+        palette: impl ColorPolicy,
+    ) -> Result<(), EncodingError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_png(&mut writer, cell_size, palette)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    ///
+    /// Write this grid as a PNG image, with each cell drawn as a `cell_size`
+    /// by `cell_size` square, colored by `palette`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while encoding or writing the image,
+    /// including if `cell_size` is 0.
+    ///
+    pub fn write_png(
+        &self,
+        writer: impl Write,
+        cell_size: u32,
+        palette: impl ColorPolicy,
+    ) -> Result<(), EncodingError> {
+        let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "image too large");
+        if cell_size == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "cell size is 0").into());
+        }
+
+        let width = u32::try_from(W)
+            .ok()
+            .and_then(|w| w.checked_mul(cell_size))
+            .ok_or_else(too_large)?;
+        let height = u32::try_from(H)
+            .ok()
+            .and_then(|h| h.checked_mul(cell_size))
+            .ok_or_else(too_large)?;
+
+        let mut encoder = Encoder::new(writer, width, height);
+        encoder.set_color(ColorType::Rgb);
+        encoder.set_depth(BitDepth::Eight);
+
+        let mut data = Vec::with_capacity(width as usize * height as usize * 3);
+        for (y, row) in self.cells.iter().enumerate() {
+            let start = data.len();
+            for (x, &cell) in row.iter().enumerate() {
+                let color = palette.color(Coord(x, y), cell);
+                for _ in 0..cell_size {
+                    data.extend_from_slice(&color);
+                }
+            }
+            for _ in 1..cell_size {
+                data.extend_from_within(start..start + width as usize * 3);
+            }
+        }
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&data)?;
+        writer.finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{vec, vec::Vec};
+
+    use super::*;
+    use crate::{cell::Cell, io::Palette};
+
+    #[test]
+    fn write_png() {
+        let mut grid = Grid::<3, 2>::new();
+        grid[Coord(1, 1)] = Cell::Alive;
+
+        let palette = Palette {
+            alive: [1, 2, 3],
+            dead: [4, 5, 6],
+        };
+        let mut out = Vec::new();
+        grid.write_png(&mut out, 2, palette).unwrap();
+
+        let mut reader = png::Decoder::new(io::Cursor::new(out)).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (6, 4));
+
+        let pixel = |x: usize, y: usize| &pixels[(y * 6 + x) * 3..][..3];
+        assert_eq!(pixel(0, 0), [4, 5, 6]);
+        assert_eq!(pixel(2, 2), [1, 2, 3]);
+        assert_eq!(pixel(3, 3), [1, 2, 3]);
+        assert_eq!(pixel(4, 3), [4, 5, 6]);
+
+        assert!(grid.write_png(io::sink(), 0, palette).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+//!
+//! Binary [PBM and PGM](https://netpbm.sourceforge.net/doc/pbm.html) image
+//! export.
+//!
+//! See [`Grid::write_pbm()`] and [`write_pgm()`].
+//!
+
+use std::{
+    io::{self, Write},
+    vec,
+};
+
+use crate::{cell::Cell, grid::Grid};
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Write this grid as a binary PBM image, with one pixel per cell.
+    ///
+    /// Live cells are black, and dead cells are white.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while writing.
+    ///
+    pub fn write_pbm(&self, mut writer: impl Write) -> io::Result<()> {
+        write!(writer, "P4\n{W} {H}\n")?;
+
+        let mut row = vec![0; W.div_ceil(8)];
+        for cells in &self.cells {
+            row.fill(0);
+            for (x, &cell) in cells.iter().enumerate() {
+                if cell == Cell::Alive {
+                    row[x / 8] |= 0x80 >> (x % 8);
+                }
+            }
+            writer.write_all(&row)?;
+        }
+
+        Ok(())
+    }
+}
+
+///
+/// Write a layer of per-cell values, such as cell ages or activity, as a
+/// binary PGM image, with one pixel per cell.
+///
+/// Values are gray levels from 0 (black) to `max` (white); `max` must be
+/// at least 1, and values above it are clamped.
+///
+/// # Errors
+///
+/// Returns any error encountered while writing, or
+/// [`io::ErrorKind::InvalidInput`] if `max` is 0.
+///
+pub fn write_pgm<const W: usize, const H: usize>(
+    layer: &[[u8; W]; H],
+    max: u8,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    if max == 0 {
+        return Err(io::ErrorKind::InvalidInput.into());
+    }
+
+    write!(writer, "P5\n{W} {H}\n{max}\n")?;
+    for row in layer {
+        writer.write_all(&row.map(|value| value.min(max)))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::vec::Vec;
+
+    use super::*;
+    use crate::math::Coord;
+
+    #[test]
+    fn pbm() {
+        let mut grid = Grid::<10, 2>::new();
+        grid[Coord(0, 0)] = Cell::Alive;
+        grid[Coord(9, 1)] = Cell::Alive;
+
+        let mut out = Vec::new();
+        grid.write_pbm(&mut out).unwrap();
+        assert_eq!(out, b"P4\n10 2\n\x80\x00\x00\x40");
+    }
+
+    #[test]
+    fn pgm() {
+        let mut out = Vec::new();
+        write_pgm(&[[0, 3, 9]], 5, &mut out).unwrap();
+        assert_eq!(out, b"P5\n3 1\n5\n\x00\x03\x05");
+
+        assert!(write_pgm(&[[0]], 0, &mut out).is_err());
+    }
+}
@@ -0,0 +1,286 @@
+//!
+//! [RLE](https://conwaylife.com/wiki/Run_Length_Encoded) patterns, the
+//! de facto standard pattern format.
+//!
+//! See [`Rle::parse()`] and [`Grid::write_rle()`].
+//!
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+use super::SyntaxError;
+use crate::{cell::Cell, grid::Grid, math::Coord, pattern::PatternBuf};
+
+/// The maximum length of a line of run-length encoded cells.
+const LINE_LEN: usize = 70;
+
+/// The maximum extents of a parsed pattern, which keeps huge run counts from
+/// exhausting memory.
+const MAX_EXTENT: usize = 1 << 20;
+
+/// The maximum population of a parsed pattern, which keeps many long runs
+/// from exhausting memory.
+const MAX_POPULATION: usize = 1 << 20;
+
+///
+/// A parsed RLE pattern, along with its metadata.
+///
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Rle {
+    pub pattern: PatternBuf,
+    /// The name given by a `#N` line, if any.
+    pub name: Option<String>,
+    /// The rule given in the header line, if any.
+    pub rule: Option<String>,
+}
+
+impl Rle {
+    ///
+    /// Parse an RLE pattern.
+    ///
+    /// The extents in the header line are ignored in favor of those of the
+    /// cells. Cells of any state other than `b` or `.` are alive.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SyntaxError`] if the header line is missing or malformed,
+    /// if the cells contain an unexpected character, or if they extend more
+    /// than 2<sup>20</sup> cells in either direction or number more than
+    /// 2<sup>20</sup> live cells.
+    ///
+    pub fn parse(src: &str) -> Result<Self, SyntaxError> {
+        let mut lines = src
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        let mut rle = Self::default();
+        let header = loop {
+            let (line, text) = lines.next().ok_or(SyntaxError { line: 1 })?;
+            match text.strip_prefix('#') {
+                Some(comment) => {
+                    if let Some(name) = comment.strip_prefix('N') {
+                        rle.name = Some(name.trim().into());
+                    }
+                }
+                None => break (line, text),
+            }
+        };
+
+        for field in header.1.split(',') {
+            let (key, value) = field
+                .split_once('=')
+                .ok_or(SyntaxError { line: header.0 })?;
+            match key.trim() {
+                "rule" => rle.rule = Some(value.trim().into()),
+                "x" | "y" => {}
+                _ => return Err(SyntaxError { line: header.0 }),
+            }
+        }
+
+        let mut cells = Vec::new();
+        let (mut x, mut y, mut count) = (0, 0, None::<usize>);
+        'lines: for (line, text) in lines {
+            for c in text.chars() {
+                if let Some(digit) = c.to_digit(10) {
+                    count = count
+                        .unwrap_or(0)
+                        .checked_mul(10)
+                        .and_then(|n| n.checked_add(digit as usize));
+                    if count.is_none() {
+                        return Err(SyntaxError { line });
+                    }
+                    continue;
+                }
+
+                let n = count.take().unwrap_or(1);
+                let advance = |from: usize| {
+                    from.checked_add(n)
+                        .filter(|&to| to <= MAX_EXTENT)
+                        .ok_or(SyntaxError { line })
+                };
+                match c {
+                    'b' | '.' => x = advance(x)?,
+                    '$' => (x, y) = (0, advance(y)?),
+                    '!' => break 'lines,
+                    c if c.is_ascii_alphabetic() => {
+                        let to = advance(x)?;
+                        if cells.len() + n > MAX_POPULATION {
+                            return Err(SyntaxError { line });
+                        }
+                        cells.extend((x..to).map(|x| Coord(x, y)));
+                        x = to;
+                    }
+                    c if c.is_whitespace() => count = (n > 1).then_some(n),
+                    _ => return Err(SyntaxError { line }),
+                }
+            }
+        }
+
+        rle.pattern = PatternBuf::new(cells);
+        Ok(rle)
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Write the live cells of this grid as an RLE pattern under `rule`,
+    /// cropped to their bounding box.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while writing.
+    ///
+    pub fn write_rle(&self, mut writer: impl Write, rule: &str) -> fmt::Result {
+        let alive = |y: &usize| self.cells[*y].contains(&Cell::Alive);
+        let (Some(top), Some(bottom)) = ((0..H).find(alive), (0..H).rev().find(alive)) else {
+            return writeln!(writer, "x = 0, y = 0, rule = {rule}\n!");
+        };
+        let column = |x: &usize| (top..=bottom).any(|y| self.cells[y][*x] == Cell::Alive);
+        let left = (0..W).find(column).unwrap_or_default();
+        let right = (0..W).rev().find(column).unwrap_or_default();
+
+        writeln!(
+            writer,
+            "x = {}, y = {}, rule = {rule}",
+            right - left + 1,
+            bottom - top + 1
+        )?;
+
+        let mut out = Tokens {
+            writer,
+            line_len: 0,
+        };
+        let mut newlines = 0;
+        for row in &self.cells[top..=bottom] {
+            let row = &row[left..=right];
+            let Some(end) = row.iter().rposition(|&cell| cell == Cell::Alive) else {
+                newlines += 1;
+                continue;
+            };
+            if newlines > 0 {
+                out.token(newlines, '$')?;
+            }
+            newlines = 1;
+
+            let mut x = 0;
+            while x <= end {
+                let cell = row[x];
+                let len = row[x..=end].iter().take_while(|&&c| c == cell).count();
+                out.token(len, if cell == Cell::Alive { 'o' } else { 'b' })?;
+                x += len;
+            }
+        }
+        out.token(1, '!')?;
+        out.writer.write_char('\n')
+    }
+}
+
+/// Writes run-length encoded tokens, wrapping lines at [`LINE_LEN`].
+struct Tokens<T> {
+    writer: T,
+    line_len: usize,
+}
+
+impl<T: Write> Tokens<T> {
+    fn token(&mut self, count: usize, tag: char) -> fmt::Result {
+        let len = if count > 1 {
+            count.ilog10() as usize + 2
+        } else {
+            1
+        };
+        if self.line_len + len > LINE_LEN {
+            self.writer.write_char('\n')?;
+            self.line_len = 0;
+        }
+        self.line_len += len;
+
+        if count > 1 {
+            write!(self.writer, "{count}{tag}")
+        } else {
+            self.writer.write_char(tag)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::String;
+
+    use super::*;
+
+    const GLIDER: &str = "#N Glider\n#C A comment.\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!\n";
+
+    #[test]
+    fn parse() {
+        let rle = Rle::parse(GLIDER).unwrap();
+        assert_eq!(rle.name.as_deref(), Some("Glider"));
+        assert_eq!(rle.rule.as_deref(), Some("B3/S23"));
+        assert_eq!(
+            rle.pattern.into_cells(),
+            [
+                Coord(1, 0),
+                Coord(2, 1),
+                Coord(0, 2),
+                Coord(1, 2),
+                Coord(2, 2)
+            ]
+        );
+
+        let rle = Rle::parse("x = 12, y = 3\n12o2$\no!").unwrap();
+        assert_eq!(rle.rule, None);
+        assert_eq!(rle.pattern.as_pattern().extents(), Coord(12, 3));
+
+        assert_eq!(Rle::parse("bo$"), Err(SyntaxError { line: 1 }));
+        assert_eq!(Rle::parse("x = 1\n\nbo?"), Err(SyntaxError { line: 3 }));
+        assert_eq!(Rle::parse(""), Err(SyntaxError { line: 1 }));
+        assert_eq!(
+            Rle::parse("x = 1\n4000000000o!"),
+            Err(SyntaxError { line: 2 })
+        );
+        assert_eq!(
+            Rle::parse("x = 1\n1048576bo!"),
+            Err(SyntaxError { line: 2 })
+        );
+        assert_eq!(
+            Rle::parse("x = 1\n$1048576$o!"),
+            Err(SyntaxError { line: 2 })
+        );
+        assert!(Rle::parse("x = 1\n1048575bo!").is_ok());
+        assert_eq!(
+            Rle::parse("x = 1\n1048576o$1048576o$o!"),
+            Err(SyntaxError { line: 2 })
+        );
+    }
+
+    #[test]
+    fn write_rle() {
+        let mut grid = Grid::<8, 8>::new();
+        grid.paste(
+            &Rle::parse(GLIDER).unwrap().pattern.as_pattern(),
+            Coord(2, 3),
+        );
+
+        let mut rle = String::new();
+        grid.write_rle(&mut rle, "B3/S23").unwrap();
+        assert_eq!(rle, "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+
+        let mut grid = Grid::<80, 3>::new();
+        grid[Coord(0, 0)] = Cell::Alive;
+        for x in (0..80).step_by(2) {
+            grid[Coord(x, 2)] = Cell::Alive;
+        }
+        let mut rle = String::new();
+        grid.write_rle(&mut rle, "B3/S23").unwrap();
+        assert!(rle.lines().all(|line| line.len() <= LINE_LEN));
+        assert!(rle.starts_with("x = 79, y = 3, rule = B3/S23\no2$obobob"));
+
+        let parsed = Rle::parse(&rle).unwrap().pattern;
+        assert_eq!(parsed.as_pattern().population(), grid.population());
+
+        let mut rle = String::new();
+        Grid::<4, 4>::new().write_rle(&mut rle, "B3/S23").unwrap();
+        assert_eq!(rle, "x = 0, y = 0, rule = B3/S23\n!\n");
+    }
+}
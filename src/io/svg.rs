@@ -0,0 +1,133 @@
+//!
+//! SVG image export.
+//!
+//! See [`Grid::write_svg()`].
+//!
+
+use core::fmt::{self, Write};
+
+use super::Palette;
+use crate::{cell::Cell, grid::Grid};
+
+///
+/// Options for [`Grid::write_svg()`].
+///
+/// By default, cells are 10 pixels wide, and runs of live cells in a row
+/// are merged into one rectangle.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SvgOptions {
+    /// The size of each cell, in pixels.
+    pub scale: u32,
+    pub palette: Palette,
+    /// Whether to merge runs of live cells in a row into one rectangle,
+    /// rather than drawing one rectangle per live cell.
+    pub merge_runs: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            scale: 10,
+            palette: Palette::default(),
+            merge_runs: true,
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Write this grid as an SVG image.
+    ///
+    /// The image's user units are cells, so it can be scaled to any size by
+    /// changing its `width` and `height` attributes.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered while writing.
+    ///
+    pub fn write_svg(&self, mut writer: impl Write, options: &SvgOptions) -> fmt::Result {
+        let [width, height] = [W, H].map(|n| n as u64 * u64::from(options.scale));
+        let [alive, dead] = [options.palette.alive, options.palette.dead];
+
+        writeln!(
+            writer,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {W} {H}" shape-rendering="crispEdges">"#
+        )?;
+        writeln!(
+            writer,
+            r#"<rect width="{W}" height="{H}" fill="{}"/>"#,
+            Hex(dead)
+        )?;
+        writeln!(writer, r#"<g fill="{}">"#, Hex(alive))?;
+
+        for (y, runs) in self.runs().enumerate() {
+            let mut x = 0;
+            for (cell, len) in runs {
+                if cell == Cell::Alive {
+                    let width = if options.merge_runs { len } else { 1 };
+                    for x in (x..x + len).step_by(width) {
+                        writeln!(
+                            writer,
+                            r#"<rect x="{x}" y="{y}" width="{width}" height="1"/>"#
+                        )?;
+                    }
+                }
+                x += len;
+            }
+        }
+
+        writeln!(writer, "</g>\n</svg>")
+    }
+}
+
+/// Formats an RGB triple as a hex color.
+struct Hex([u8; 3]);
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [r, g, b] = self.0;
+        write!(f, "#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use std::string::String;
+
+    use super::*;
+    use crate::math::Coord;
+
+    #[test]
+    fn write_svg() {
+        let mut grid = Grid::<4, 2>::new();
+        for coord in [Coord(0, 0), Coord(1, 0), Coord(3, 0), Coord(2, 1)] {
+            grid[coord] = Cell::Alive;
+        }
+
+        let mut svg = String::new();
+        grid.write_svg(&mut svg, &SvgOptions::default()).unwrap();
+        assert_eq!(
+            svg,
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="40" height="20" viewBox="0 0 4 2" shape-rendering="crispEdges">
+<rect width="4" height="2" fill="#ffffff"/>
+<g fill="#000000">
+<rect x="0" y="0" width="2" height="1"/>
+<rect x="3" y="0" width="1" height="1"/>
+<rect x="2" y="1" width="1" height="1"/>
+</g>
+</svg>
+"##
+        );
+
+        let options = SvgOptions {
+            merge_runs: false,
+            ..SvgOptions::default()
+        };
+        let mut svg = String::new();
+        grid.write_svg(&mut svg, &options).unwrap();
+        assert_eq!(svg.matches("<rect x=").count(), 4);
+    }
+}
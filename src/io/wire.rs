@@ -0,0 +1,354 @@
+//!
+//! A compact binary format for sending grids over the wire.
+//!
+//! An encoded grid consists of a [`HEADER_LEN`]-byte header, made up of the
+//! grid's width and height as little-endian `u32`s and its generation as a
+//! little-endian `u64`, followed by its cells packed 8 to a byte in
+//! row-major order, least significant bit first.
+//!
+//! An encoded diff between two grids (see [`Grid::diff()`]) consists of a
+//! [`DIFF_HEADER_LEN`]-byte header, made up of the target grid's generation
+//! as a little-endian `u64` and the number of changed cells as a
+//! little-endian `u32`, followed by one
+//! [LEB128](https://en.wikipedia.org/wiki/LEB128) varint per changed cell.
+//! Each varint holds the number of unchanged cells since the previous
+//! changed cell in row-major order, shifted left by one, with the new state
+//! of the cell in the lowest bit.
+//!
+//! See [`Grid::to_bytes()`], [`Grid::from_bytes()`], [`Grid::diff_to_bytes()`]
+//! and [`Grid::apply_diff_bytes()`].
+//!
+
+use core::fmt;
+
+use crate::{cell::Cell, grid::Grid, math::Coord};
+
+/// The length of the header of an encoded grid, in bytes.
+pub const HEADER_LEN: usize = 16;
+
+/// The length of the header of an encoded diff, in bytes.
+pub const DIFF_HEADER_LEN: usize = 12;
+
+///
+/// An error encountered while encoding or decoding a grid.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WireError {
+    /// The buffer is too small; `needed` bytes are required.
+    BufferTooSmall { needed: usize },
+    /// The encoded grid has different extents than the grid decoded into.
+    ExtentsMismatch { width: u32, height: u32 },
+    /// The encoded data is malformed, or refers to cells out of bounds.
+    Malformed,
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferTooSmall { needed } => {
+                write!(f, "buffer too small, {needed} bytes needed")
+            }
+            Self::ExtentsMismatch { width, height } => {
+                write!(f, "mismatched extents, encoded grid is {width}x{height}")
+            }
+            Self::Malformed => write!(f, "malformed data"),
+        }
+    }
+}
+
+impl core::error::Error for WireError {}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    /// The length of this grid when encoded, in bytes.
+    pub const ENCODED_LEN: usize = HEADER_LEN + (W * H).div_ceil(8);
+
+    ///
+    /// Encode this grid into `buf`, returning the encoded prefix of it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WireError::BufferTooSmall`] if `buf` is shorter than
+    /// [`Self::ENCODED_LEN`].
+    ///
+    pub fn to_bytes<'b>(&self, buf: &'b mut [u8]) -> Result<&'b mut [u8], WireError> {
+        let buf = buf
+            .get_mut(..Self::ENCODED_LEN)
+            .ok_or(WireError::BufferTooSmall {
+                needed: Self::ENCODED_LEN,
+            })?;
+        let (header, body) = buf.split_at_mut(HEADER_LEN);
+
+        header[0..4].copy_from_slice(&(W as u32).to_le_bytes());
+        header[4..8].copy_from_slice(&(H as u32).to_le_bytes());
+        header[8..16].copy_from_slice(&self.generation.to_le_bytes());
+
+        body.fill(0);
+        for (i, cell) in self.cells.iter().flatten().enumerate() {
+            body[i / 8] |= (*cell as u8) << (i % 8);
+        }
+
+        Ok(buf)
+    }
+
+    ///
+    /// Decode a grid from the start of `bytes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WireError::BufferTooSmall`] if `bytes` is truncated, or
+    /// [`WireError::ExtentsMismatch`] if it holds a grid of other extents.
+    ///
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WireError> {
+        let header = bytes
+            .get(..HEADER_LEN)
+            .ok_or(WireError::BufferTooSmall { needed: HEADER_LEN })?;
+
+        let width = u32::from_le_bytes(header[0..4].try_into().expect("slice is 4 bytes"));
+        let height = u32::from_le_bytes(header[4..8].try_into().expect("slice is 4 bytes"));
+        if (width as usize, height as usize) != (W, H) {
+            return Err(WireError::ExtentsMismatch { width, height });
+        }
+
+        let body = bytes
+            .get(HEADER_LEN..Self::ENCODED_LEN)
+            .ok_or(WireError::BufferTooSmall {
+                needed: Self::ENCODED_LEN,
+            })?;
+
+        let mut grid = Self::new();
+        grid.generation = u64::from_le_bytes(header[8..16].try_into().expect("slice is 8 bytes"));
+        for (i, cell) in grid.cells.iter_mut().flatten().enumerate() {
+            if body[i / 8] & (1 << (i % 8)) != 0 {
+                *cell = Cell::Alive;
+            }
+        }
+
+        Ok(grid)
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Encode the diff from this grid to `other` into `buf`, returning the
+    /// encoded prefix of it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WireError::BufferTooSmall`] if the diff does not fit in
+    /// `buf`.
+    ///
+    pub fn diff_to_bytes<'b>(
+        &self,
+        other: &Self,
+        buf: &'b mut [u8],
+    ) -> Result<&'b mut [u8], WireError> {
+        let changes = self.diff(other).count();
+        let too_small = |needed| WireError::BufferTooSmall { needed };
+
+        let header = buf
+            .get_mut(..DIFF_HEADER_LEN)
+            .ok_or(too_small(DIFF_HEADER_LEN))?;
+        header[0..8].copy_from_slice(&other.generation.to_le_bytes());
+        header[8..12].copy_from_slice(&(changes as u32).to_le_bytes());
+
+        let mut len = DIFF_HEADER_LEN;
+        let mut next_index = 0;
+        for (Coord(x, y), cell) in self.diff(other) {
+            let index = y * W + x;
+            let mut value = (index - next_index) << 1 | cell as usize;
+            next_index = index + 1;
+
+            loop {
+                let byte = buf.get_mut(len).ok_or(too_small(len + 1))?;
+                *byte = (value & 0x7f) as u8;
+                len += 1;
+                value >>= 7;
+                if value == 0 {
+                    break;
+                }
+                *byte |= 0x80;
+            }
+        }
+
+        Ok(&mut buf[..len])
+    }
+
+    ///
+    /// Apply an encoded diff to this grid, setting its generation to that
+    /// of the diff's target.
+    ///
+    /// The grid is left unchanged if the diff is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WireError::BufferTooSmall`] if `bytes` is truncated, or
+    /// [`WireError::Malformed`] if it is not a valid diff for this grid.
+    ///
+    pub fn apply_diff_bytes(&mut self, bytes: &[u8]) -> Result<(), WireError> {
+        let header = bytes
+            .get(..DIFF_HEADER_LEN)
+            .ok_or(WireError::BufferTooSmall {
+                needed: DIFF_HEADER_LEN,
+            })?;
+        let generation = u64::from_le_bytes(header[0..8].try_into().expect("slice is 8 bytes"));
+        let changes = u32::from_le_bytes(header[8..12].try_into().expect("slice is 4 bytes"));
+
+        let decode = || {
+            DiffDecoder::<W, H> {
+                bytes: &bytes[DIFF_HEADER_LEN..],
+                next_index: 0,
+            }
+            .take(changes as usize)
+        };
+
+        let mut decoded = 0;
+        for change in decode() {
+            change?;
+            decoded += 1;
+        }
+        if decoded != changes {
+            return Err(WireError::BufferTooSmall {
+                needed: bytes.len() + 1,
+            });
+        }
+
+        self.apply_diff(decode().map(|change| change.expect("diff is valid")));
+        self.generation = generation;
+        Ok(())
+    }
+}
+
+/// Decodes the changed cells of an encoded diff.
+struct DiffDecoder<'a, const W: usize, const H: usize> {
+    bytes: &'a [u8],
+    next_index: usize,
+}
+
+impl<const W: usize, const H: usize> Iterator for DiffDecoder<'_, W, H> {
+    type Item = Result<(Coord, Cell), WireError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut value = 0usize;
+        for shift in (0..).step_by(7) {
+            let (&byte, rest) = self.bytes.split_first()?;
+            self.bytes = rest;
+
+            let Some(bits) = usize::from(byte & 0x7f)
+                .checked_shl(shift)
+                .filter(|bits| bits >> shift == usize::from(byte & 0x7f))
+            else {
+                return Some(Err(WireError::Malformed));
+            };
+            value |= bits;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+
+        let Some(index) = self
+            .next_index
+            .checked_add(value >> 1)
+            .filter(|&index| index < W * H)
+        else {
+            return Some(Err(WireError::Malformed));
+        };
+        self.next_index = index + 1;
+
+        Some(Ok((
+            Coord(index % W, index / W),
+            Cell::from(value & 1 != 0),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut grid = Grid::<5, 3>::new();
+        grid[Coord(0, 0)] = Cell::Alive;
+        grid[Coord(3, 1)] = Cell::Alive;
+        grid[Coord(4, 2)] = Cell::Alive;
+        grid.generation = 42;
+
+        let mut buf = [0xff; 64];
+        let bytes = grid.to_bytes(&mut buf).unwrap();
+        assert_eq!(bytes.len(), Grid::<5, 3>::ENCODED_LEN);
+        assert_eq!(bytes[HEADER_LEN..], [0b0000_0001, 0b0100_0001]);
+
+        let decoded = Grid::<5, 3>::from_bytes(bytes).unwrap();
+        assert_eq!(decoded.cells, grid.cells);
+        assert_eq!(decoded.generation, 42);
+    }
+
+    #[test]
+    fn errors() {
+        let grid = Grid::<5, 3>::new();
+        assert_eq!(
+            grid.to_bytes(&mut [0; 17]),
+            Err(WireError::BufferTooSmall { needed: 18 })
+        );
+
+        let mut buf = [0; 18];
+        grid.to_bytes(&mut buf).unwrap();
+        assert_eq!(
+            Grid::<3, 5>::from_bytes(&buf).err(),
+            Some(WireError::ExtentsMismatch {
+                width: 5,
+                height: 3
+            })
+        );
+        assert_eq!(
+            Grid::<5, 3>::from_bytes(&buf[..17]).err(),
+            Some(WireError::BufferTooSmall { needed: 18 })
+        );
+    }
+
+    #[test]
+    fn diff_round_trip() {
+        let mut grid = Grid::<200, 2>::new();
+        grid[Coord(0, 0)] = Cell::Alive;
+        grid[Coord(199, 1)] = Cell::Alive;
+
+        let mut target = grid.clone();
+        target[Coord(0, 0)] = Cell::Dead;
+        target[Coord(5, 0)] = Cell::Alive;
+        target.generation = 9;
+
+        let mut buf = [0; 32];
+        let bytes = grid.diff_to_bytes(&target, &mut buf).unwrap();
+        // 0 skipped and dead, 4 skipped and alive, then none
+        assert_eq!(bytes[DIFF_HEADER_LEN..], [0b0000, 0b1001]);
+
+        grid.apply_diff_bytes(bytes).unwrap();
+        assert_eq!(grid.cells, target.cells);
+        assert_eq!(grid.generation, 9);
+
+        let mut empty = [0; DIFF_HEADER_LEN];
+        let bytes = grid.diff_to_bytes(&grid.clone(), &mut empty).unwrap();
+        assert_eq!(bytes.len(), DIFF_HEADER_LEN);
+    }
+
+    #[test]
+    fn diff_errors() {
+        let mut grid = Grid::<4, 4>::new();
+        let mut header = [0; DIFF_HEADER_LEN];
+        header[8] = 1;
+
+        assert_eq!(
+            grid.apply_diff_bytes(&header),
+            Err(WireError::BufferTooSmall { needed: 13 })
+        );
+        assert_eq!(
+            grid.apply_diff_bytes(&[&header[..], &[16 << 1]].concat()),
+            Err(WireError::Malformed)
+        );
+        assert_eq!(
+            grid.apply_diff_bytes(&[&header[..], &[0xff; 12]].concat()),
+            Err(WireError::Malformed)
+        );
+        assert!(grid.cells.iter().flatten().all(|&cell| cell == Cell::Dead));
+    }
+}
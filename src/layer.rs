@@ -0,0 +1,330 @@
+//!
+//! Per-cell data maintained alongside a grid while stepping it.
+//!
+//! See:
+//! - [`Layer`] for the trait layers implement.
+//! - [`HeatMap`] for the recent activity of each cell.
+//! - [`Ages`] for how long each cell has been alive.
+//! - [`Changes`] for the cells that changed.
+//!
+
+use crate::{cell::Cell, grid::Grid, math::Coord, rule::Rule};
+
+///
+/// Something updated with every cell of a grid as it is stepped, by
+/// [`Grid::step_with_layer()`].
+///
+/// Layers see each cell's state before and after the step without a second
+/// pass over the grids. Several layers can be updated at once by passing a
+/// tuple of them.
+///
+pub trait Layer<const W: usize, const H: usize> {
+    /// Called once per cell and step, in row-major order.
+    fn update(&mut self, coord: Coord, prev: Cell, next: Cell);
+}
+
+impl<const W: usize, const H: usize, L: Layer<W, H> + ?Sized> Layer<W, H> for &mut L {
+    fn update(&mut self, coord: Coord, prev: Cell, next: Cell) {
+        (**self).update(coord, prev, next);
+    }
+}
+
+impl<const W: usize, const H: usize, A: Layer<W, H>, B: Layer<W, H>> Layer<W, H> for (A, B) {
+    fn update(&mut self, coord: Coord, prev: Cell, next: Cell) {
+        self.0.update(coord, prev, next);
+        self.1.update(coord, prev, next);
+    }
+}
+
+impl<const W: usize, const H: usize, A, B, C> Layer<W, H> for (A, B, C)
+where
+    A: Layer<W, H>,
+    B: Layer<W, H>,
+    C: Layer<W, H>,
+{
+    fn update(&mut self, coord: Coord, prev: Cell, next: Cell) {
+        self.0.update(coord, prev, next);
+        self.1.update(coord, prev, next);
+        self.2.update(coord, prev, next);
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Calculates the next generation of this grid according to `rule`,
+    /// updating `layer` with every cell along the way.
+    ///
+    #[must_use]
+    pub fn step_with_layer(&self, rule: &impl Rule, mut layer: impl Layer<W, H>) -> Self {
+        let mut next = |coord: Coord| {
+            let next = self.state_next_with(rule, coord);
+            layer.update(coord, self[coord], next);
+            next
+        };
+
+        Self {
+            cells: core::array::from_fn(|y| core::array::from_fn(|x| next(Coord(x, y)))),
+            generation: self.generation + 1,
+        }
+    }
+}
+
+///
+/// The recent activity of each cell of a grid.
+///
+/// Every time a cell changes, its heat rises by `gain`, and every time it
+/// does not, its heat falls by `decay`, saturating at 0 and 255.
+///
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HeatMap<const W: usize, const H: usize> {
+    heat: [[u8; W]; H],
+    gain: u8,
+    decay: u8,
+}
+
+impl<const W: usize, const H: usize> HeatMap<W, H> {
+    /// Construct a new cold [`HeatMap`].
+    #[must_use]
+    pub const fn new(gain: u8, decay: u8) -> Self {
+        Self {
+            heat: [[0; W]; H],
+            gain,
+            decay,
+        }
+    }
+
+    /// Returns the heat of a cell.
+    #[must_use]
+    pub fn get(&self, coord: Coord) -> u8 {
+        self.heat[coord.1][coord.0]
+    }
+
+    /// Returns the heat of every cell.
+    #[must_use]
+    pub const fn rows(&self) -> &[[u8; W]; H] {
+        &self.heat
+    }
+
+    /// Cools every cell down to 0.
+    pub fn clear(&mut self) {
+        self.heat = [[0; W]; H];
+    }
+}
+
+impl<const W: usize, const H: usize> Default for HeatMap<W, H> {
+    /// A heat map where a change heats a cell up by 64 and cools down by 4 per generation.
+    fn default() -> Self {
+        Self::new(64, 4)
+    }
+}
+
+impl<const W: usize, const H: usize> Layer<W, H> for HeatMap<W, H> {
+    fn update(&mut self, Coord(x, y): Coord, prev: Cell, next: Cell) {
+        let heat = &mut self.heat[y][x];
+        *heat = if prev == next {
+            heat.saturating_sub(self.decay)
+        } else {
+            heat.saturating_add(self.gain)
+        };
+    }
+}
+
+///
+/// The age of each cell of a grid: the number of consecutive generations it
+/// has been alive for, including the current one.
+///
+/// Dead cells have age 0, newborn cells have age 1, and ages saturate at
+/// [`u16::MAX`].
+///
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Ages<const W: usize, const H: usize> {
+    ages: [[u16; W]; H],
+}
+
+impl<const W: usize, const H: usize> Ages<W, H> {
+    /// Construct new [`Ages`] where every cell is dead.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { ages: [[0; W]; H] }
+    }
+
+    /// Returns the age of a cell.
+    #[must_use]
+    pub fn get(&self, coord: Coord) -> u16 {
+        self.ages[coord.1][coord.0]
+    }
+
+    /// Returns the age of every cell.
+    #[must_use]
+    pub const fn rows(&self) -> &[[u16; W]; H] {
+        &self.ages
+    }
+}
+
+impl<const W: usize, const H: usize> Default for Ages<W, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize, const H: usize> From<&Grid<W, H>> for Ages<W, H> {
+    /// Construct new [`Ages`] where every live cell of `grid` is newborn.
+    fn from(grid: &Grid<W, H>) -> Self {
+        Self {
+            ages: grid.cells.map(|row| row.map(|cell| cell as u16)),
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Layer<W, H> for Ages<W, H> {
+    fn update(&mut self, Coord(x, y): Coord, _: Cell, next: Cell) {
+        let age = &mut self.ages[y][x];
+        *age = match next {
+            Cell::Alive => age.saturating_add(1),
+            Cell::Dead => 0,
+        };
+    }
+}
+
+///
+/// A list of the cells that changed in a step, along with their new state,
+/// in row-major order.
+///
+/// Changes are recorded into a caller-provided buffer. If it fills up,
+/// further changes are only counted, so callers can fall back to a full
+/// refresh.
+///
+#[derive(PartialEq, Eq, Debug)]
+pub struct Changes<'a> {
+    buf: &'a mut [(Coord, Cell)],
+    len: usize,
+    total: usize,
+}
+
+impl<'a> Changes<'a> {
+    /// Construct a new empty [`Changes`] recording into `buf`.
+    #[must_use]
+    pub fn new(buf: &'a mut [(Coord, Cell)]) -> Self {
+        Self {
+            buf,
+            len: 0,
+            total: 0,
+        }
+    }
+
+    /// Returns the recorded changes.
+    #[must_use]
+    pub fn as_slice(&self) -> &[(Coord, Cell)] {
+        &self.buf[..self.len]
+    }
+
+    /// Returns the number of changes, including those that did not fit.
+    #[must_use]
+    pub const fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Returns whether some changes did not fit in the buffer.
+    #[must_use]
+    pub const fn overflowed(&self) -> bool {
+        self.total > self.len
+    }
+
+    /// Forgets all changes, to record another step.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.total = 0;
+    }
+}
+
+impl<const W: usize, const H: usize> Layer<W, H> for Changes<'_> {
+    fn update(&mut self, coord: Coord, prev: Cell, next: Cell) {
+        if prev == next {
+            return;
+        }
+
+        self.total += 1;
+        if let Some(slot) = self.buf.get_mut(self.len) {
+            *slot = (coord, next);
+            self.len += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{rule::Life, sim::StepStats};
+
+    #[test]
+    fn heat_map() {
+        let mut grid = Grid::<7, 7>::new();
+        grid[Coord(2, 1)] = Cell::Alive;
+        grid[Coord(2, 2)] = Cell::Alive;
+        grid[Coord(2, 3)] = Cell::Alive;
+        grid[Coord(6, 6)] = Cell::Alive;
+
+        let mut heat = HeatMap::new(100, 10);
+        let mut stats = StepStats::default();
+        for _ in 0..3 {
+            grid = grid.step_with_layer(&Life, &mut heat);
+        }
+        let _ = grid.step_with_layer(&Life, (&mut heat, &mut stats));
+
+        assert_eq!(heat.get(Coord(1, 2)), 255);
+        assert_eq!(heat.get(Coord(2, 2)), 0);
+        assert_eq!(heat.get(Coord(6, 6)), 100 - 30);
+        assert_eq!(stats.changed, 4);
+    }
+
+    #[test]
+    fn ages() {
+        let mut grid = Grid::<6, 6>::new();
+        for coord in [
+            Coord(1, 1),
+            Coord(2, 1),
+            Coord(1, 2),
+            Coord(2, 2),
+            Coord(4, 4),
+        ] {
+            grid[coord] = Cell::Alive;
+        }
+
+        let mut ages = Ages::from(&grid);
+        assert_eq!(ages.get(Coord(1, 1)), 1);
+        for _ in 0..3 {
+            grid = grid.step_with_layer(&Life, &mut ages);
+        }
+
+        assert_eq!(ages.get(Coord(1, 1)), 4);
+        assert_eq!(ages.get(Coord(3, 3)), 0);
+        assert_eq!(ages.get(Coord(4, 4)), 0);
+        assert!(ages
+            .rows()
+            .iter()
+            .flatten()
+            .zip(grid.cells.iter().flatten())
+            .all(|(&age, &cell)| (age > 0) == (cell == Cell::Alive)));
+    }
+
+    #[test]
+    fn changes() {
+        let mut grid = Grid::<5, 5>::new();
+        grid[Coord(2, 1)] = Cell::Alive;
+        grid[Coord(2, 2)] = Cell::Alive;
+        grid[Coord(2, 3)] = Cell::Alive;
+
+        let mut buf = [(Coord(0, 0), Cell::Dead); 8];
+        let mut changes = Changes::new(&mut buf);
+        let next = grid.step_with_layer(&Life, &mut changes);
+        assert!(changes.as_slice().iter().copied().eq(grid.diff(&next)));
+        assert!(!changes.overflowed());
+
+        let mut buf = [(Coord(0, 0), Cell::Dead); 3];
+        let mut changes = Changes::new(&mut buf);
+        let _ = grid.step_with_layer(&Life, &mut changes);
+        assert_eq!(changes.as_slice().len(), 3);
+        assert_eq!(changes.total(), 4);
+        assert!(changes.overflowed());
+    }
+}
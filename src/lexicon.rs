@@ -0,0 +1,364 @@
+//!
+//! A lexicon of well-known named patterns.
+//!
+//! Use [`lookup()`] to find a pattern by name or alias, or [`entries()`] to
+//! iterate over all of them.
+//!
+
+use core::slice;
+
+use crate::{
+    cell::{Cell, Charset},
+    math::Coord,
+    pattern::Pattern,
+};
+
+///
+/// An entry in the lexicon.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Entry {
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    pub pattern: Pattern<'static>,
+}
+
+impl Entry {
+    ///
+    /// Returns whether `name` refers to this entry.
+    ///
+    /// Matching is ASCII case-insensitive, and ignores all characters that
+    /// are not ASCII alphanumeric, so `"Gosper glider gun"` is matched by
+    /// `"gosperglidergun"` and `"GOSPER-GLIDER-GUN"` alike.
+    ///
+    #[must_use]
+    pub fn matches(&self, name: &str) -> bool {
+        fn normalize(s: &str) -> impl Iterator<Item = u8> + '_ {
+            s.bytes()
+                .filter(u8::is_ascii_alphanumeric)
+                .map(|b| b.to_ascii_lowercase())
+        }
+
+        [self.name]
+            .iter()
+            .chain(self.aliases)
+            .any(|alias| normalize(alias).eq(normalize(name)))
+    }
+}
+
+///
+/// Look up an [`Entry`] by its name or one of its aliases.
+///
+/// See [`Entry::matches()`] for how names are compared.
+///
+#[must_use]
+pub fn lookup(name: &str) -> Option<&'static Entry> {
+    ENTRIES.iter().find(|entry| entry.matches(name))
+}
+
+/// Returns an iterator over all entries in the lexicon.
+pub fn entries() -> slice::Iter<'static, Entry> {
+    ENTRIES.iter()
+}
+
+///
+/// Build a `Pattern<'static>` from rows in plaintext format at compile time,
+/// where `O` is a live cell and any other character is a dead cell.
+///
+macro_rules! plaintext {
+    ($($row:literal),+ $(,)?) => {{
+        const ROWS: &[&str] = &[$($row),+];
+        const CELLS: [Coord; count_alive(ROWS)] = parse_alive(ROWS);
+        Pattern::new(&CELLS)
+    }};
+}
+
+const fn count_alive(rows: &[&str]) -> usize {
+    let mut count = 0;
+
+    let mut y = 0;
+    while y < rows.len() {
+        let row = rows[y].as_bytes();
+        let mut x = 0;
+        while x < row.len() {
+            if matches!(
+                Cell::from_char(row[x] as char, Charset::PLAINTEXT),
+                Some(Cell::Alive)
+            ) {
+                count += 1;
+            }
+            x += 1;
+        }
+        y += 1;
+    }
+
+    count
+}
+
+const fn parse_alive<const N: usize>(rows: &[&str]) -> [Coord; N] {
+    let mut cells = [Coord(0, 0); N];
+    let mut i = 0;
+
+    let mut y = 0;
+    while y < rows.len() {
+        let row = rows[y].as_bytes();
+        let mut x = 0;
+        while x < row.len() {
+            if matches!(
+                Cell::from_char(row[x] as char, Charset::PLAINTEXT),
+                Some(Cell::Alive)
+            ) {
+                cells[i] = Coord(x, y);
+                i += 1;
+            }
+            x += 1;
+        }
+        y += 1;
+    }
+
+    cells
+}
+
+#[rustfmt::skip]
+static ENTRIES: &[Entry] = &[
+    Entry {
+        name: "Block",
+        aliases: &[],
+        pattern: plaintext![
+            "OO",
+            "OO",
+        ],
+    },
+    Entry {
+        name: "Beehive",
+        aliases: &["hive"],
+        pattern: plaintext![
+            ".OO.",
+            "O..O",
+            ".OO.",
+        ],
+    },
+    Entry {
+        name: "Loaf",
+        aliases: &[],
+        pattern: plaintext![
+            ".OO.",
+            "O..O",
+            ".O.O",
+            "..O.",
+        ],
+    },
+    Entry {
+        name: "Boat",
+        aliases: &[],
+        pattern: plaintext![
+            "OO.",
+            "O.O",
+            ".O.",
+        ],
+    },
+    Entry {
+        name: "Tub",
+        aliases: &[],
+        pattern: plaintext![
+            ".O.",
+            "O.O",
+            ".O.",
+        ],
+    },
+    Entry {
+        name: "Pond",
+        aliases: &[],
+        pattern: plaintext![
+            ".OO.",
+            "O..O",
+            "O..O",
+            ".OO.",
+        ],
+    },
+    Entry {
+        name: "Ship",
+        aliases: &[],
+        pattern: plaintext![
+            "OO.",
+            "O.O",
+            ".OO",
+        ],
+    },
+    Entry {
+        name: "Eater 1",
+        aliases: &["eater", "fishhook"],
+        pattern: plaintext![
+            "OO..",
+            "O.O.",
+            "..O.",
+            "..OO",
+        ],
+    },
+    Entry {
+        name: "Blinker",
+        aliases: &[],
+        pattern: plaintext![
+            "OOO",
+        ],
+    },
+    Entry {
+        name: "Toad",
+        aliases: &[],
+        pattern: plaintext![
+            ".OOO",
+            "OOO.",
+        ],
+    },
+    Entry {
+        name: "Beacon",
+        aliases: &[],
+        pattern: plaintext![
+            "OO..",
+            "OO..",
+            "..OO",
+            "..OO",
+        ],
+    },
+    Entry {
+        name: "Pulsar",
+        aliases: &[],
+        pattern: plaintext![
+            "..OOO...OOO..",
+            ".............",
+            "O....O.O....O",
+            "O....O.O....O",
+            "O....O.O....O",
+            "..OOO...OOO..",
+            ".............",
+            "..OOO...OOO..",
+            "O....O.O....O",
+            "O....O.O....O",
+            "O....O.O....O",
+            ".............",
+            "..OOO...OOO..",
+        ],
+    },
+    Entry {
+        name: "Pentadecathlon",
+        aliases: &[],
+        pattern: plaintext![
+            "..O....O..",
+            "OO.OOOO.OO",
+            "..O....O..",
+        ],
+    },
+    Entry {
+        name: "Glider",
+        aliases: &[],
+        pattern: plaintext![
+            ".O.",
+            "..O",
+            "OOO",
+        ],
+    },
+    Entry {
+        name: "Lightweight spaceship",
+        aliases: &["LWSS"],
+        pattern: plaintext![
+            ".O..O",
+            "O....",
+            "O...O",
+            "OOOO.",
+        ],
+    },
+    Entry {
+        name: "Middleweight spaceship",
+        aliases: &["MWSS"],
+        pattern: plaintext![
+            "...O..",
+            ".O...O",
+            "O.....",
+            "O....O",
+            "OOOOO.",
+        ],
+    },
+    Entry {
+        name: "Heavyweight spaceship",
+        aliases: &["HWSS"],
+        pattern: plaintext![
+            "...OO..",
+            ".O....O",
+            "O......",
+            "O.....O",
+            "OOOOOO.",
+        ],
+    },
+    Entry {
+        name: "R-pentomino",
+        aliases: &[],
+        pattern: plaintext![
+            ".OO",
+            "OO.",
+            ".O.",
+        ],
+    },
+    Entry {
+        name: "Diehard",
+        aliases: &[],
+        pattern: plaintext![
+            "......O.",
+            "OO......",
+            ".O...OOO",
+        ],
+    },
+    Entry {
+        name: "Acorn",
+        aliases: &[],
+        pattern: plaintext![
+            ".O.....",
+            "...O...",
+            "OO..OOO",
+        ],
+    },
+    Entry {
+        name: "Gosper glider gun",
+        aliases: &["Gosper gun", "glider gun"],
+        pattern: plaintext![
+            "........................O...........",
+            "......................O.O...........",
+            "............OO......OO............OO",
+            "...........O...O....OO............OO",
+            "OO........O.....O...OO..............",
+            "OO........O...O.OO....O.O...........",
+            "..........O.....O.......O...........",
+            "...........O...O....................",
+            "............OO......................",
+        ],
+    },
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lookup_aliases() {
+        let gun = lookup("gosperglidergun").unwrap();
+        assert_eq!(gun.name, "Gosper glider gun");
+        assert_eq!(gun.pattern.population(), 36);
+        assert_eq!(gun.pattern.extents(), Coord(36, 9));
+
+        assert_eq!(lookup("r-pentomino").unwrap().pattern.population(), 5);
+        assert_eq!(lookup("Glider").unwrap().name, "Glider");
+        assert_eq!(lookup("lwss").unwrap().name, "Lightweight spaceship");
+        assert_eq!(lookup("not a pattern"), None);
+    }
+
+    #[test]
+    fn names_unique() {
+        for (i, a) in entries().enumerate() {
+            for b in entries().skip(i + 1) {
+                assert!(!b.matches(a.name), "{} is ambiguous", a.name);
+                for alias in a.aliases {
+                    assert!(!b.matches(alias), "{alias} is ambiguous");
+                }
+            }
+        }
+    }
+}
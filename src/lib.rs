@@ -6,15 +6,79 @@
 //! - [`Cell`] for the cells.
 //! - [`Grid`] for the cell grid.
 //! - [`Coord`] for the coordinates used in the cell grid.
+//! - [`Pattern`] for finite patterns, and [`lexicon`] for well-known ones.
+//! - [`Simulation`] for running a grid under a [`Rule`](rule::Rule).
+//! - [`layer`] for per-cell data maintained while stepping.
+//! - [`view`] for views into regions of a grid.
+//! - [`distance`] for distance transforms, such as for proximity-based coloring.
+//! - [`column`](mod@column) for column-major storage, for column-oriented displays.
+//! - [`compare`] for running engines or rules side by side to find where
+//!   they diverge.
+//! - `padded` for faster stepping with padded rows, with the `alloc` feature.
+//! - `banded` for multi-threaded stepping, with the `std` feature.
+//! - `frontend` for interactive frontends, with the `crossterm` or `minifb`
+//!   feature.
+//! - `ffi` for C bindings, with the `ffi` feature.
+//! - `runner` for headless batch runs, with the `cli` feature.
+//! - `python` for Python bindings, with the `pyo3` feature.
+//! - `wasm` for JavaScript bindings, with the `wasm` feature.
 //!
 
 #![no_std]
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "ffi"), forbid(unsafe_code))]
+#![cfg_attr(feature = "ffi", deny(unsafe_code))]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+pub mod analysis;
+#[cfg(feature = "std")]
+pub mod banded;
 pub mod cell;
+pub mod column;
+pub mod compare;
+#[cfg(feature = "defmt")]
+mod defmt;
+pub mod distance;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(any(feature = "crossterm", feature = "minifb"))]
+pub mod frontend;
 pub mod grid;
+pub mod io;
+pub mod layer;
+pub mod lexicon;
 pub mod math;
+pub mod metrics;
+#[cfg(feature = "alloc")]
+pub mod padded;
+pub mod pattern;
+pub mod predecessor;
+#[cfg(feature = "pyo3")]
+pub mod python;
+pub mod random;
+pub mod render;
+pub mod rule;
+#[cfg(feature = "cli")]
+pub mod runner;
+#[cfg(feature = "alloc")]
+pub mod search;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod sim;
+#[cfg(feature = "ufmt")]
+mod ufmt;
+pub mod view;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
-pub use cell::Cell;
+pub use cell::{Cell, CellState, Charset, State};
 pub use grid::Grid;
 pub use math::Coord;
+#[cfg(feature = "alloc")]
+pub use pattern::PatternBuf;
+pub use pattern::{Orientation, Pattern};
+pub use sim::Simulation;
@@ -1,5 +1,6 @@
 use core::{
     array,
+    iter::FusedIterator,
     ops::{Add, Sub},
 };
 
@@ -19,18 +20,79 @@ pub struct Neighbors {
     inner: array::IntoIter<Option<Coord>, 8>,
 }
 
+///
+/// The coordinates on a line between two [`Coord`]s.
+///
+/// Obtained by calling [`line()`].
+///
+#[derive(Clone, Debug)]
+pub struct Line {
+    x: isize,
+    y: isize,
+    end: (isize, isize),
+    /// The absolute horizontal and negated absolute vertical distance.
+    delta: (isize, isize),
+    sign: (isize, isize),
+    error: isize,
+    done: bool,
+}
+
+///
+/// The coordinates around a [`Coord`], in rings of growing distance.
+///
+/// Obtained by calling [`Coord::spiral()`].
+///
+#[derive(Clone, Debug)]
+pub struct Spiral {
+    center: Coord,
+    extents: Coord,
+    radius: usize,
+    max_radius: usize,
+    x: usize,
+    y: usize,
+}
+
+///
+/// A rectangle, given by its top-left corner and its extents.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Rect {
+    pub origin: Coord,
+    pub extents: Coord,
+}
+
+impl Rect {
+    /// Construct a new [`Rect`].
+    #[must_use]
+    pub const fn new(origin: Coord, extents: Coord) -> Self {
+        Self { origin, extents }
+    }
+
+    /// Returns whether `coord` lies inside of this rectangle.
+    #[must_use]
+    pub const fn contains(&self, Coord(x, y): Coord) -> bool {
+        let (Coord(left, top), Coord(w, h)) = (self.origin, self.extents);
+        x >= left && x - left < w && y >= top && y - top < h
+    }
+}
+
 impl Coord {
     ///
-    /// Returns the neighbors of a [`Coord`].
+    /// Returns the neighbors of a [`Coord`] within `extents`.
+    ///
+    /// Grids only one cell wide or high are handled like any other, so a
+    /// cell in a single row only has neighbors to its left and right. With
+    /// empty `extents`, there are no neighbors; see [`Coord::try_neighbors()`]
+    /// to tell that apart.
     ///
     #[rustfmt::skip]
     #[must_use]
     pub fn neighbors(&self, extents: Self) -> Neighbors {
         macro_rules! pos {
             (L) => { self.0 == 0 };
-            (R) => { self.0 >= extents.0 - 1 };
+            (R) => { self.0 + 1 >= extents.0 };
             (T) => { self.1 == 0 };
-            (B) => { self.1 >= extents.1 - 1 };
+            (B) => { self.1 + 1 >= extents.1 };
             (TL) => { pos!(T) || pos!(L) };
             (TR) => { pos!(T) || pos!(R) };
             (BL) => { pos!(B) || pos!(L) };
@@ -56,6 +118,71 @@ impl Coord {
             ].into_iter()
         }
     }
+
+    ///
+    /// Returns the neighbors of a [`Coord`] within `extents`, or [`None`] if
+    /// it does not lie inside of `extents`, such as when they are empty.
+    ///
+    #[must_use]
+    pub fn try_neighbors(&self, extents: Self) -> Option<Neighbors> {
+        (self.0 < extents.0 && self.1 < extents.1).then(|| self.neighbors(extents))
+    }
+
+    ///
+    /// Returns the coordinates within `extents` in square rings around this
+    /// coordinate, starting with itself, then the coordinates at Chebyshev
+    /// distance 1, then 2, and so on.
+    ///
+    /// Each ring is clipped to `extents` and visited in row-major order. The
+    /// iterator ends once every coordinate within `extents` is visited, even
+    /// if this coordinate lies outside of it.
+    ///
+    #[must_use]
+    pub fn spiral(&self, extents: Self) -> Spiral {
+        let Self(x, y) = *self;
+        let max_radius = match extents {
+            Self(0, _) | Self(_, 0) => None,
+            Self(w, h) => [
+                x.abs_diff(0),
+                x.abs_diff(w - 1),
+                y.abs_diff(0),
+                y.abs_diff(h - 1),
+            ]
+            .into_iter()
+            .max(),
+        };
+
+        Spiral {
+            center: *self,
+            extents,
+            // an empty extent has no rings at all
+            radius: if max_radius.is_some() { 0 } else { 1 },
+            max_radius: max_radius.unwrap_or_default(),
+            x,
+            y,
+        }
+    }
+}
+
+///
+/// Returns the coordinates on the line from `a` to `b`, both included, as
+/// given by Bresenham's line algorithm.
+///
+/// Consecutive coordinates are always neighbors, so the line has no gaps.
+///
+#[must_use]
+pub fn line(a: Coord, b: Coord) -> Line {
+    let [ax, ay, bx, by] = [a.0, a.1, b.0, b.1].map(|c| c as isize);
+    let delta = ((bx - ax).abs(), -(by - ay).abs());
+    Line {
+        x: ax,
+        y: ay,
+        end: (bx, by),
+        delta,
+        sign: ((bx - ax).signum(), (by - ay).signum()),
+        error: delta.0 + delta.1,
+        done: false,
+    }
 }
 
 #[rustfmt::skip]
@@ -85,6 +212,101 @@ impl Sub<Self> for Coord {
     }
 }
 
+impl Iterator for Line {
+    type Item = Coord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let coord = Coord(self.x as usize, self.y as usize);
+        if (self.x, self.y) == self.end {
+            self.done = true;
+        } else {
+            let error = 2 * self.error;
+            if error >= self.delta.1 {
+                self.error += self.delta.1;
+                self.x += self.sign.0;
+            }
+            if error <= self.delta.0 {
+                self.error += self.delta.0;
+                self.y += self.sign.1;
+            }
+        }
+        Some(coord)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = if self.done {
+            0
+        } else {
+            let (x, y) = self.end;
+            self.x.abs_diff(x).max(self.y.abs_diff(y)) + 1
+        };
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Line {}
+
+impl Spiral {
+    /// Returns the left, top, right and bottom of the current ring, clipped.
+    fn bounds(&self) -> [usize; 4] {
+        let (Coord(x, y), Coord(w, h), r) = (self.center, self.extents, self.radius);
+        [
+            x.saturating_sub(r),
+            y.saturating_sub(r),
+            (x + r).min(w - 1),
+            (y + r).min(h - 1),
+        ]
+    }
+}
+
+impl Iterator for Spiral {
+    type Item = Coord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Coord(cx, cy) = self.center;
+        loop {
+            if self.radius > self.max_radius {
+                return None;
+            }
+
+            let [left, _, right, bottom] = self.bounds();
+            if self.y > bottom {
+                self.radius += 1;
+                let [left, top, ..] = self.bounds();
+                (self.x, self.y) = (left, top);
+                continue;
+            }
+            if self.x > right {
+                (self.x, self.y) = (left, self.y + 1);
+                continue;
+            }
+
+            let coord = Coord(self.x, self.y);
+            let r = self.radius;
+            let on_ring = coord.0.abs_diff(cx) == r || coord.1.abs_diff(cy) == r;
+            // skip over the inside of the ring, which was already visited
+            self.x = if coord.1.abs_diff(cy) == r {
+                self.x + 1
+            } else if self.x < cx + r {
+                cx + r
+            } else {
+                right + 1
+            };
+            if on_ring {
+                return Some(coord);
+            }
+        }
+    }
+}
+
+impl FusedIterator for Spiral {}
+
+impl FusedIterator for Line {}
+
 impl Iterator for Neighbors {
     type Item = Coord;
 
@@ -122,6 +344,85 @@ mod test {
         assert_iter_next_eq!(br, Coord(1, 1), Coord(2, 1), Coord(1, 2));
     }
 
+    #[test]
+    fn neighbors_degenerate() {
+        assert!(Coord(0, 2)
+            .neighbors(Coord(1, 4))
+            .eq([Coord(0, 1), Coord(0, 3)]));
+        assert!(Coord(2, 0)
+            .neighbors(Coord(4, 1))
+            .eq([Coord(3, 0), Coord(1, 0)]));
+        assert!(Coord(3, 0).neighbors(Coord(4, 1)).eq([Coord(2, 0)]));
+        assert_eq!(Coord(0, 0).neighbors(Coord(1, 1)).count(), 0);
+
+        assert_eq!(Coord(0, 0).neighbors(Coord(0, 0)).count(), 0);
+        assert!(Coord(0, 0).try_neighbors(Coord(0, 5)).is_none());
+        assert!(Coord(1, 0).try_neighbors(Coord(1, 5)).is_none());
+        assert_eq!(Coord(0, 4).try_neighbors(Coord(1, 5)).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn line() {
+        let mut iter = super::line(Coord(0, 1), Coord(5, 3));
+        assert_eq!(iter.len(), 6);
+        assert_iter_next_eq!(
+            iter,
+            Coord(0, 1),
+            Coord(1, 1),
+            Coord(2, 2),
+            Coord(3, 2),
+            Coord(4, 3),
+            Coord(5, 3),
+        );
+        assert_eq!(iter.next(), None);
+
+        let mut iter = super::line(Coord(2, 4), Coord(2, 1));
+        assert_iter_next_eq!(iter, Coord(2, 4), Coord(2, 3), Coord(2, 2), Coord(2, 1));
+        assert!(super::line(Coord(3, 3), Coord(3, 3)).eq([Coord(3, 3)]));
+
+        let forward = super::line(Coord(1, 7), Coord(6, 0));
+        let mut previous = Coord(1, 7);
+        for coord in forward {
+            assert!(previous.0.abs_diff(coord.0) <= 1 && previous.1.abs_diff(coord.1) <= 1);
+            previous = coord;
+        }
+        assert_eq!(previous, Coord(6, 0));
+    }
+
+    #[test]
+    fn spiral() {
+        let mut iter = Coord(1, 1).spiral(Coord(4, 3));
+        assert_iter_next_eq!(
+            iter,
+            Coord(1, 1),
+            Coord(0, 0),
+            Coord(1, 0),
+            Coord(2, 0),
+            Coord(0, 1),
+            Coord(2, 1),
+            Coord(0, 2),
+            Coord(1, 2),
+            Coord(2, 2),
+            Coord(3, 0),
+            Coord(3, 1),
+            Coord(3, 2),
+        );
+        assert_eq!(iter.next(), None);
+
+        for center in [Coord(0, 0), Coord(6, 2), Coord(3, 4), Coord(9, 8)] {
+            let mut seen = [[false; 7]; 5];
+            let mut last = 0;
+            for coord in center.spiral(Coord(7, 5)) {
+                let distance = coord.0.abs_diff(center.0).max(coord.1.abs_diff(center.1));
+                assert!(distance >= last);
+                last = distance;
+                assert!(!core::mem::replace(&mut seen[coord.1][coord.0], true));
+            }
+            assert!(seen.iter().flatten().all(|&seen| seen));
+        }
+        assert_eq!(Coord(0, 0).spiral(Coord(0, 3)).next(), None);
+    }
+
     #[test]
     fn neighbors_iter_middle() {
         let mut iter = Coord(1, 1).neighbors(Coord(3, 3));
@@ -0,0 +1,153 @@
+//!
+//! Measures of how dense, disordered and active a grid is.
+//!
+//! See [`Metrics`], [`Grid::density()`] and [`Grid::block_entropy()`].
+//!
+
+use crate::{cell::Cell, grid::Grid, math::Coord, sim::StepStats};
+
+///
+/// Measures of a grid after a step, as passed to an
+/// [`Observer`](crate::sim::Observer).
+///
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Metrics {
+    /// The fraction of cells that are alive.
+    pub density: f64,
+    /// The entropy of the grid's 2 by 2 blocks, in bits.
+    pub entropy: f64,
+    /// The fraction of cells that changed in the step.
+    pub change_rate: f64,
+}
+
+impl Metrics {
+    /// Calculate the metrics of `grid` after a step with the given statistics.
+    #[must_use]
+    pub fn new<const W: usize, const H: usize>(grid: &Grid<W, H>, stats: StepStats) -> Self {
+        Self {
+            density: grid.density(),
+            entropy: grid.block_entropy(2),
+            change_rate: ratio(stats.changed, W * H),
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    /// Returns the fraction of cells that are alive.
+    #[must_use]
+    pub fn density(&self) -> f64 {
+        ratio(self.population(), W * H)
+    }
+
+    ///
+    /// Returns the [Shannon entropy](https://en.wikipedia.org/wiki/Entropy_(information_theory)),
+    /// in bits, of the `k` by `k` blocks of this grid.
+    ///
+    /// Every block lying entirely inside the grid is counted, so blocks
+    /// overlap. The result ranges from 0 for a uniform grid up to `k * k`
+    /// bits for a perfectly random one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is not 1, 2 or 3.
+    ///
+    #[must_use]
+    pub fn block_entropy(&self, k: usize) -> f64 {
+        assert!((1..=3).contains(&k), "block size must be 1, 2 or 3");
+        if W < k || H < k {
+            return 0.0;
+        }
+
+        let mut counts = [0usize; 1 << 9];
+        for y in 0..=H - k {
+            for x in 0..=W - k {
+                let block = (0..k * k)
+                    .map(|i| self[Coord(x + i % k, y + i / k)])
+                    .fold(0, |block, cell| block << 1 | (cell == Cell::Alive) as usize);
+                counts[block] += 1;
+            }
+        }
+
+        let blocks = (W - k + 1) * (H - k + 1);
+        -counts
+            .iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| ratio(count, blocks))
+            .map(|p| p * log2(p))
+            .sum::<f64>()
+    }
+}
+
+/// Returns `n / total`, or 0 if `total` is 0.
+fn ratio(n: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        n as f64 / total as f64
+    }
+}
+
+///
+/// Returns the base 2 logarithm of a positive, finite number.
+///
+/// `f64::log2()` is not available without `std`.
+///
+fn log2(x: f64) -> f64 {
+    // split into x = m * 2^e, where 1 <= m < 2.
+    let bits = x.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i64 - 1023;
+    let mantissa = f64::from_bits((bits & ((1 << 52) - 1)) | (1023 << 52));
+
+    // ln(m) = 2 atanh(z), where z = (m - 1) / (m + 1) <= 1/3.
+    let z = (mantissa - 1.0) / (mantissa + 1.0);
+    let (mut term, mut atanh) = (z, 0.0);
+    for n in (1..40).step_by(2) {
+        atanh += term / n as f64;
+        term *= z * z;
+    }
+
+    exponent as f64 + 2.0 * atanh / core::f64::consts::LN_2
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn log2() {
+        for (x, expected) in [
+            (1.0, 0.0),
+            (2.0, 1.0),
+            (0.25, -2.0),
+            (3.0, 1.584_962_500_721_156),
+        ] {
+            assert!(close(super::log2(x), expected), "{x}");
+        }
+    }
+
+    #[test]
+    fn metrics() {
+        let empty = Grid::<8, 8>::new();
+        assert_eq!(empty.density(), 0.0);
+        assert_eq!(empty.block_entropy(2), 0.0);
+
+        let mut checkerboard = Grid::<8, 8>::new();
+        for (i, cell) in checkerboard.cells.iter_mut().flatten().enumerate() {
+            if (i + i / 8) % 2 == 0 {
+                *cell = Cell::Alive;
+            }
+        }
+        assert!(close(checkerboard.density(), 0.5));
+        assert!(close(checkerboard.block_entropy(1), 1.0));
+        assert!(close(checkerboard.block_entropy(3), 1.0));
+
+        let (next, stats) = checkerboard.step_with_stats(&crate::rule::Life);
+        let metrics = Metrics::new(&next, stats);
+        assert!(close(metrics.density, next.density()));
+        assert!(close(metrics.change_rate, stats.changed as f64 / 64.0));
+    }
+}
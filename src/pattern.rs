@@ -0,0 +1,447 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{cell::Cell, grid::Grid, math::Coord};
+
+///
+/// A finite pattern of live cells.
+///
+/// Cell coordinates are relative to the pattern's origin, and are in the
+/// range `(0..width, 0..height)`. For the extents to be those of the
+/// pattern's bounding box, its cells should touch both axes.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Pattern<'a> {
+    cells: &'a [Coord],
+    width: usize,
+    height: usize,
+}
+
+impl<'a> Pattern<'a> {
+    ///
+    /// Construct a new [`Pattern`] from its live cells.
+    ///
+    /// The pattern's extents reach from the origin to the furthest of
+    /// `cells`, so they only match its bounding box if `cells` touch both
+    /// axes. `cells` are used as given, not shifted.
+    ///
+    #[must_use]
+    pub const fn new(cells: &'a [Coord]) -> Self {
+        let (mut width, mut height) = (0, 0);
+
+        let mut i = 0;
+        while i < cells.len() {
+            let Coord(x, y) = cells[i];
+            if x >= width {
+                width = x + 1;
+            }
+            if y >= height {
+                height = y + 1;
+            }
+            i += 1;
+        }
+
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the live cells of this pattern.
+    #[must_use]
+    pub const fn cells(&self) -> &'a [Coord] {
+        self.cells
+    }
+
+    /// Returns the width of this pattern.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of this pattern.
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the extents of this pattern.
+    #[must_use]
+    pub const fn extents(&self) -> Coord {
+        Coord(self.width, self.height)
+    }
+
+    /// Returns the number of live cells in this pattern.
+    #[must_use]
+    pub const fn population(&self) -> usize {
+        self.cells.len()
+    }
+
+    ///
+    /// Returns the live cells of this pattern in the given [`Orientation`].
+    ///
+    /// The extents of the result are given by [`Orientation::extents()`].
+    ///
+    pub fn oriented_cells(&self, orientation: Orientation) -> impl Iterator<Item = Coord> + 'a {
+        let extents = self.extents();
+        self.cells
+            .iter()
+            .map(move |&coord| orientation.transform(coord, extents))
+    }
+}
+
+///
+/// An owned [`Pattern`].
+///
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct PatternBuf {
+    cells: Vec<Coord>,
+}
+
+#[cfg(feature = "alloc")]
+impl PatternBuf {
+    /// Construct a new [`PatternBuf`] from its live cells.
+    #[must_use]
+    pub fn new(cells: Vec<Coord>) -> Self {
+        Self { cells }
+    }
+
+    /// Borrow this pattern as a [`Pattern`].
+    #[must_use]
+    pub fn as_pattern(&self) -> Pattern<'_> {
+        Pattern::new(&self.cells)
+    }
+
+    /// Returns the number of bytes this pattern takes up, including its cells.
+    #[must_use]
+    pub fn memory_footprint(&self) -> usize {
+        size_of::<Self>() + self.cells.capacity() * size_of::<Coord>()
+    }
+
+    /// Consume this pattern, returning its live cells.
+    #[must_use]
+    pub fn into_cells(self) -> Vec<Coord> {
+        self.cells
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<Pattern<'_>> for PatternBuf {
+    fn from(pattern: Pattern<'_>) -> Self {
+        Self::new(pattern.cells().into())
+    }
+}
+
+///
+/// One of the 8 rotations and reflections of a [`Pattern`].
+///
+/// Rotations are clockwise, with the Y axis pointing down.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Orientation {
+    #[default]
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+    Transpose,
+    AntiTranspose,
+}
+
+impl Orientation {
+    /// All orientations, starting with [`Orientation::Identity`].
+    pub const ALL: [Self; 8] = [
+        Self::Identity,
+        Self::Rotate90,
+        Self::Rotate180,
+        Self::Rotate270,
+        Self::FlipHorizontal,
+        Self::FlipVertical,
+        Self::Transpose,
+        Self::AntiTranspose,
+    ];
+
+    ///
+    /// Returns whether this orientation swaps the X and Y axes.
+    ///
+    #[must_use]
+    pub const fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Self::Rotate90 | Self::Rotate270 | Self::Transpose | Self::AntiTranspose
+        )
+    }
+
+    /// Returns the orientation that undoes this one.
+    #[must_use]
+    pub const fn inverse(self) -> Self {
+        match self {
+            Self::Rotate90 => Self::Rotate270,
+            Self::Rotate270 => Self::Rotate90,
+            other => other,
+        }
+    }
+
+    ///
+    /// Returns the extents of a bounding box of `extents` in this orientation.
+    ///
+    #[must_use]
+    pub const fn extents(self, extents: Coord) -> Coord {
+        if self.swaps_axes() {
+            Coord(extents.1, extents.0)
+        } else {
+            extents
+        }
+    }
+
+    ///
+    /// Transform a coordinate in a bounding box of `extents` into this
+    /// orientation.
+    ///
+    #[rustfmt::skip]
+    #[must_use]
+    pub const fn transform(self, coord: Coord, extents: Coord) -> Coord {
+        let (Coord(x, y), Coord(w, h)) = (coord, extents);
+        match self {
+            Self::Identity => Coord(x, y),
+            Self::Rotate90 => Coord(h - 1 - y, x),
+            Self::Rotate180 => Coord(w - 1 - x, h - 1 - y),
+            Self::Rotate270 => Coord(y, w - 1 - x),
+            Self::FlipHorizontal => Coord(w - 1 - x, y),
+            Self::FlipVertical => Coord(x, h - 1 - y),
+            Self::Transpose => Coord(y, x),
+            Self::AntiTranspose => Coord(h - 1 - y, w - 1 - x),
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Paste a [`Pattern`] onto this grid, with its top-left corner at `at`.
+    ///
+    /// Live cells of the pattern are set to [`Cell::Alive`]; other cells are
+    /// left untouched. Cells falling outside of the grid are ignored.
+    ///
+    pub fn paste(&mut self, pattern: &Pattern, at: Coord) {
+        for &Coord(x, y) in pattern.cells() {
+            let (Some(x), Some(y)) = (x.checked_add(at.0), y.checked_add(at.1)) else {
+                continue;
+            };
+            if let Some(cell) = self.get_mut(Coord(x, y)) {
+                *cell = Cell::Alive;
+            }
+        }
+    }
+
+    ///
+    /// Find all placements of a [`Pattern`] on this grid, returning the
+    /// top-left corner of each.
+    ///
+    /// A pattern is found at a placement if the cells inside of its bounding
+    /// box match it exactly. An empty pattern is never found.
+    ///
+    pub fn find<'a>(&'a self, pattern: &Pattern<'a>) -> impl Iterator<Item = Coord> + 'a {
+        let pattern = *pattern;
+        Self::coords().filter(move |&at| self.matches_at(&pattern, Orientation::Identity, at))
+    }
+
+    ///
+    /// Find all placements of a [`Pattern`] on this grid in any
+    /// [`Orientation`], returning the top-left corner of each along with the
+    /// orientation found there.
+    ///
+    /// If a symmetric pattern matches in several orientations at the same
+    /// placement, only the first in [`Orientation::ALL`] is returned.
+    ///
+    pub fn find_any_orientation<'a>(
+        &'a self,
+        pattern: &Pattern<'a>,
+    ) -> impl Iterator<Item = (Coord, Orientation)> + 'a {
+        let pattern = *pattern;
+        Self::coords().filter_map(move |at| {
+            Orientation::ALL
+                .into_iter()
+                .find(|&orientation| self.matches_at(&pattern, orientation, at))
+                .map(|orientation| (at, orientation))
+        })
+    }
+
+    ///
+    /// Returns each maximal 8-connected group of live cells on this grid, as
+    /// a [`PatternBuf`] along with the top-left corner of its bounding box.
+    ///
+    /// Groups are ordered by their first cell in row-major order, and the
+    /// cells of each group are in row-major order.
+    ///
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn components(&self) -> Vec<(PatternBuf, Coord)> {
+        let mut seen = alloc::vec![false; W * H];
+        let mut components = Vec::new();
+
+        for start in Self::coords() {
+            if seen[start.1 * W + start.0] || self[start] == Cell::Dead {
+                continue;
+            }
+
+            let mut cells = Vec::from([start]);
+            let mut stack = Vec::from([start]);
+            seen[start.1 * W + start.0] = true;
+            while let Some(coord) = stack.pop() {
+                for neighbor in coord.neighbors(Coord(W, H)) {
+                    let seen = &mut seen[neighbor.1 * W + neighbor.0];
+                    if !*seen && self[neighbor] == Cell::Alive {
+                        *seen = true;
+                        cells.push(neighbor);
+                        stack.push(neighbor);
+                    }
+                }
+            }
+
+            let origin = cells.iter().fold(start, |origin, coord| {
+                Coord(origin.0.min(coord.0), origin.1.min(coord.1))
+            });
+            cells.sort_unstable_by_key(|coord| (coord.1, coord.0));
+            cells.iter_mut().for_each(|coord| *coord = *coord - origin);
+            components.push((PatternBuf::new(cells), origin));
+        }
+
+        components
+    }
+
+    fn coords() -> impl Iterator<Item = Coord> {
+        (0..H).flat_map(|y| (0..W).map(move |x| Coord(x, y)))
+    }
+
+    fn matches_at(&self, pattern: &Pattern, orientation: Orientation, at: Coord) -> bool {
+        let Coord(w, h) = orientation.extents(pattern.extents());
+        if pattern.population() == 0 || at.0 + w > W || at.1 + h > H {
+            return false;
+        }
+
+        let alive = self.cells[at.1..at.1 + h]
+            .iter()
+            .flat_map(|row| &row[at.0..at.0 + w])
+            .filter(|&&cell| cell == Cell::Alive)
+            .count();
+
+        alive == pattern.population()
+            && pattern
+                .oriented_cells(orientation)
+                .all(|coord| self[coord + at] == Cell::Alive)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn extents() {
+        let pattern = Pattern::new(&[Coord(1, 0), Coord(2, 1), Coord(0, 2)]);
+        assert_eq!(pattern.extents(), Coord(3, 3));
+        assert_eq!(pattern.population(), 3);
+
+        assert_eq!(Pattern::new(&[]).extents(), Coord(0, 0));
+        // extents are measured from the origin, not the nearest cell
+        assert_eq!(Pattern::new(&[Coord(2, 3)]).extents(), Coord(3, 4));
+    }
+
+    #[test]
+    fn orientations() {
+        let extents = Coord(3, 2);
+        for orientation in Orientation::ALL {
+            let Coord(w, h) = orientation.extents(extents);
+            assert_eq!(w * h, 6);
+            let corners = [Coord(0, 0), Coord(2, 0), Coord(0, 1), Coord(2, 1)]
+                .map(|corner| orientation.transform(corner, extents));
+            for corner in [
+                Coord(0, 0),
+                Coord(w - 1, 0),
+                Coord(0, h - 1),
+                Coord(w - 1, h - 1),
+            ] {
+                assert!(corners.contains(&corner), "{orientation:?}");
+            }
+        }
+
+        assert_eq!(
+            Orientation::Rotate90.transform(Coord(0, 0), extents),
+            Coord(1, 0)
+        );
+    }
+
+    #[test]
+    fn find() {
+        let glider = Pattern::new(&[
+            Coord(1, 0),
+            Coord(2, 1),
+            Coord(0, 2),
+            Coord(1, 2),
+            Coord(2, 2),
+        ]);
+
+        let mut grid = Grid::<8, 8>::new();
+        grid.paste(&glider, Coord(1, 1));
+        assert!(grid.find(&glider).eq([Coord(1, 1)]));
+
+        let mut grid = grid.step().step();
+        assert_eq!(grid.find(&glider).next(), None);
+        assert!(grid
+            .find_any_orientation(&glider)
+            .eq([(Coord(1, 2), Orientation::Transpose)]));
+
+        grid[Coord(1, 2)] = Cell::Alive;
+        assert_eq!(grid.find_any_orientation(&glider).next(), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn components() {
+        let mut grid = Grid::<8, 8>::new();
+        for coord in [
+            Coord(6, 0),
+            Coord(7, 1),
+            Coord(1, 2),
+            Coord(2, 2),
+            Coord(2, 3),
+        ] {
+            grid[coord] = Cell::Alive;
+        }
+
+        let components = grid.components();
+        assert_eq!(components.len(), 2);
+
+        let (pattern, origin) = &components[0];
+        assert_eq!(*origin, Coord(6, 0));
+        assert_eq!(pattern.as_pattern().cells(), [Coord(0, 0), Coord(1, 1)]);
+
+        let (pattern, origin) = &components[1];
+        assert_eq!(*origin, Coord(1, 2));
+        assert_eq!(pattern.as_pattern().extents(), Coord(2, 2));
+    }
+
+    #[test]
+    fn paste_clipped() {
+        let mut grid = Grid::<3, 3>::new();
+        grid.paste(&Pattern::new(&[Coord(0, 0), Coord(1, 1)]), Coord(2, 2));
+
+        assert_eq!(grid[Coord(2, 2)], Cell::Alive);
+        assert_eq!(
+            grid.cells
+                .iter()
+                .flatten()
+                .filter(|&&c| c == Cell::Alive)
+                .count(),
+            1
+        );
+
+        grid.paste(&Pattern::new(&[Coord(1, 0)]), Coord(usize::MAX, 0));
+        grid.paste(&Pattern::new(&[Coord(0, 1)]), Coord(0, usize::MAX));
+        assert_eq!(grid.population(), 1);
+    }
+}
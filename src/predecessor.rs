@@ -0,0 +1,120 @@
+//!
+//! Searching for the parents of a grid.
+//!
+//! See [`Grid::predecessor()`].
+//!
+
+use crate::{cell::Cell, grid::Grid, math::Coord, rule::Rule};
+
+///
+/// The outcome of [`Grid::predecessor()`].
+///
+#[derive(Clone, Debug)]
+pub enum Predecessor<const W: usize, const H: usize> {
+    /// A grid which steps into the target.
+    Found(Grid<W, H>),
+    /// No grid of the same extents steps into the target, so it is a
+    /// Garden of Eden within these bounds.
+    None,
+    /// The search gave up after trying `max_nodes` cell assignments.
+    GaveUp,
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Search for a grid of the same extents which steps into this grid
+    /// under `rule`, with cells outside of the grid dead.
+    ///
+    /// The search assigns cells one by one in row-major order, and backtracks
+    /// as soon as a cell of this grid can no longer be produced. It is
+    /// exponential in the width of the grid, so is only practical for small
+    /// windows, and gives up after `max_nodes` assignments.
+    ///
+    /// The generation of the predecessor is one less than that of this
+    /// grid, saturating at 0.
+    ///
+    #[must_use]
+    pub fn predecessor(&self, rule: &impl Rule, max_nodes: u64) -> Predecessor<W, H> {
+        let mut parent = Grid {
+            cells: [[Cell::Dead; W]; H],
+            generation: self.generation.saturating_sub(1),
+        };
+        if W * H == 0 {
+            return Predecessor::Found(parent);
+        }
+
+        let coord = |i: usize| Coord(i % W, i / W);
+        let mut i = 0;
+        for _ in 0..max_nodes {
+            if self.produced_by(&parent, rule, coord(i)) {
+                if i + 1 == W * H {
+                    return Predecessor::Found(parent);
+                }
+                i += 1;
+                continue;
+            }
+
+            // try the next state of this cell, backtracking over cells
+            // which have no states left to try.
+            while parent[coord(i)] == Cell::Alive {
+                parent[coord(i)] = Cell::Dead;
+                let Some(prev) = i.checked_sub(1) else {
+                    return Predecessor::None;
+                };
+                i = prev;
+            }
+            parent[coord(i)] = Cell::Alive;
+        }
+
+        Predecessor::GaveUp
+    }
+
+    ///
+    /// Returns whether every cell of this grid whose neighborhood is fully
+    /// assigned once `last` is assigned in `parent` is produced by it.
+    ///
+    fn produced_by(&self, parent: &Self, rule: &impl Rule, last: Coord) -> bool {
+        let Coord(x, y) = last;
+        let xs = x.saturating_sub(1)..if x + 1 == W { W } else { x };
+        let ys = y.saturating_sub(1)..if y + 1 == H { H } else { y };
+
+        ys.flat_map(|y| xs.clone().map(move |x| Coord(x, y)))
+            .all(|coord| parent.state_next_with(rule, coord) == self[coord])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rule::Life;
+
+    #[test]
+    fn predecessor() {
+        let mut grid = Grid::<5, 5>::new();
+        grid[Coord(2, 1)] = Cell::Alive;
+        grid[Coord(2, 2)] = Cell::Alive;
+        grid[Coord(2, 3)] = Cell::Alive;
+        grid.generation = 7;
+
+        let Predecessor::Found(parent) = grid.predecessor(&Life, 1_000_000) else {
+            panic!("a blinker has a predecessor");
+        };
+        assert_eq!(parent.step().cells, grid.cells);
+        assert_eq!(parent.generation, 6);
+
+        assert!(matches!(grid.predecessor(&Life, 10), Predecessor::GaveUp));
+    }
+
+    #[test]
+    fn garden_of_eden() {
+        // a lone live cell in a corner with dead cells outside of the grid
+        // needs 3 live neighbors, which would make its other neighbors alive.
+        let mut grid = Grid::<3, 3>::new();
+        grid[Coord(0, 0)] = Cell::Alive;
+
+        assert!(matches!(
+            grid.predecessor(&Life, 1_000_000),
+            Predecessor::None
+        ));
+    }
+}
@@ -0,0 +1,245 @@
+//!
+//! Python bindings with [`pyo3`], so that experiments can be scripted in
+//! Python on top of the Rust core.
+//!
+//! Python classes cannot be generic, so grids of a given size are exported
+//! with [`python_grid!`](crate::python_grid), from an extension module
+//! depending on the same version of `pyo3`:
+//!
+//! ```ignore
+//! lifeless::python_grid! {
+//!     /// A 64 by 48 grid.
+//!     pub struct Board(64, 48);
+//! }
+//!
+//! #[pyo3::pymodule]
+//! fn life(module: &pyo3::Bound<'_, pyo3::types::PyModule>) -> pyo3::PyResult<()> {
+//!     use pyo3::types::PyModuleMethods;
+//!     module.add_class::<Board>()
+//! }
+//! ```
+//!
+//! which can then be used from Python:
+//!
+//! ```python
+//! board = life.Board()
+//! board.load("glider", 1, 1)
+//! board.step(4)
+//! cells = board.to_numpy()  # a (48, 64) array of 0s and 1s
+//! ```
+//!
+
+pub use numpy;
+pub use pyo3;
+
+///
+/// Export a grid of a fixed size to Python as a `pyo3` class.
+///
+/// The class has methods for construction, reading and writing cells,
+/// stepping, loading patterns from the [`lexicon`](crate::lexicon), and
+/// converting to and from 2D `numpy` arrays of `uint8`, indexed by row then
+/// column.
+///
+/// See the [module documentation](crate::python) for an example.
+///
+#[macro_export]
+macro_rules! python_grid {
+    ($(#[$attr:meta])* $vis:vis struct $name:ident($w:expr, $h:expr);) => {
+        $(#[$attr])*
+        #[::pyo3::pyclass]
+        $vis struct $name {
+            grid: $crate::Grid<{ $w }, { $h }>,
+        }
+
+        #[::pyo3::pymethods]
+        impl $name {
+            /// Construct a new empty grid.
+            #[new]
+            #[must_use]
+            pub fn new() -> Self {
+                Self {
+                    grid: $crate::Grid::new(),
+                }
+            }
+
+            /// The width of the grid, in cells.
+            #[getter]
+            #[must_use]
+            pub fn width(&self) -> usize {
+                $w
+            }
+
+            /// The height of the grid, in cells.
+            #[getter]
+            #[must_use]
+            pub fn height(&self) -> usize {
+                $h
+            }
+
+            /// The generation of the grid.
+            #[getter]
+            #[must_use]
+            pub fn generation(&self) -> u64 {
+                self.grid.generation
+            }
+
+            /// The number of live cells.
+            #[getter]
+            #[must_use]
+            pub fn population(&self) -> usize {
+                self.grid.population()
+            }
+
+            /// Returns whether a cell is alive, or `False` outside the grid.
+            #[must_use]
+            pub fn get(&self, x: usize, y: usize) -> bool {
+                self.grid
+                    .get($crate::Coord(x, y))
+                    .is_some_and(|&cell| cell.into())
+            }
+
+            /// Sets whether a cell is alive, doing nothing outside the grid.
+            pub fn set(&mut self, x: usize, y: usize, alive: bool) {
+                if let Some(cell) = self.grid.get_mut($crate::Coord(x, y)) {
+                    *cell = alive.into();
+                }
+            }
+
+            /// Steps the grid by `generations` generations.
+            #[pyo3(signature = (generations = 1))]
+            pub fn step(&mut self, generations: u64) {
+                for _ in 0..generations {
+                    self.grid = self.grid.step();
+                }
+            }
+
+            ///
+            /// Pastes the lexicon pattern named `name` with its top left
+            /// corner at `(x, y)`.
+            ///
+            /// # Errors
+            ///
+            /// Raises `KeyError` if there is no such pattern.
+            ///
+            pub fn load(&mut self, name: &str, x: usize, y: usize) -> ::pyo3::PyResult<()> {
+                $crate::python::__private::load(&mut self.grid, name, x, y)
+            }
+
+            /// Returns the grid as a 2D array of 0s and 1s.
+            #[must_use]
+            pub fn to_numpy<'py>(
+                &self,
+                py: ::pyo3::Python<'py>,
+            ) -> ::pyo3::Bound<'py, $crate::python::numpy::PyArray2<u8>> {
+                $crate::python::__private::to_numpy(py, &self.grid)
+            }
+
+            ///
+            /// Constructs a grid from a 2D array, where non-zero entries
+            /// are live cells.
+            ///
+            /// # Errors
+            ///
+            /// Raises `ValueError` if the array's shape is not the grid's.
+            ///
+            #[staticmethod]
+            pub fn from_numpy(
+                array: $crate::python::numpy::PyReadonlyArray2<'_, u8>,
+            ) -> ::pyo3::PyResult<Self> {
+                Ok(Self {
+                    grid: $crate::python::__private::from_numpy(&array)?,
+                })
+            }
+
+            fn __str__(&self) -> $crate::python::__private::String {
+                $crate::python::__private::display(&self.grid)
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use std::string::String;
+
+    use std::string::ToString;
+
+    use numpy::{ndarray::Array2, IntoPyArray, PyArray2, PyReadonlyArray2};
+    use pyo3::{
+        exceptions::{PyKeyError, PyValueError},
+        Bound, PyResult, Python,
+    };
+
+    use crate::{cell::Cell, grid::Grid, lexicon, math::Coord};
+
+    pub fn display<const W: usize, const H: usize>(grid: &Grid<W, H>) -> String {
+        grid.to_string()
+    }
+
+    pub fn load<const W: usize, const H: usize>(
+        grid: &mut Grid<W, H>,
+        name: &str,
+        x: usize,
+        y: usize,
+    ) -> PyResult<()> {
+        let entry = lexicon::lookup(name)
+            .ok_or_else(|| PyKeyError::new_err(std::format!("no pattern named {name:?}")))?;
+        grid.paste(&entry.pattern, Coord(x, y));
+        Ok(())
+    }
+
+    pub fn to_numpy<'py, const W: usize, const H: usize>(
+        py: Python<'py>,
+        grid: &Grid<W, H>,
+    ) -> Bound<'py, PyArray2<u8>> {
+        Array2::from_shape_fn((H, W), |(y, x)| grid[Coord(x, y)] as u8).into_pyarray(py)
+    }
+
+    pub fn from_numpy<const W: usize, const H: usize>(
+        array: &PyReadonlyArray2<'_, u8>,
+    ) -> PyResult<Grid<W, H>> {
+        let array = array.as_array();
+        if array.dim() != (H, W) {
+            return Err(PyValueError::new_err(std::format!(
+                "expected an array of shape ({H}, {W}), got {:?}",
+                array.dim()
+            )));
+        }
+
+        let mut grid = Grid::new();
+        for ((y, x), &value) in array.indexed_iter() {
+            if value != 0 {
+                grid[Coord(x, y)] = Cell::Alive;
+            }
+        }
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    crate::python_grid! {
+        struct Board(5, 5);
+    }
+
+    #[test]
+    fn board() {
+        let mut board = Board::new();
+        assert_eq!((board.width(), board.height()), (5, 5));
+
+        board.load("blinker", 1, 2).unwrap();
+        board.set(9, 9, true);
+        assert_eq!(board.population(), 3);
+
+        board.step(1);
+        assert!(board.get(2, 1) && board.get(2, 3) && !board.get(1, 2));
+        assert_eq!(board.generation(), 1);
+        assert_eq!(board.__str__().lines().nth(1), Some("..#.."));
+    }
+}
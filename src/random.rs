@@ -0,0 +1,296 @@
+//!
+//! Random number generation, for soups and perturbations.
+//!
+//! See:
+//! - [`Rng`] for sources of random numbers.
+//! - [`SplitMix64`] for a small built-in generator.
+//! - [`Grid::sample_live()`] for picking random cells.
+//! - [`Grid::randomize()`] for filling grids with random soups.
+//! - [`Coord::random()`] for picking random coordinates.
+//!
+//! With the `rand` feature, `RandRng` adapts any `rand` generator into an
+//! [`Rng`], and [`SplitMix64`] can be used wherever `rand` expects one.
+//!
+
+use crate::{
+    cell::{Cell, CellState},
+    grid::Grid,
+    math::{Coord, Rect},
+};
+
+///
+/// A source of uniformly distributed random numbers.
+///
+pub trait Rng {
+    /// Returns a random `u64`.
+    fn next_u64(&mut self) -> u64;
+
+    ///
+    /// Returns a random number in `0..n`, without modulo bias.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    ///
+    fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "range is empty");
+
+        // Lemire's nearly divisionless method.
+        let threshold = n.wrapping_neg() % n;
+        loop {
+            let product = u128::from(self.next_u64()) * u128::from(n);
+            if product as u64 >= threshold {
+                return (product >> 64) as u64;
+            }
+        }
+    }
+
+    /// Returns `true` with the given probability.
+    fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64) < probability * (1u64 << 53) as f64
+    }
+}
+
+impl<R: Rng + ?Sized> Rng for &mut R {
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+}
+
+///
+/// An adapter using a generator from the
+/// [`rand`](https://docs.rs/rand) ecosystem as an [`Rng`].
+///
+#[cfg(feature = "rand")]
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct RandRng<R>(pub R);
+
+#[cfg(feature = "rand")]
+impl<R: rand_core::RngCore> Rng for RandRng<R> {
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+}
+
+///
+/// The [SplitMix64](https://prng.di.unimi.it/splitmix64.c) generator.
+///
+/// It is fast and tiny, but not cryptographically secure.
+///
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Construct a new [`SplitMix64`] from a seed.
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+}
+
+impl Rng for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl rand_core::RngCore for SplitMix64 {
+    fn next_u32(&mut self) -> u32 {
+        (Rng::next_u64(self) >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        Rng::next_u64(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        rand_core::impls::fill_bytes_via_next(self, dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl Coord {
+    ///
+    /// Returns a coordinate within `extents` chosen uniformly at random.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `extents` is empty.
+    ///
+    pub fn random(extents: Self, rng: &mut impl Rng) -> Self {
+        Self::random_in(Rect::new(Self(0, 0), extents), rng)
+    }
+
+    ///
+    /// Returns a coordinate inside of `rect` chosen uniformly at random.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rect` is empty.
+    ///
+    pub fn random_in(rect: Rect, rng: &mut impl Rng) -> Self {
+        let (Self(x, y), Self(w, h)) = (rect.origin, rect.extents);
+        Self(
+            x + rng.below(w as u64) as usize,
+            y + rng.below(h as u64) as usize,
+        )
+    }
+}
+
+impl<const W: usize, const H: usize, C: CellState> Grid<W, H, C> {
+    ///
+    /// Returns a live cell of this grid chosen uniformly at random, or
+    /// [`None`] if there are none.
+    ///
+    pub fn sample_live(&self, rng: &mut impl Rng) -> Option<Coord> {
+        self.sample(rng, true)
+    }
+
+    ///
+    /// Returns a dead cell of this grid chosen uniformly at random, or
+    /// [`None`] if there are none.
+    ///
+    pub fn sample_dead(&self, rng: &mut impl Rng) -> Option<Coord> {
+        self.sample(rng, false)
+    }
+
+    /// Returns a random cell whose liveness is `alive`.
+    fn sample(&self, rng: &mut impl Rng, alive: bool) -> Option<Coord> {
+        let matching = |cell: &C| cell.is_alive() == alive;
+        let count = self
+            .cells
+            .iter()
+            .flatten()
+            .filter(|cell| matching(cell))
+            .count();
+        if count == 0 {
+            return None;
+        }
+
+        let n = rng.below(count as u64) as usize;
+        let index = self
+            .cells
+            .iter()
+            .flatten()
+            .enumerate()
+            .filter(|(_, cell)| matching(cell))
+            .nth(n)?
+            .0;
+        Some(Coord(index % W, index / W))
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Sets every cell of this grid, each alive with the given probability
+    /// independently of the others.
+    ///
+    /// The generation is left as it is.
+    ///
+    pub fn randomize(&mut self, density: f64, rng: &mut impl Rng) {
+        for cell in self.cells.iter_mut().flatten() {
+            *cell = Cell::from(rng.chance(density));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn split_mix() {
+        let mut rng = SplitMix64::new(1234567);
+        assert_eq!(rng.next_u64(), 6_457_827_717_110_365_317);
+        assert_eq!(rng.next_u64(), 3_203_168_211_198_807_973);
+
+        assert!((0..1000).all(|_| rng.below(7) < 7));
+        assert!((0..1000).all(|_| !rng.chance(0.0) && rng.chance(1.0)));
+
+        let heads = (0..10_000).filter(|_| rng.chance(0.25)).count();
+        assert!((2_300..2_700).contains(&heads));
+    }
+
+    #[test]
+    fn random_coord() {
+        let mut rng = SplitMix64::new(9);
+        let mut hits = [[0; 3]; 2];
+        for _ in 0..6000 {
+            let Coord(x, y) = Coord::random(Coord(3, 2), &mut rng);
+            hits[y][x] += 1;
+        }
+        assert!(hits.iter().flatten().all(|hits| (850..1150).contains(hits)));
+
+        let rect = Rect::new(Coord(5, 7), Coord(2, 4));
+        assert!((0..1000).all(|_| rect.contains(Coord::random_in(rect, &mut rng))));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn rand() {
+        use rand_core::RngCore;
+
+        let mut expected = SplitMix64::new(11);
+        let mut rng = RandRng(SplitMix64::new(11));
+        assert_eq!(Rng::next_u64(&mut rng), Rng::next_u64(&mut expected));
+
+        let mut bytes = [0; 12];
+        rng.0.fill_bytes(&mut bytes);
+        assert_eq!(bytes[..8], Rng::next_u64(&mut expected).to_le_bytes());
+        let high = (Rng::next_u64(&mut expected) >> 32) as u32;
+        assert_eq!(bytes[8..], high.to_le_bytes());
+    }
+
+    #[test]
+    fn sample() {
+        let mut rng = SplitMix64::new(42);
+        let mut grid = Grid::<4, 3>::new();
+        assert_eq!(grid.sample_live(&mut rng), None);
+
+        let live = [Coord(1, 0), Coord(3, 1), Coord(0, 2)];
+        for coord in live {
+            grid[coord] = Cell::Alive;
+        }
+
+        let mut hits = [0; 3];
+        for _ in 0..3000 {
+            let coord = grid.sample_live(&mut rng).unwrap();
+            hits[live.iter().position(|&live| live == coord).unwrap()] += 1;
+
+            let coord = grid.sample_dead(&mut rng).unwrap();
+            assert_eq!(grid[coord], Cell::Dead);
+        }
+        assert!(hits.iter().all(|hits| (900..1100).contains(hits)));
+
+        for cell in grid.cells.iter_mut().flatten() {
+            *cell = Cell::Alive;
+        }
+        assert_eq!(grid.sample_dead(&mut rng), None);
+    }
+
+    #[test]
+    fn randomize() {
+        let mut rng = SplitMix64::new(6);
+        let mut grid = Grid::<100, 100>::new();
+        grid.generation = 3;
+        grid.randomize(0.25, &mut rng);
+        assert!((2_300..2_700).contains(&grid.population()));
+        assert_eq!(grid.generation, 3);
+
+        grid.randomize(0.0, &mut rng);
+        assert_eq!(grid.population(), 0);
+        grid.randomize(1.0, &mut rng);
+        assert_eq!(grid.population(), 100 * 100);
+    }
+}
@@ -0,0 +1,118 @@
+//!
+//! Drawing grids onto displays.
+//!
+//! See:
+//! - [`Renderer`] for swapping between display targets.
+//! - [`color`] for coloring cells by state, age or activity.
+//! - [`braille`] for compact text using Braille patterns.
+//! - [`half_block`] for text using half blocks, optionally colored.
+//! - [`kitty`] for pixel graphics in terminals supporting the Kitty graphics
+//!   protocol.
+//! - [`sixel`] for pixel graphics in terminals supporting Sixel.
+//! - [`to_packed_bits()`] for 1 bit per pixel framebuffers.
+//! - [`to_rgba()`] for RGBA pixel buffers, such as HTML canvases.
+//! - [`led`] for addressable LED matrices.
+//! - `graphics` for `embedded-graphics` draw targets, with the
+//!   `embedded-graphics` feature.
+//!
+
+pub mod braille;
+pub mod color;
+pub mod framebuffer;
+#[cfg(feature = "embedded-graphics")]
+pub mod graphics;
+pub mod half_block;
+pub mod kitty;
+pub mod led;
+pub mod rgba;
+pub mod sixel;
+
+pub use framebuffer::{to_packed_bits, BitLayout};
+pub use rgba::to_rgba;
+
+use crate::{cell::Cell, grid::Grid, math::Coord};
+
+///
+/// A display target that grids can be rendered onto, so that applications
+/// can swap between targets without touching simulation code.
+///
+/// See [`half_block::TerminalRenderer`], [`framebuffer::FramebufferRenderer`],
+/// `sixel::SixelRenderer` and `kitty::KittyRenderer` with the `std` feature,
+/// and `graphics::GraphicsRenderer` with the `embedded-graphics` feature.
+///
+pub trait Renderer<const W: usize, const H: usize> {
+    type Error;
+
+    ///
+    /// Render the part of `grid` inside `viewport`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error encountered by the display target.
+    ///
+    fn render(&mut self, grid: &Grid<W, H>, viewport: &Viewport) -> Result<(), Self::Error>;
+}
+
+impl<const W: usize, const H: usize, R: Renderer<W, H>> Renderer<W, H> for &mut R {
+    type Error = R::Error;
+
+    fn render(&mut self, grid: &Grid<W, H>, viewport: &Viewport) -> Result<(), Self::Error> {
+        (**self).render(grid, viewport)
+    }
+}
+
+///
+/// A rectangular window onto a grid, starting at `origin` and spanning
+/// `extents` cells.
+///
+/// Cells of the window outside the grid are dead.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Viewport {
+    pub origin: Coord,
+    pub extents: Coord,
+}
+
+impl Viewport {
+    /// Construct a new [`Viewport`].
+    #[must_use]
+    pub const fn new(origin: Coord, extents: Coord) -> Self {
+        Self { origin, extents }
+    }
+
+    ///
+    /// Returns the cell of `grid` at `coord`, relative to this viewport's
+    /// origin.
+    ///
+    #[must_use]
+    pub fn get<const W: usize, const H: usize>(&self, grid: &Grid<W, H>, coord: Coord) -> Cell {
+        let Coord(x, y) = self.origin + coord;
+        if x < W && y < H {
+            grid[Coord(x, y)]
+        } else {
+            Cell::Dead
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> From<&Grid<W, H>> for Viewport {
+    fn from(_: &Grid<W, H>) -> Self {
+        Self::new(Coord(0, 0), Coord(W, H))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn viewport() {
+        let mut grid = Grid::<3, 3>::new();
+        grid[Coord(2, 2)] = Cell::Alive;
+
+        let viewport = Viewport::new(Coord(1, 1), Coord(4, 4));
+        assert_eq!(viewport.get(&grid, Coord(1, 1)), Cell::Alive);
+        assert_eq!(viewport.get(&grid, Coord(3, 3)), Cell::Dead);
+        assert_eq!(Viewport::from(&grid).extents, Coord(3, 3));
+    }
+}
@@ -0,0 +1,94 @@
+//!
+//! Compact text rendering with
+//! [Braille patterns](https://en.wikipedia.org/wiki/Braille_Patterns).
+//!
+//! See [`Grid::braille()`].
+//!
+
+use core::fmt;
+
+use crate::{cell::Cell, grid::Grid, math::Coord};
+
+/// The Braille dot of each cell in a 2 by 4 block, indexed by `[y][x]`.
+const DOTS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+///
+/// A [`Grid`] displayed as Braille patterns, with each character showing a
+/// 2 by 4 block of cells, so that 8 times as many cells fit in a terminal.
+///
+/// Obtained by calling [`Grid::braille()`]. Blocks past the edges of the
+/// grid are padded with dead cells.
+///
+#[derive(Clone, Copy, Debug)]
+pub struct Braille<'a, const W: usize, const H: usize> {
+    grid: &'a Grid<W, H>,
+    line_ending: &'a str,
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    /// Returns a wrapper displaying this grid as Braille patterns.
+    #[must_use]
+    pub fn braille(&self) -> Braille<'_, W, H> {
+        Braille {
+            grid: self,
+            line_ending: "\n",
+        }
+    }
+}
+
+impl<'a, const W: usize, const H: usize> Braille<'a, W, H> {
+    /// Ends lines with `line_ending` instead of `"\n"`.
+    #[must_use]
+    pub fn line_ending(self, line_ending: &'a str) -> Self {
+        Self {
+            line_ending,
+            ..self
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> fmt::Display for Braille<'_, W, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y0 in (0..H).step_by(4) {
+            for x0 in (0..W).step_by(2) {
+                let mut bits = 0;
+                for (dy, row) in DOTS.iter().enumerate() {
+                    for (dx, dot) in row.iter().enumerate() {
+                        let (x, y) = (x0 + dx, y0 + dy);
+                        if x < W && y < H && self.grid[Coord(x, y)] == Cell::Alive {
+                            bits |= dot;
+                        }
+                    }
+                }
+
+                let braille = char::from_u32(0x2800 + u32::from(bits)).expect("valid braille");
+                fmt::Write::write_char(f, braille)?;
+            }
+            f.write_str(self.line_ending)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use std::format;
+
+    use super::*;
+
+    #[test]
+    fn braille() {
+        let mut grid = Grid::<3, 5>::new();
+        for coord in [Coord(0, 0), Coord(1, 3), Coord(2, 0), Coord(0, 4)] {
+            grid[coord] = Cell::Alive;
+        }
+
+        assert_eq!(format!("{}", grid.braille()), "⢁⠁\n⠁⠀\n");
+        assert_eq!(
+            format!("{}", Grid::<2, 4>::new().braille().line_ending("\r\n")),
+            "⠀\r\n"
+        );
+    }
+}
@@ -0,0 +1,163 @@
+//!
+//! Coloring cells by their state, age or activity.
+//!
+//! See [`ColorPolicy`], [`ByAge`] and [`ByHeat`].
+//!
+
+use crate::{
+    cell::Cell,
+    io::Palette,
+    layer::{Ages, HeatMap},
+    math::Coord,
+};
+
+///
+/// A mapping from cells to RGB colors, used by the terminal, image and
+/// `embedded-graphics` renderers alike.
+///
+/// [`Palette`] colors cells by state alone, while [`ByAge`] and [`ByHeat`]
+/// look up further state in a [`Layer`](crate::layer::Layer).
+///
+pub trait ColorPolicy {
+    ///
+    /// Returns the color of the cell at `coord`.
+    ///
+    /// `coord` may lie outside the grid for dead cells past its edges, such
+    /// as in a viewport larger than the grid.
+    ///
+    fn color(&self, coord: Coord, cell: Cell) -> [u8; 3];
+}
+
+impl<P: ColorPolicy + ?Sized> ColorPolicy for &P {
+    fn color(&self, coord: Coord, cell: Cell) -> [u8; 3] {
+        (**self).color(coord, cell)
+    }
+}
+
+impl ColorPolicy for Palette {
+    fn color(&self, _: Coord, cell: Cell) -> [u8; 3] {
+        match cell {
+            Cell::Alive => self.alive,
+            Cell::Dead => self.dead,
+        }
+    }
+}
+
+///
+/// Colors live cells by age, fading from `young` for newborn cells to `old`
+/// for cells at least `max_age` generations old.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ByAge<'a, const W: usize, const H: usize> {
+    pub ages: &'a Ages<W, H>,
+    pub young: [u8; 3],
+    pub old: [u8; 3],
+    pub dead: [u8; 3],
+    pub max_age: u16,
+}
+
+impl<const W: usize, const H: usize> ColorPolicy for ByAge<'_, W, H> {
+    fn color(&self, coord: Coord, cell: Cell) -> [u8; 3] {
+        match cell {
+            Cell::Alive => {
+                let age = self.ages.get(coord).saturating_sub(1);
+                let span = self.max_age.saturating_sub(1).max(1);
+                lerp(
+                    self.young,
+                    self.old,
+                    u32::from(age.min(span)),
+                    u32::from(span),
+                )
+            }
+            Cell::Dead => self.dead,
+        }
+    }
+}
+
+///
+/// Colors dead cells by heat, glowing from `cold` for inactive cells to
+/// `hot` for the most active ones, so that the trail of a pattern stays
+/// visible.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ByHeat<'a, const W: usize, const H: usize> {
+    pub heat: &'a HeatMap<W, H>,
+    pub alive: [u8; 3],
+    pub cold: [u8; 3],
+    pub hot: [u8; 3],
+}
+
+impl<const W: usize, const H: usize> ColorPolicy for ByHeat<'_, W, H> {
+    fn color(&self, Coord(x, y): Coord, cell: Cell) -> [u8; 3] {
+        match cell {
+            Cell::Alive => self.alive,
+            Cell::Dead => {
+                let heat = self.heat.rows().get(y).and_then(|row| row.get(x));
+                lerp(
+                    self.cold,
+                    self.hot,
+                    heat.map_or(0, |&heat| heat.into()),
+                    255,
+                )
+            }
+        }
+    }
+}
+
+/// Interpolates between two colors, `num / den` of the way from `from` to `to`.
+fn lerp(from: [u8; 3], to: [u8; 3], num: u32, den: u32) -> [u8; 3] {
+    core::array::from_fn(|i| {
+        let [from, to] = [from[i], to[i]].map(u32::from);
+        let mixed = (from * (den - num) + to * num + den / 2) / den;
+        mixed as u8
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{grid::Grid, layer::Layer, rule::Life};
+
+    #[test]
+    fn by_age() {
+        let mut grid = Grid::<4, 4>::new();
+        for coord in [Coord(1, 1), Coord(2, 1), Coord(1, 2), Coord(2, 2)] {
+            grid[coord] = Cell::Alive;
+        }
+
+        let mut ages = Ages::from(&grid);
+        for _ in 0..2 {
+            grid = grid.step_with_layer(&Life, &mut ages);
+        }
+
+        let mut colors = ByAge {
+            ages: &ages,
+            young: [0, 0, 0],
+            old: [200, 100, 0],
+            dead: [9, 9, 9],
+            max_age: 5,
+        };
+        assert_eq!(colors.color(Coord(1, 1), Cell::Alive), [100, 50, 0]);
+        assert_eq!(colors.color(Coord(0, 0), Cell::Dead), [9, 9, 9]);
+
+        colors.max_age = 2;
+        assert_eq!(colors.color(Coord(1, 1), Cell::Alive), [200, 100, 0]);
+    }
+
+    #[test]
+    fn by_heat() {
+        let mut heat = HeatMap::<2, 1>::new(51, 0);
+        heat.update(Coord(0, 0), Cell::Dead, Cell::Alive);
+
+        let colors = ByHeat {
+            heat: &heat,
+            alive: [255; 3],
+            cold: [0; 3],
+            hot: [250, 0, 0],
+        };
+        assert_eq!(colors.color(Coord(0, 0), Cell::Dead), [50, 0, 0]);
+        assert_eq!(colors.color(Coord(1, 0), Cell::Dead), [0; 3]);
+        assert_eq!(colors.color(Coord(5, 5), Cell::Dead), [0; 3]);
+        assert_eq!(colors.color(Coord(1, 0), Cell::Alive), [255; 3]);
+    }
+}
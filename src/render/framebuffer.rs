@@ -0,0 +1,197 @@
+//!
+//! Packing grids into 1 bit per pixel framebuffers.
+//!
+//! See [`to_packed_bits()`] and [`FramebufferRenderer`].
+//!
+
+use core::fmt;
+
+use super::{Renderer, Viewport};
+use crate::{cell::Cell, grid::Grid, math::Coord};
+
+///
+/// The layout of a 1 bit per pixel framebuffer.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum BitLayout {
+    ///
+    /// Rows from top to bottom, each padded to a whole number of bytes,
+    /// with the leftmost pixel in the most significant bit. This is the
+    /// layout of PBM images and most TFT and e-paper controllers.
+    ///
+    #[default]
+    RowMajorMsbFirst,
+    /// Like [`BitLayout::RowMajorMsbFirst`], but with the leftmost pixel in
+    /// the least significant bit.
+    RowMajorLsbFirst,
+    ///
+    /// Pages of 8 rows from top to bottom, each a byte per column from left
+    /// to right, with the top pixel in the least significant bit. This is
+    /// the layout of SSD1306, SH1106 and similar OLED controllers.
+    ///
+    Pages,
+}
+
+impl BitLayout {
+    /// Returns the length in bytes of a framebuffer of `width` by `height` pixels.
+    #[must_use]
+    pub const fn buffer_len(self, width: usize, height: usize) -> usize {
+        match self {
+            Self::RowMajorMsbFirst | Self::RowMajorLsbFirst => width.div_ceil(8) * height,
+            Self::Pages => width * height.div_ceil(8),
+        }
+    }
+
+    /// Returns the byte index and bit mask of a pixel.
+    const fn locate(self, width: usize, x: usize, y: usize) -> (usize, u8) {
+        match self {
+            Self::RowMajorMsbFirst => (y * width.div_ceil(8) + x / 8, 0x80 >> (x % 8)),
+            Self::RowMajorLsbFirst => (y * width.div_ceil(8) + x / 8, 1 << (x % 8)),
+            Self::Pages => ((y / 8) * width + x, 1 << (y % 8)),
+        }
+    }
+}
+
+///
+/// Pack `grid` into `buf` with one pixel per cell in the given layout, with
+/// live cells as set bits, returning the packed prefix of `buf`.
+///
+/// Padding bits are cleared.
+///
+/// # Panics
+///
+/// Panics if `buf` is shorter than [`BitLayout::buffer_len()`] of the grid's
+/// extents.
+///
+pub fn to_packed_bits<'b, const W: usize, const H: usize>(
+    grid: &Grid<W, H>,
+    buf: &'b mut [u8],
+    layout: BitLayout,
+) -> &'b mut [u8] {
+    let len = layout.buffer_len(W, H);
+    assert!(buf.len() >= len, "buffer too small, {len} bytes needed");
+
+    pack(grid, &Viewport::from(grid), &mut buf[..len], layout)
+}
+
+/// Packs the part of `grid` inside `viewport` into `buf`, which must be exactly long enough.
+fn pack<'b, const W: usize, const H: usize>(
+    grid: &Grid<W, H>,
+    viewport: &Viewport,
+    buf: &'b mut [u8],
+    layout: BitLayout,
+) -> &'b mut [u8] {
+    let Coord(width, height) = viewport.extents;
+
+    buf.fill(0);
+    for y in 0..height {
+        for x in 0..width {
+            if viewport.get(grid, Coord(x, y)) == Cell::Alive {
+                let (i, mask) = layout.locate(width, x, y);
+                buf[i] |= mask;
+            }
+        }
+    }
+
+    buf
+}
+
+///
+/// A [`Renderer`] packing grids into a 1 bit per pixel framebuffer, with one
+/// pixel per cell of the viewport.
+///
+#[derive(Debug)]
+pub struct FramebufferRenderer<'b> {
+    pub buf: &'b mut [u8],
+    pub layout: BitLayout,
+}
+
+impl<'b> FramebufferRenderer<'b> {
+    /// Construct a new [`FramebufferRenderer`] packing into `buf`.
+    #[must_use]
+    pub fn new(buf: &'b mut [u8], layout: BitLayout) -> Self {
+        Self { buf, layout }
+    }
+}
+
+impl<const W: usize, const H: usize> Renderer<W, H> for FramebufferRenderer<'_> {
+    type Error = BufferTooSmall;
+
+    fn render(&mut self, grid: &Grid<W, H>, viewport: &Viewport) -> Result<(), BufferTooSmall> {
+        let Coord(width, height) = viewport.extents;
+        let needed = self.layout.buffer_len(width, height);
+        let buf = self
+            .buf
+            .get_mut(..needed)
+            .ok_or(BufferTooSmall { needed })?;
+
+        pack(grid, viewport, buf, self.layout);
+        Ok(())
+    }
+}
+
+///
+/// An error returned by [`FramebufferRenderer`] when its buffer is too small
+/// for the viewport.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BufferTooSmall {
+    /// The length in bytes needed.
+    pub needed: usize,
+}
+
+impl fmt::Display for BufferTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "buffer too small, {} bytes needed", self.needed)
+    }
+}
+
+impl core::error::Error for BufferTooSmall {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn layouts() {
+        let mut grid = Grid::<10, 9>::new();
+        for coord in [Coord(0, 0), Coord(9, 0), Coord(1, 7), Coord(2, 8)] {
+            grid[coord] = Cell::Alive;
+        }
+
+        let mut buf = [0xff; 20];
+        let msb = to_packed_bits(&grid, &mut buf, BitLayout::RowMajorMsbFirst);
+        assert_eq!(msb.len(), 18);
+        assert_eq!(msb[0..2], [0x80, 0x40]);
+        assert_eq!(msb[14..18], [0x40, 0x00, 0x20, 0x00]);
+
+        let lsb = to_packed_bits(&grid, &mut buf, BitLayout::RowMajorLsbFirst);
+        assert_eq!(lsb[0..2], [0x01, 0x02]);
+        assert_eq!(lsb[14..18], [0x02, 0x00, 0x04, 0x00]);
+
+        let pages = to_packed_bits(&grid, &mut buf, BitLayout::Pages);
+        assert_eq!(pages.len(), 20);
+        assert_eq!(pages[0..3], [0x01, 0x80, 0x00]);
+        assert_eq!(pages[9], 0x01);
+        assert_eq!(pages[12], 0x01);
+        assert_eq!(pages.iter().map(|byte| byte.count_ones()).sum::<u32>(), 4);
+    }
+
+    #[test]
+    fn framebuffer_renderer() {
+        let mut grid = Grid::<16, 16>::new();
+        grid[Coord(9, 4)] = Cell::Alive;
+
+        let mut buf = [0xff; 2];
+        let mut renderer = FramebufferRenderer::new(&mut buf, BitLayout::RowMajorMsbFirst);
+        let viewport = Viewport::new(Coord(8, 4), Coord(8, 2));
+        renderer.render(&grid, &viewport).unwrap();
+        assert_eq!(buf, [0x40, 0x00]);
+
+        let mut renderer = FramebufferRenderer::new(&mut buf, BitLayout::Pages);
+        assert_eq!(
+            renderer.render(&grid, &Viewport::from(&grid)),
+            Err(BufferTooSmall { needed: 32 })
+        );
+    }
+}
@@ -0,0 +1,265 @@
+//!
+//! Drawing grids onto [`embedded-graphics`](embedded_graphics) draw targets.
+//!
+//! See [`Grid::drawable()`], [`Grid::drawable_with()`] and
+//! [`GraphicsRenderer`].
+//!
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, Point, Size},
+    pixelcolor::{PixelColor, Rgb888},
+    primitives::{PointsIter, Rectangle},
+    Drawable, Pixel,
+};
+
+use super::{color::ColorPolicy, Renderer, Viewport};
+use crate::{cell::Cell, grid::Grid, math::Coord};
+
+///
+/// A [`Grid`] that can be drawn onto any [`DrawTarget`], such as an SSD1306
+/// or ST7789 display or the `embedded-graphics` simulator.
+///
+/// Each cell inside `viewport` is drawn as a square of `cell_size` pixels,
+/// starting from `top_left`. Dead cells are only drawn if they have a color, so that a
+/// grid can be drawn over an existing background.
+///
+#[derive(Clone, Copy, Debug)]
+pub struct GridDrawable<'a, const W: usize, const H: usize, C> {
+    pub grid: &'a Grid<W, H>,
+    pub viewport: Viewport,
+    pub top_left: Point,
+    pub cell_size: u32,
+    pub alive: C,
+    pub dead: Option<C>,
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Returns a [`GridDrawable`] of the whole grid, with cells of 1 pixel
+    /// starting from the origin.
+    ///
+    #[must_use]
+    pub fn drawable<C: PixelColor>(&self, alive: C, dead: Option<C>) -> GridDrawable<'_, W, H, C> {
+        GridDrawable {
+            grid: self,
+            viewport: Viewport::from(self),
+            top_left: Point::zero(),
+            cell_size: 1,
+            alive,
+            dead,
+        }
+    }
+
+    ///
+    /// Returns a [`ColoredDrawable`] of the whole grid, colored by `colors`,
+    /// with cells of 1 pixel starting from the origin.
+    ///
+    #[must_use]
+    pub fn drawable_with<P: ColorPolicy>(&self, colors: P) -> ColoredDrawable<'_, W, H, P> {
+        ColoredDrawable {
+            grid: self,
+            viewport: Viewport::from(self),
+            top_left: Point::zero(),
+            cell_size: 1,
+            colors,
+        }
+    }
+}
+
+/// Returns the bounding box of a viewport drawn with cells of `cell_size` pixels.
+fn bounding_box(top_left: Point, viewport: &Viewport, cell_size: u32) -> Rectangle {
+    let Coord(width, height) = viewport.extents;
+    Rectangle::new(top_left, Size::new(width as u32, height as u32) * cell_size)
+}
+
+/// Returns the viewport coordinate of the cell covering `point`.
+fn locate(point: Point, top_left: Point, cell_size: u32) -> Coord {
+    let size = cell_size.max(1) as usize;
+    let Point { x, y } = point - top_left;
+    Coord(x as usize / size, y as usize / size)
+}
+
+impl<const W: usize, const H: usize, C: PixelColor> Dimensions for GridDrawable<'_, W, H, C> {
+    fn bounding_box(&self) -> Rectangle {
+        bounding_box(self.top_left, &self.viewport, self.cell_size)
+    }
+}
+
+impl<const W: usize, const H: usize, C: PixelColor> Drawable for GridDrawable<'_, W, H, C> {
+    type Color = C;
+    type Output = ();
+
+    fn draw<D: DrawTarget<Color = C>>(&self, target: &mut D) -> Result<(), D::Error> {
+        let color = |point: Point| {
+            let coord = locate(point, self.top_left, self.cell_size);
+            match self.viewport.get(self.grid, coord) {
+                Cell::Alive => Some(self.alive),
+                Cell::Dead => self.dead,
+            }
+        };
+
+        match self.dead {
+            Some(_) => target.fill_contiguous(
+                &self.bounding_box(),
+                self.bounding_box()
+                    .points()
+                    .map(|point| color(point).unwrap_or(self.alive)),
+            ),
+            None => target.draw_iter(
+                self.bounding_box()
+                    .points()
+                    .filter_map(|point| color(point).map(|color| Pixel(point, color))),
+            ),
+        }
+    }
+}
+
+///
+/// A [`Grid`] that can be drawn onto any [`DrawTarget`] with RGB colors
+/// given by a [`ColorPolicy`], such as cells colored by age.
+///
+/// Colors are [`Rgb888`], so targets with other color types can be drawn
+/// onto with [`DrawTargetExt::color_converted()`].
+///
+/// [`DrawTargetExt::color_converted()`]: embedded_graphics::draw_target::DrawTargetExt::color_converted
+///
+#[derive(Clone, Copy, Debug)]
+pub struct ColoredDrawable<'a, const W: usize, const H: usize, P> {
+    pub grid: &'a Grid<W, H>,
+    pub viewport: Viewport,
+    pub top_left: Point,
+    pub cell_size: u32,
+    pub colors: P,
+}
+
+impl<const W: usize, const H: usize, P: ColorPolicy> Dimensions for ColoredDrawable<'_, W, H, P> {
+    fn bounding_box(&self) -> Rectangle {
+        bounding_box(self.top_left, &self.viewport, self.cell_size)
+    }
+}
+
+impl<const W: usize, const H: usize, P: ColorPolicy> Drawable for ColoredDrawable<'_, W, H, P> {
+    type Color = Rgb888;
+    type Output = ();
+
+    fn draw<D: DrawTarget<Color = Rgb888>>(&self, target: &mut D) -> Result<(), D::Error> {
+        let area = self.bounding_box();
+        target.fill_contiguous(
+            &area,
+            area.points().map(|point| {
+                let coord = locate(point, self.top_left, self.cell_size);
+                let cell = self.viewport.get(self.grid, coord);
+                let [r, g, b] = self.colors.color(self.viewport.origin + coord, cell);
+                Rgb888::new(r, g, b)
+            }),
+        )
+    }
+}
+
+///
+/// A [`Renderer`] drawing grids onto a [`DrawTarget`], with the same options
+/// as [`GridDrawable`].
+///
+#[derive(Clone, Copy, Debug)]
+pub struct GraphicsRenderer<D, C> {
+    pub target: D,
+    pub top_left: Point,
+    pub cell_size: u32,
+    pub alive: C,
+    pub dead: Option<C>,
+}
+
+impl<D: DrawTarget<Color = C>, C: PixelColor> GraphicsRenderer<D, C> {
+    ///
+    /// Construct a new [`GraphicsRenderer`] drawing onto `target`, with
+    /// cells of 1 pixel starting from the origin.
+    ///
+    #[must_use]
+    pub fn new(target: D, alive: C, dead: Option<C>) -> Self {
+        Self {
+            target,
+            top_left: Point::zero(),
+            cell_size: 1,
+            alive,
+            dead,
+        }
+    }
+}
+
+impl<const W: usize, const H: usize, D, C> Renderer<W, H> for GraphicsRenderer<D, C>
+where
+    D: DrawTarget<Color = C>,
+    C: PixelColor,
+{
+    type Error = D::Error;
+
+    fn render(&mut self, grid: &Grid<W, H>, viewport: &Viewport) -> Result<(), D::Error> {
+        GridDrawable {
+            grid,
+            viewport: *viewport,
+            top_left: self.top_left,
+            cell_size: self.cell_size,
+            alive: self.alive,
+            dead: self.dead,
+        }
+        .draw(&mut self.target)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    use super::*;
+    use crate::io::Palette;
+
+    #[test]
+    fn draw() {
+        let mut grid = Grid::<3, 2>::new();
+        grid[Coord(0, 0)] = Cell::Alive;
+        grid[Coord(2, 1)] = Cell::Alive;
+
+        let mut display = MockDisplay::new();
+        grid.drawable(BinaryColor::On, Some(BinaryColor::Off))
+            .draw(&mut display)
+            .unwrap();
+        display.assert_pattern(&["#..", "..#"]);
+
+        let mut display = MockDisplay::new();
+        let drawable = GridDrawable {
+            top_left: Point::new(1, 0),
+            cell_size: 2,
+            ..grid.drawable(BinaryColor::On, None)
+        };
+        drawable.draw(&mut display).unwrap();
+        display.assert_pattern(&[" ##    ", " ##    ", "     ##", "     ##"]);
+        assert_eq!(drawable.bounding_box().size, Size::new(6, 4));
+    }
+
+    #[test]
+    fn graphics_renderer() {
+        let mut grid = Grid::<4, 4>::new();
+        grid[Coord(2, 3)] = Cell::Alive;
+
+        let mut renderer = GraphicsRenderer::new(MockDisplay::new(), BinaryColor::On, None);
+        renderer
+            .render(&grid, &Viewport::new(Coord(1, 2), Coord(2, 2)))
+            .unwrap();
+        renderer.target.assert_pattern(&["  ", " #"]);
+    }
+
+    #[test]
+    fn draw_colored() {
+        let mut grid = Grid::<2, 1>::new();
+        grid[Coord(1, 0)] = Cell::Alive;
+
+        let palette = Palette {
+            alive: [255, 0, 0],
+            dead: [0, 0, 255],
+        };
+        let mut display = MockDisplay::new();
+        grid.drawable_with(palette).draw(&mut display).unwrap();
+        display.assert_pattern(&["BR"]);
+    }
+}
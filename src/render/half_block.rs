@@ -0,0 +1,241 @@
+//!
+//! Text rendering with half blocks, showing two rows of cells per line.
+//!
+//! See [`Grid::half_blocks()`] and [`TerminalRenderer`].
+//!
+
+use core::fmt::{self, Write};
+
+use super::{color::ColorPolicy, Renderer, Viewport};
+use crate::{cell::Cell, grid::Grid, io::Palette, math::Coord};
+
+///
+/// A [`Grid`] displayed with `▀`, `▄` and `█`, with each character showing
+/// two vertically adjacent cells, so that cells are roughly square in most
+/// terminals.
+///
+/// Obtained by calling [`Grid::half_blocks()`]. With a palette, cells are
+/// colored with 24-bit ANSI escape codes, which are only emitted when the
+/// colors change.
+///
+#[derive(Clone, Copy, Debug)]
+pub struct HalfBlocks<'a, const W: usize, const H: usize, P = Palette> {
+    grid: &'a Grid<W, H>,
+    viewport: Viewport,
+    palette: Option<P>,
+    line_ending: &'a str,
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    /// Returns a wrapper displaying this grid with half blocks.
+    #[must_use]
+    pub fn half_blocks(&self) -> HalfBlocks<'_, W, H> {
+        HalfBlocks {
+            grid: self,
+            viewport: Viewport::from(self),
+            palette: None,
+            line_ending: "\n",
+        }
+    }
+}
+
+impl<'a, const W: usize, const H: usize, P> HalfBlocks<'a, W, H, P> {
+    /// Only displays the cells inside `viewport`.
+    #[must_use]
+    pub fn viewport(self, viewport: Viewport) -> Self {
+        Self { viewport, ..self }
+    }
+
+    /// Colors cells with ANSI escape codes according to `palette`.
+    #[must_use]
+    pub fn palette<Q: ColorPolicy>(self, palette: Q) -> HalfBlocks<'a, W, H, Q> {
+        HalfBlocks {
+            grid: self.grid,
+            viewport: self.viewport,
+            palette: Some(palette),
+            line_ending: self.line_ending,
+        }
+    }
+
+    /// Ends lines with `line_ending` instead of `"\n"`.
+    #[must_use]
+    pub fn line_ending(self, line_ending: &'a str) -> Self {
+        Self {
+            line_ending,
+            ..self
+        }
+    }
+}
+
+impl<const W: usize, const H: usize, P: ColorPolicy> HalfBlocks<'_, W, H, P> {
+    /// Writes one line of colored half blocks, starting at row `y`.
+    fn fmt_colored(&self, f: &mut fmt::Formatter<'_>, palette: &P, y: usize) -> fmt::Result {
+        let Coord(width, height) = self.viewport.extents;
+        let cell = |coord| {
+            let cell = self.viewport.get(self.grid, coord);
+            (cell, palette.color(self.viewport.origin + coord, cell))
+        };
+
+        let (mut fg, mut bg) = (None, None);
+        for x in 0..width {
+            let top = cell(Coord(x, y));
+            let bottom = (y + 1 < height).then(|| cell(Coord(x, y + 1)));
+
+            let (glyph, new_fg, new_bg) = match (top, bottom) {
+                ((Cell::Alive, top), Some((Cell::Alive, bottom))) if top == bottom => {
+                    ("█", Some(top), None)
+                }
+                ((Cell::Dead, top), Some((Cell::Dead, bottom))) if top == bottom => {
+                    (" ", None, Some(top))
+                }
+                ((Cell::Alive, top), None) => ("▀", Some(top), None),
+                ((Cell::Dead, top), None) => (" ", None, Some(top)),
+                ((Cell::Dead, top), Some((Cell::Alive, bottom))) => ("▄", Some(bottom), Some(top)),
+                ((_, top), Some((_, bottom))) => ("▀", Some(top), Some(bottom)),
+            };
+
+            if let Some([r, g, b]) = new_fg.filter(|_| new_fg != fg) {
+                write!(f, "\x1b[38;2;{r};{g};{b}m")?;
+                fg = new_fg;
+            }
+            if let Some([r, g, b]) = new_bg.filter(|_| new_bg != bg) {
+                write!(f, "\x1b[48;2;{r};{g};{b}m")?;
+                bg = new_bg;
+            }
+            f.write_str(glyph)?;
+        }
+
+        if fg.is_some() || bg.is_some() {
+            f.write_str("\x1b[0m")?;
+        }
+        Ok(())
+    }
+}
+
+impl<const W: usize, const H: usize, P: ColorPolicy> fmt::Display for HalfBlocks<'_, W, H, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Coord(width, height) = self.viewport.extents;
+        let alive = |x, y| y < height && self.viewport.get(self.grid, Coord(x, y)) == Cell::Alive;
+
+        for y in (0..height).step_by(2) {
+            if let Some(palette) = &self.palette {
+                self.fmt_colored(f, palette, y)?;
+            } else {
+                for x in 0..width {
+                    f.write_str(match (alive(x, y), alive(x, y + 1)) {
+                        (true, true) => "█",
+                        (true, false) => "▀",
+                        (false, true) => "▄",
+                        (false, false) => " ",
+                    })?;
+                }
+            }
+            f.write_str(self.line_ending)?;
+        }
+        Ok(())
+    }
+}
+
+///
+/// A [`Renderer`] writing grids as half blocks to a terminal, or any other
+/// [`Write`].
+///
+/// Each render starts with moving the cursor to the top left corner, so
+/// that frames overwrite each other.
+///
+#[derive(Clone, Copy, Debug)]
+pub struct TerminalRenderer<'a, T, P = Palette> {
+    pub writer: T,
+    pub palette: Option<P>,
+    pub line_ending: &'a str,
+}
+
+impl<T: Write> TerminalRenderer<'_, T> {
+    /// Construct a new [`TerminalRenderer`] writing to `writer`.
+    #[must_use]
+    pub const fn new(writer: T) -> Self {
+        Self {
+            writer,
+            palette: None,
+            line_ending: "\n",
+        }
+    }
+}
+
+impl<const W: usize, const H: usize, T: Write, P: ColorPolicy> Renderer<W, H>
+    for TerminalRenderer<'_, T, P>
+{
+    type Error = fmt::Error;
+
+    fn render(&mut self, grid: &Grid<W, H>, viewport: &Viewport) -> fmt::Result {
+        let blocks = grid
+            .half_blocks()
+            .viewport(*viewport)
+            .line_ending(self.line_ending);
+
+        match &self.palette {
+            Some(palette) => write!(self.writer, "\x1b[H{}", blocks.palette(palette)),
+            None => write!(self.writer, "\x1b[H{blocks}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use std::{format, string::String};
+
+    use super::*;
+
+    #[test]
+    fn half_blocks() {
+        let mut grid = Grid::<4, 3>::new();
+        for coord in [
+            Coord(0, 0),
+            Coord(0, 1),
+            Coord(1, 0),
+            Coord(2, 1),
+            Coord(3, 2),
+        ] {
+            grid[coord] = Cell::Alive;
+        }
+
+        assert_eq!(format!("{}", grid.half_blocks()), "█▀▄ \n   ▀\n");
+
+        let palette = Palette {
+            alive: [255, 0, 0],
+            dead: [0, 0, 32],
+        };
+        let grid = Grid::<1, 1>::new();
+        assert_eq!(
+            format!(
+                "{}",
+                grid.half_blocks().palette(palette).line_ending("\r\n")
+            ),
+            "\x1b[48;2;0;0;32m \x1b[0m\r\n"
+        );
+
+        let mut grid = Grid::<3, 2>::new();
+        grid[Coord(0, 0)] = Cell::Alive;
+        grid[Coord(1, 1)] = Cell::Alive;
+        grid[Coord(2, 1)] = Cell::Alive;
+        assert_eq!(
+            format!("{}", grid.half_blocks().palette(palette)),
+            "\x1b[38;2;255;0;0m\x1b[48;2;0;0;32m▀▄▄\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn terminal_renderer() {
+        let mut grid = Grid::<4, 4>::new();
+        grid[Coord(1, 2)] = Cell::Alive;
+        grid[Coord(3, 3)] = Cell::Alive;
+
+        let mut renderer = TerminalRenderer::new(String::new());
+        renderer
+            .render(&grid, &Viewport::new(Coord(1, 2), Coord(3, 1)))
+            .unwrap();
+        assert_eq!(renderer.writer, "\x1b[H▀  \n");
+    }
+}
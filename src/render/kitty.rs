@@ -0,0 +1,227 @@
+//!
+//! Pixel graphics for terminals supporting the Kitty graphics protocol,
+//! such as Kitty, WezTerm and Ghostty.
+//!
+//! See [`Grid::kitty()`], and `KittyRenderer` with the `std` feature.
+//!
+
+use core::fmt::{self, Write};
+
+use super::{color::ColorPolicy, Viewport};
+use crate::{grid::Grid, io::Palette, math::Coord};
+
+/// The maximum length of the payload of one escape code.
+const CHUNK_LEN: usize = 4096;
+
+/// The base 64 alphabet of the protocol's payloads.
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+///
+/// A [`Grid`] displayed as an image with the Kitty graphics protocol, with
+/// one pixel per cell.
+///
+/// Obtained by calling [`Grid::kitty()`]. The image is sent uncompressed as
+/// 24-bit RGB and placed at the cursor, which is left in place. Sending
+/// another image with the same id replaces it, so frames can be pushed
+/// without flicker.
+///
+#[derive(Clone, Copy, Debug)]
+pub struct Kitty<'a, const W: usize, const H: usize, P = Palette> {
+    grid: &'a Grid<W, H>,
+    viewport: Viewport,
+    palette: P,
+    id: u32,
+    size: Option<Coord>,
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Returns a wrapper displaying this grid with the Kitty graphics
+    /// protocol, in the default palette with image id 1.
+    ///
+    #[must_use]
+    pub fn kitty(&self) -> Kitty<'_, W, H> {
+        Kitty {
+            grid: self,
+            viewport: Viewport::from(self),
+            palette: Palette::default(),
+            id: 1,
+            size: None,
+        }
+    }
+}
+
+impl<'a, const W: usize, const H: usize, P> Kitty<'a, W, H, P> {
+    /// Only displays the cells inside `viewport`.
+    #[must_use]
+    pub fn viewport(self, viewport: Viewport) -> Self {
+        Self { viewport, ..self }
+    }
+
+    /// Colors cells according to `palette`.
+    #[must_use]
+    pub fn palette<Q: ColorPolicy>(self, palette: Q) -> Kitty<'a, W, H, Q> {
+        Kitty {
+            grid: self.grid,
+            viewport: self.viewport,
+            palette,
+            id: self.id,
+            size: self.size,
+        }
+    }
+
+    /// Sends the image with id `id`, which must be non-zero.
+    #[must_use]
+    pub fn id(self, id: u32) -> Self {
+        Self { id, ..self }
+    }
+
+    ///
+    /// Scales the image to fill `size` terminal columns and rows, instead
+    /// of showing it at one terminal pixel per cell.
+    ///
+    #[must_use]
+    pub fn size(self, size: Coord) -> Self {
+        Self {
+            size: Some(size),
+            ..self
+        }
+    }
+}
+
+impl<const W: usize, const H: usize, P: ColorPolicy> fmt::Display for Kitty<'_, W, H, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Coord(width, height) = self.viewport.extents;
+        let id = self.id;
+        write!(f, "\x1b_Ga=T,f=24,s={width},v={height},i={id},q=2,C=1")?;
+        if let Some(Coord(columns, rows)) = self.size {
+            write!(f, ",c={columns},r={rows}")?;
+        }
+
+        // every pixel is 3 bytes, which encode to exactly 4 characters.
+        let pixels = width * height;
+        let per_chunk = CHUNK_LEN / 4;
+        for start in (0..pixels.max(1)).step_by(per_chunk) {
+            if start > 0 {
+                f.write_str("\x1b_G")?;
+            }
+            let more = start + per_chunk < pixels;
+            write!(
+                f,
+                "{}m={};",
+                if start > 0 { "" } else { "," },
+                u8::from(more)
+            )?;
+
+            for i in start..pixels.min(start + per_chunk) {
+                let coord = Coord(i % width, i / width);
+                let cell = self.viewport.get(self.grid, coord);
+                let [r, g, b] = self.palette.color(self.viewport.origin + coord, cell);
+
+                let bits = u32::from_be_bytes([0, r, g, b]);
+                for shift in [18, 12, 6, 0] {
+                    f.write_char(char::from(BASE64[(bits >> shift) as usize & 0x3f]))?;
+                }
+            }
+            f.write_str("\x1b\\")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+pub use renderer::KittyRenderer;
+
+#[cfg(feature = "std")]
+mod renderer {
+    use std::io::{self, Write};
+
+    use super::super::{color::ColorPolicy, Renderer, Viewport};
+    use crate::{grid::Grid, io::Palette, math::Coord};
+
+    ///
+    /// A [`Renderer`] pushing grids as images with the Kitty graphics
+    /// protocol to a terminal, or any other [`Write`].
+    ///
+    /// Every frame replaces the image with id `id`, placed at the top left
+    /// corner and scaled to `size` terminal columns and rows, if any.
+    ///
+    #[derive(Clone, Copy, Debug)]
+    pub struct KittyRenderer<T, P = Palette> {
+        pub writer: T,
+        pub palette: P,
+        pub id: u32,
+        pub size: Option<Coord>,
+    }
+
+    impl<T: Write> KittyRenderer<T> {
+        ///
+        /// Construct a new [`KittyRenderer`] writing to `writer`, in the
+        /// default palette with image id 1.
+        ///
+        #[must_use]
+        pub fn new(writer: T) -> Self {
+            Self {
+                writer,
+                palette: Palette::default(),
+                id: 1,
+                size: None,
+            }
+        }
+    }
+
+    impl<const W: usize, const H: usize, T: Write, P: ColorPolicy> Renderer<W, H>
+        for KittyRenderer<T, P>
+    {
+        type Error = io::Error;
+
+        fn render(&mut self, grid: &Grid<W, H>, viewport: &Viewport) -> io::Result<()> {
+            let kitty = grid
+                .kitty()
+                .viewport(*viewport)
+                .palette(&self.palette)
+                .id(self.id);
+            match self.size {
+                Some(size) => write!(self.writer, "\x1b[H{}", kitty.size(size))?,
+                None => write!(self.writer, "\x1b[H{kitty}")?,
+            }
+            self.writer.flush()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use std::format;
+
+    use super::*;
+    use crate::cell::Cell;
+
+    #[test]
+    fn kitty() {
+        let mut grid = Grid::<2, 1>::new();
+        grid[Coord(1, 0)] = Cell::Alive;
+        let palette = Palette {
+            alive: [0xff, 0x00, 0x80],
+            dead: [0x00; 3],
+        };
+        assert_eq!(
+            format!("{}", grid.kitty().palette(palette).id(7).size(Coord(4, 1))),
+            "\x1b_Ga=T,f=24,s=2,v=1,i=7,q=2,C=1,c=4,r=1,m=0;AAAA/wCA\x1b\\"
+        );
+    }
+
+    #[test]
+    fn chunks() {
+        let grid = Grid::<1100, 1>::new();
+        let kitty = format!("{}", grid.kitty());
+        let chunks = kitty.split("\x1b\\").collect::<std::vec::Vec<_>>();
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[0].contains(",m=1;"));
+        assert!(chunks[0].ends_with(&"/".repeat(CHUNK_LEN)));
+        assert_eq!(chunks[1], format!("\x1b_Gm=0;{}", "/".repeat(76 * 4)));
+        assert!(chunks[2].is_empty());
+    }
+}
@@ -0,0 +1,157 @@
+//!
+//! Mapping grids onto addressable LED matrices, such as WS2812 panels.
+//!
+//! See [`LedLayout`] and [`Grid::leds()`].
+//!
+
+use crate::{cell::Cell, grid::Grid, math::Coord};
+
+///
+/// The order LEDs are wired in, within a panel or across panels.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Wiring {
+    /// Every row runs left to right.
+    #[default]
+    RowMajor,
+    /// Rows alternate between running left to right and right to left,
+    /// starting with left to right.
+    Serpentine,
+}
+
+impl Wiring {
+    /// Returns the position of `(x, y)` in a `width` wide block.
+    const fn index(self, Coord(x, y): Coord, width: usize) -> usize {
+        match self {
+            Self::Serpentine if y % 2 == 1 => y * width + width - 1 - x,
+            _ => y * width + x,
+        }
+    }
+
+    /// Returns the coordinate at `index` in a `width` wide block.
+    const fn coord(self, index: usize, width: usize) -> Coord {
+        let (x, y) = (index % width, index / width);
+        match self {
+            Self::Serpentine if y % 2 == 1 => Coord(width - 1 - x, y),
+            _ => Coord(x, y),
+        }
+    }
+}
+
+///
+/// The layout of an LED matrix made of identical panels of `panel` extents,
+/// each wired according to `wiring`, and chained together according to
+/// `chain`.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LedLayout {
+    pub panel: Coord,
+    pub wiring: Wiring,
+    pub chain: Wiring,
+}
+
+impl LedLayout {
+    /// Construct a new [`LedLayout`] of a single panel of the given extents.
+    #[must_use]
+    pub const fn single(extents: Coord, wiring: Wiring) -> Self {
+        Self {
+            panel: extents,
+            wiring,
+            chain: Wiring::RowMajor,
+        }
+    }
+
+    ///
+    /// Returns the index along the strip of the LED at `coord` in a matrix
+    /// of `extents`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the extents are not a multiple of the panel extents.
+    ///
+    #[must_use]
+    pub const fn index(&self, coord: Coord, extents: Coord) -> usize {
+        let Coord(pw, ph) = self.panel;
+        assert!(
+            extents.0.is_multiple_of(pw) && extents.1.is_multiple_of(ph),
+            "extents must be a multiple of the panel extents"
+        );
+
+        let panel = self
+            .chain
+            .index(Coord(coord.0 / pw, coord.1 / ph), extents.0 / pw);
+        panel * pw * ph + self.wiring.index(Coord(coord.0 % pw, coord.1 % ph), pw)
+    }
+
+    ///
+    /// Returns the coordinate of the LED at `index` along the strip in a
+    /// matrix of `extents`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the extents are not a multiple of the panel extents.
+    ///
+    #[must_use]
+    pub const fn coord(&self, index: usize, extents: Coord) -> Coord {
+        let Coord(pw, ph) = self.panel;
+        assert!(
+            extents.0.is_multiple_of(pw) && extents.1.is_multiple_of(ph),
+            "extents must be a multiple of the panel extents"
+        );
+
+        let panel = self.chain.coord(index / (pw * ph), extents.0 / pw);
+        let local = self.wiring.coord(index % (pw * ph), pw);
+        Coord(panel.0 * pw + local.0, panel.1 * ph + local.1)
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Returns the color of every LED of a matrix of this grid's extents,
+    /// in strip order, as given by `color` for each cell.
+    ///
+    pub fn leds<'a, C>(
+        &'a self,
+        layout: LedLayout,
+        color: impl Fn(Coord, Cell) -> C + 'a,
+    ) -> impl Iterator<Item = C> + 'a {
+        (0..W * H).map(move |index| {
+            let coord = layout.coord(index, Coord(W, H));
+            color(coord, self[coord])
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn serpentine() {
+        let layout = LedLayout::single(Coord(3, 2), Wiring::Serpentine);
+        assert_eq!(layout.index(Coord(0, 1), Coord(3, 2)), 5);
+        assert_eq!(layout.coord(3, Coord(3, 2)), Coord(2, 1));
+
+        let mut grid = Grid::<3, 2>::new();
+        grid[Coord(2, 1)] = Cell::Alive;
+        let leds = grid.leds(layout, |_, cell| cell as u8);
+        assert!(leds.eq([0, 0, 0, 1, 0, 0]));
+    }
+
+    #[test]
+    fn tiled() {
+        // a 2 by 2 wall of 4 by 4 panels, chained in a serpentine.
+        let layout = LedLayout {
+            panel: Coord(4, 4),
+            wiring: Wiring::RowMajor,
+            chain: Wiring::Serpentine,
+        };
+        let extents = Coord(8, 8);
+
+        assert_eq!(layout.index(Coord(5, 1), extents), 16 + 5);
+        assert_eq!(layout.index(Coord(0, 4), extents), 48);
+        for index in 0..64 {
+            assert_eq!(layout.index(layout.coord(index, extents), extents), index);
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//!
+//! Rendering grids into RGBA pixel buffers.
+//!
+//! See [`to_rgba()`].
+//!
+
+use super::color::ColorPolicy;
+use crate::{grid::Grid, math::Coord};
+
+///
+/// Render `grid` into `buf` with one opaque RGBA pixel of 4 bytes per cell,
+/// in row-major order, colored by `colors`. Returns the rendered prefix of
+/// `buf`.
+///
+/// This is the layout of HTML canvas `ImageData` and most software
+/// framebuffers.
+///
+/// # Panics
+///
+/// Panics if `buf` is shorter than 4 bytes per cell.
+///
+pub fn to_rgba<'b, const W: usize, const H: usize>(
+    grid: &Grid<W, H>,
+    buf: &'b mut [u8],
+    colors: impl ColorPolicy,
+) -> &'b mut [u8] {
+    let len = W * H * 4;
+    assert!(buf.len() >= len, "buffer too small, {len} bytes needed");
+
+    let buf = &mut buf[..len];
+    for (i, pixel) in buf.chunks_exact_mut(4).enumerate() {
+        let coord = Coord(i % W, i / W);
+        let [r, g, b] = colors.color(coord, grid[coord]);
+        pixel.copy_from_slice(&[r, g, b, 0xff]);
+    }
+
+    buf
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{cell::Cell, io::Palette};
+
+    #[test]
+    fn to_rgba() {
+        let mut grid = Grid::<2, 1>::new();
+        grid[Coord(1, 0)] = Cell::Alive;
+
+        let mut buf = [0; 12];
+        let rgba = super::to_rgba(&grid, &mut buf, Palette::default());
+        assert_eq!(rgba, [0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0xff]);
+    }
+}
@@ -0,0 +1,202 @@
+//!
+//! Pixel graphics for terminals supporting Sixel, such as xterm and foot.
+//!
+//! See [`Grid::sixel()`], and `SixelRenderer` with the `std` feature.
+//!
+
+use core::fmt::{self, Write};
+
+use super::Viewport;
+use crate::{cell::Cell, grid::Grid, io::Palette, math::Coord};
+
+///
+/// A [`Grid`] displayed as a Sixel image, with each cell drawn as a square
+/// of `scale` pixels.
+///
+/// Obtained by calling [`Grid::sixel()`]. The image is drawn at the cursor,
+/// which is left below it.
+///
+#[derive(Clone, Copy, Debug)]
+pub struct Sixel<'a, const W: usize, const H: usize> {
+    grid: &'a Grid<W, H>,
+    viewport: Viewport,
+    scale: usize,
+    palette: Palette,
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Returns a wrapper displaying this grid as a Sixel image, with cells
+    /// of 4 pixels in the default palette.
+    ///
+    #[must_use]
+    pub fn sixel(&self) -> Sixel<'_, W, H> {
+        Sixel {
+            grid: self,
+            viewport: Viewport::from(self),
+            scale: 4,
+            palette: Palette::default(),
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Sixel<'_, W, H> {
+    /// Only displays the cells inside `viewport`.
+    #[must_use]
+    pub fn viewport(self, viewport: Viewport) -> Self {
+        Self { viewport, ..self }
+    }
+
+    /// Draws each cell as a square of `scale` pixels, which must be non-zero.
+    #[must_use]
+    pub fn scale(self, scale: usize) -> Self {
+        Self { scale, ..self }
+    }
+
+    /// Colors cells according to `palette`.
+    #[must_use]
+    pub fn palette(self, palette: Palette) -> Self {
+        Self { palette, ..self }
+    }
+}
+
+impl<const W: usize, const H: usize> fmt::Display for Sixel<'_, W, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scale = self.scale.max(1);
+        let Coord(width, height) = self.viewport.extents;
+        let (width, height) = (width * scale, height * scale);
+        let pixel = |x: usize, y: usize| self.viewport.get(self.grid, Coord(x / scale, y / scale));
+
+        write!(f, "\x1bPq\"1;1;{width};{height}")?;
+        for (register, color) in [self.palette.dead, self.palette.alive].iter().enumerate() {
+            let [r, g, b] = color.map(|channel| (u16::from(channel) * 100 + 127) / 255);
+            write!(f, "#{register};2;{r};{g};{b}")?;
+        }
+
+        for band in (0..height).step_by(6) {
+            for (register, cell) in [Cell::Dead, Cell::Alive].into_iter().enumerate() {
+                if register > 0 {
+                    f.write_char('$')?;
+                }
+                write!(f, "#{register}")?;
+
+                let mut run = None;
+                for x in 0..width {
+                    let bits = (0..6)
+                        .filter(|dy| band + dy < height && pixel(x, band + dy) == cell)
+                        .fold(0, |bits, dy| bits | 1 << dy);
+                    let sixel = char::from(63 + bits);
+
+                    run = match run {
+                        Some((prev, len)) if prev == sixel => Some((prev, len + 1)),
+                        Some(prev) => {
+                            write_run(f, prev)?;
+                            Some((sixel, 1))
+                        }
+                        None => Some((sixel, 1)),
+                    };
+                }
+                if let Some(run) = run {
+                    write_run(f, run)?;
+                }
+            }
+            f.write_char('-')?;
+        }
+
+        f.write_str("\x1b\\")
+    }
+}
+
+/// Writes a run of sixels, using a repeat introducer for long runs.
+fn write_run(f: &mut fmt::Formatter<'_>, (sixel, len): (char, usize)) -> fmt::Result {
+    if len > 3 {
+        write!(f, "!{len}{sixel}")
+    } else {
+        (0..len).try_for_each(|_| f.write_char(sixel))
+    }
+}
+
+#[cfg(feature = "std")]
+pub use renderer::SixelRenderer;
+
+#[cfg(feature = "std")]
+mod renderer {
+    use std::io::{self, Write};
+
+    use super::super::{Renderer, Viewport};
+    use crate::{grid::Grid, io::Palette};
+
+    ///
+    /// A [`Renderer`] writing grids as Sixel images to a terminal, or any
+    /// other [`Write`].
+    ///
+    /// Each render starts with moving the cursor to the top left corner, so
+    /// that frames overwrite each other.
+    ///
+    #[derive(Clone, Copy, Debug)]
+    pub struct SixelRenderer<T> {
+        pub writer: T,
+        pub scale: usize,
+        pub palette: Palette,
+    }
+
+    impl<T: Write> SixelRenderer<T> {
+        ///
+        /// Construct a new [`SixelRenderer`] writing to `writer`, with cells
+        /// of 4 pixels in the default palette.
+        ///
+        #[must_use]
+        pub fn new(writer: T) -> Self {
+            Self {
+                writer,
+                scale: 4,
+                palette: Palette::default(),
+            }
+        }
+    }
+
+    impl<const W: usize, const H: usize, T: Write> Renderer<W, H> for SixelRenderer<T> {
+        type Error = io::Error;
+
+        fn render(&mut self, grid: &Grid<W, H>, viewport: &Viewport) -> io::Result<()> {
+            let sixel = grid
+                .sixel()
+                .viewport(*viewport)
+                .scale(self.scale)
+                .palette(self.palette);
+            write!(self.writer, "\x1b[H{sixel}")?;
+            self.writer.flush()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use std::format;
+
+    use super::*;
+
+    #[test]
+    fn sixel() {
+        let mut grid = Grid::<1, 1>::new();
+        grid[Coord(0, 0)] = Cell::Alive;
+        assert_eq!(
+            format!("{}", grid.sixel().scale(1)),
+            "\x1bPq\"1;1;1;1#0;2;100;100;100#1;2;0;0;0#0?$#1@-\x1b\\"
+        );
+
+        let mut grid = Grid::<2, 2>::new();
+        grid[Coord(0, 0)] = Cell::Alive;
+        let palette = Palette {
+            alive: [255, 0, 0],
+            dead: [0, 0, 0],
+        };
+        let viewport = Viewport::new(Coord(0, 0), Coord(2, 1));
+        assert_eq!(
+            format!("{}", grid.sixel().viewport(viewport).palette(palette)),
+            "\x1bPq\"1;1;8;4#0;2;0;0;0#1;2;100;0;0#0!4?!4N$#1!4N!4?-\x1b\\"
+        );
+    }
+}
@@ -0,0 +1,691 @@
+//!
+//! Rules for stepping grids.
+//!
+//! See:
+//! - [`Rule`] for rules based on live neighbor counts, such as [`Life`].
+//! - [`LifeLike`] for rules given in B/S notation.
+//! - [`Topology`] for stepping grids on a torus.
+//! - [`competitive`] for several players competing for territory.
+//! - [`continuous`] for cells with continuous states in fixed point.
+//! - [`convolution`] for rules based on weighted sums over any neighborhood.
+//! - [`coupled`] for several grids stepped together.
+//! - [`fading`] for cells leaving trails as they die.
+//! - `lenia` for Lenia on continuous cells, with the `alloc` feature.
+//! - [`lookup`] for fast stepping with precomputed neighborhoods.
+//! - [`margolus`] for reversible block rules.
+//! - [`smooth_life`] for SmoothLife on continuous cells.
+//! - `table` for Golly rule tables, with the `alloc` feature.
+//! - `tree` for Golly rule trees, with the `alloc` feature.
+//! - [`weighted`] for rules with a weight for each neighbor.
+//!
+
+use core::fmt;
+
+use crate::{
+    cell::{Cell, CellState},
+    grid::Grid,
+    math::Coord,
+    sim::StepStats,
+};
+
+#[cfg(feature = "alloc")]
+use crate::cell::State;
+
+pub mod competitive;
+pub mod continuous;
+pub mod convolution;
+pub mod coupled;
+pub mod fading;
+#[cfg(feature = "alloc")]
+pub mod lenia;
+pub mod life_like;
+pub mod lookup;
+pub mod margolus;
+pub mod smooth_life;
+#[cfg(feature = "alloc")]
+pub mod table;
+#[cfg(feature = "alloc")]
+pub mod tree;
+pub mod weighted;
+
+pub use competitive::{Competitive, OwnedCell};
+pub use fading::{Fading, FadingCell};
+pub use life_like::LifeLike;
+pub use lookup::Lookup;
+pub use smooth_life::SmoothLife;
+pub use weighted::Weighted;
+
+///
+/// A rule for cells based on their number of live neighbors in the Moore
+/// neighborhood.
+///
+/// Rules are for two-state [`Cell`]s by default, but can be for any
+/// [`CellState`].
+///
+pub trait Rule<C = Cell> {
+    /// Calculate the next state of `cell`, given its number of live neighbors.
+    fn next(&self, cell: C, alive_neighbors: usize) -> C;
+}
+
+impl<C, R: Rule<C> + ?Sized> Rule<C> for &R {
+    fn next(&self, cell: C, alive_neighbors: usize) -> C {
+        (**self).next(cell, alive_neighbors)
+    }
+}
+
+///
+/// A rule for cells based on which of their neighbors in the Moore
+/// neighborhood are alive, such as "a cell is born if its north neighbor is
+/// alive".
+///
+/// Every [`Rule`] is an [`AnisotropicRule`] which ignores the directions of
+/// the live neighbors.
+///
+pub trait AnisotropicRule<C = Cell> {
+    ///
+    /// Calculate the next state of `cell`, given its live neighbors as a
+    /// bitmask, where bit `i` is set if the neighbor at
+    /// [`Neighborhood::Moore`]'s `i`th offset (N, NE, E, SE, S, SW, W, NW) is
+    /// alive.
+    ///
+    fn next_masked(&self, cell: C, neighbors: u8) -> C;
+}
+
+impl<C, R: Rule<C> + ?Sized> AnisotropicRule<C> for R {
+    fn next_masked(&self, cell: C, neighbors: u8) -> C {
+        self.next(cell, neighbors.count_ones() as usize)
+    }
+}
+
+///
+/// Conway's Game of Life, or B3/S23.
+///
+/// Rules are in accordance to
+/// [the Wiki page](https://www.wikiwand.com/en/Conway's_Game_of_Life).
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Life;
+
+impl Rule for Life {
+    fn next(&self, cell: Cell, alive_neighbors: usize) -> Cell {
+        cell.next(alive_neighbors)
+    }
+}
+
+impl<const W: usize, const H: usize, C: CellState> Grid<W, H, C> {
+    /// Calculates the next generation of this grid according to `rule`.
+    #[must_use]
+    pub fn step_with(&self, rule: &impl Rule<C>) -> Self {
+        Self {
+            cells: core::array::from_fn(|y| {
+                core::array::from_fn(|x| self.state_next_with(rule, Coord(x, y)))
+            }),
+            generation: self.generation + 1,
+        }
+    }
+
+    ///
+    /// Calculates the next generation of this grid according to `rule` into
+    /// `next`, overwriting it.
+    ///
+    /// No grid is returned by value, so two grids in static memory can be
+    /// stepped back and forth without a grid-sized copy on the stack, which
+    /// may not fit on small targets.
+    ///
+    pub fn step_with_into(&self, rule: &impl Rule<C>, next: &mut Self) {
+        for (y, row) in next.cells.iter_mut().enumerate() {
+            for (x, cell) in row.iter_mut().enumerate() {
+                *cell = self.state_next_with(rule, Coord(x, y));
+            }
+        }
+        next.generation = self.generation + 1;
+    }
+
+    ///
+    /// Calculates the state of this grid `n` generations ahead according to
+    /// `rule` into `scratch`, overwriting it, without changing this grid.
+    ///
+    /// Generations are stepped back and forth between `scratch` and `spare`,
+    /// which is overwritten as well, so no other grids are made. This is
+    /// meant for previews, such as showing where an edited pattern is
+    /// headed, while the grid itself is left where it is.
+    ///
+    pub fn peek_with(&self, rule: &impl Rule<C>, n: u64, scratch: &mut Self, spare: &mut Self) {
+        if n == 0 {
+            scratch.clone_from(self);
+            return;
+        }
+
+        // start in whichever grid makes the last generation land in scratch
+        let (mut current, mut next) = match n % 2 {
+            1 => (scratch, spare),
+            _ => (spare, scratch),
+        };
+        self.step_with_into(rule, current);
+        for _ in 1..n {
+            current.step_with_into(rule, next);
+            core::mem::swap(&mut current, &mut next);
+        }
+    }
+
+    ///
+    /// Calculates the next generation of this grid according to `rule`, with
+    /// the edges of the grid behaving according to `topology`.
+    ///
+    #[must_use]
+    pub fn step_on(&self, rule: &impl Rule<C>, topology: Topology) -> Self {
+        match topology {
+            Topology::Bounded => self.step_with(rule),
+            Topology::Torus => Self {
+                cells: core::array::from_fn(|y| {
+                    core::array::from_fn(|x| {
+                        let coord = Coord(x, y);
+                        let alive_neighbors = Neighborhood::Moore
+                            .offsets()
+                            .iter()
+                            .filter_map(|&(dx, dy)| topology.offset(coord, dx, dy, Coord(W, H)))
+                            .filter(|&neighbor| self[neighbor].is_alive())
+                            .count();
+                        rule.next(self[coord], alive_neighbors)
+                    })
+                }),
+                generation: self.generation + 1,
+            },
+        }
+    }
+
+    ///
+    /// Calculates the next generation of this grid according to the
+    /// anisotropic `rule`, with the edges of the grid behaving according to
+    /// `topology`.
+    ///
+    #[must_use]
+    pub fn step_anisotropic(&self, rule: &impl AnisotropicRule<C>, topology: Topology) -> Self {
+        Self {
+            cells: core::array::from_fn(|y| {
+                core::array::from_fn(|x| {
+                    let coord = Coord(x, y);
+                    rule.next_masked(self[coord], self.neighbor_mask(coord, topology))
+                })
+            }),
+            generation: self.generation + 1,
+        }
+    }
+
+    ///
+    /// Returns the live neighbors of the cell at `coord` as a bitmask, as
+    /// passed to [`AnisotropicRule::next_masked()`].
+    ///
+    #[must_use]
+    pub fn neighbor_mask(&self, coord: Coord, topology: Topology) -> u8 {
+        Neighborhood::Moore
+            .offsets()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &(dx, dy))| {
+                let neighbor = topology.offset(coord, dx, dy, Coord(W, H))?;
+                self[neighbor].is_alive().then_some(1 << i)
+            })
+            .fold(0, |mask, bit| mask | bit)
+    }
+
+    ///
+    /// Returns the cell at `(x, y)`, which may lie outside of the grid, with
+    /// the edges of the grid behaving according to `topology`.
+    ///
+    /// This lets custom rules and renderers read the border around the grid
+    /// without handling its edges themselves: on a bounded grid, cells
+    /// outside of it are [`CellState::DEAD`], and on a torus, they wrap
+    /// around to the other side.
+    ///
+    #[must_use]
+    pub fn cell_or_boundary(&self, x: isize, y: isize, topology: Topology) -> C {
+        topology
+            .offset(Coord(0, 0), x, y, Coord(W, H))
+            .map_or(C::DEAD, |coord| self[coord])
+    }
+
+    /// Calculate the state of this cell in the next generation according to `rule`.
+    pub(crate) fn state_next_with(&self, rule: &impl Rule<C>, coord: Coord) -> C {
+        rule.next(
+            self[coord],
+            coord
+                .neighbors(Coord(W, H))
+                .filter(|&coord| self[coord].is_alive())
+                .count(),
+        )
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Calculates the next generation of this grid according to `rule`,
+    /// along with statistics about the step.
+    ///
+    /// The statistics are counted while stepping, which is much cheaper than
+    /// comparing the grids afterwards with [`StepStats::between()`].
+    ///
+    #[must_use]
+    pub fn step_with_stats(&self, rule: &impl Rule) -> (Self, StepStats) {
+        let mut stats = StepStats::default();
+        let next = self.step_with_layer(rule, &mut stats);
+        (next, stats)
+    }
+}
+
+///
+/// How the edges of a grid behave when stepping.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum Topology {
+    /// Cells outside of the grid are always dead.
+    #[default]
+    Bounded,
+    ///
+    /// The grid wraps around at its edges, so that the left edge neighbors
+    /// the right edge, and the top edge neighbors the bottom edge.
+    ///
+    /// Grids narrower or shorter than 3 cells count some neighbors more
+    /// than once.
+    ///
+    Torus,
+}
+
+impl Topology {
+    ///
+    /// Returns `coord` offset by `(dx, dy)` on a grid of `extents`, wrapping
+    /// around on a torus, or `None` if it is outside of a bounded grid.
+    ///
+    pub(crate) fn offset(
+        self,
+        coord: Coord,
+        dx: isize,
+        dy: isize,
+        extents: Coord,
+    ) -> Option<Coord> {
+        let (Coord(x, y), Coord(w, h)) = (coord, extents);
+        let (x, y) = (x as isize + dx, y as isize + dy);
+        match self {
+            Self::Bounded => ((0..w as isize).contains(&x) && (0..h as isize).contains(&y))
+                .then_some(Coord(x as usize, y as usize)),
+            Self::Torus => Some(Coord(
+                x.rem_euclid(w as isize) as usize,
+                y.rem_euclid(h as isize) as usize,
+            )),
+        }
+    }
+}
+
+///
+/// The neighborhood of a table-driven rule.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Neighborhood {
+    /// The 8 surrounding cells, in the order N, NE, E, SE, S, SW, W, NW.
+    #[default]
+    Moore,
+    /// The 4 orthogonally adjacent cells, in the order N, E, S, W.
+    VonNeumann,
+}
+
+impl Neighborhood {
+    /// Returns the number of neighbors in this neighborhood.
+    #[must_use]
+    pub const fn count(self) -> usize {
+        match self {
+            Self::Moore => 8,
+            Self::VonNeumann => 4,
+        }
+    }
+
+    /// Returns the offsets of each neighbor, in order.
+    #[must_use]
+    pub const fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Self::Moore => &[
+                (0, -1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+                (0, 1),
+                (-1, 1),
+                (-1, 0),
+                (-1, -1),
+            ],
+            Self::VonNeumann => &[(0, -1), (1, 0), (0, 1), (-1, 0)],
+        }
+    }
+}
+
+///
+/// An error encountered while parsing a rule.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    /// The 1-based line number the error was encountered on.
+    pub line: usize,
+    pub kind: ParseErrorKind,
+}
+
+///
+/// The kind of a [`ParseError`].
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParseErrorKind {
+    /// The rule section (such as `@TABLE`) was not found.
+    MissingSection,
+    /// The number of states was not specified, or is out of range.
+    InvalidStates,
+    /// The neighborhood is unknown or unsupported.
+    UnsupportedNeighborhood,
+    /// The symmetries are unknown or unsupported for the neighborhood.
+    UnsupportedSymmetries,
+    /// A state is not a number, or is out of range.
+    InvalidState,
+    /// A variable was used before it was defined.
+    UndefinedVariable,
+    /// A line has the wrong number of fields.
+    WrongArity,
+    /// A rule tree node is malformed, or refers to a missing node.
+    InvalidNode,
+    /// A line could not be parsed.
+    Syntax,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self.kind {
+            ParseErrorKind::MissingSection => "missing rule section",
+            ParseErrorKind::InvalidStates => "missing or invalid number of states",
+            ParseErrorKind::UnsupportedNeighborhood => "unsupported neighborhood",
+            ParseErrorKind::UnsupportedSymmetries => "unsupported symmetries",
+            ParseErrorKind::InvalidState => "invalid state",
+            ParseErrorKind::UndefinedVariable => "undefined variable",
+            ParseErrorKind::WrongArity => "wrong number of fields",
+            ParseErrorKind::InvalidNode => "invalid rule tree node",
+            ParseErrorKind::Syntax => "syntax error",
+        };
+
+        write!(f, "line {}: {message}", self.line)
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+///
+/// Returns the lines of a section (such as `@TABLE`) of a Golly `.rule` file,
+/// along with their 1-based line numbers.
+///
+/// If the file has no sections at all, it is treated as a bare section, as
+/// in the older `.table` and `.tree` files.
+///
+#[cfg(feature = "alloc")]
+fn section<'a>(
+    src: &'a str,
+    name: &str,
+) -> Result<impl Iterator<Item = (usize, &'a str)>, ParseError> {
+    let lines = || src.lines().enumerate().map(|(i, line)| (i + 1, line));
+    let is_header = |line: &str| line.trim_start().starts_with('@');
+
+    // the line number of the section header, or 0 for a bare section.
+    let header = if lines().any(|(_, line)| is_header(line)) {
+        lines()
+            .find(|(_, line)| line.split_whitespace().next() == Some(name))
+            .ok_or(ParseError {
+                line: 0,
+                kind: ParseErrorKind::MissingSection,
+            })?
+            .0
+    } else {
+        0
+    };
+
+    Ok(lines()
+        .skip(header)
+        .take_while(move |(_, line)| header == 0 || !is_header(line))
+        .map(|(i, line)| (i, line.split('#').next().unwrap_or_default().trim()))
+        .filter(|(_, line)| !line.is_empty()))
+}
+
+///
+/// Calculate the next generation of a grid of states, given a function of
+/// each cell's state and those of its neighbors.
+///
+/// Cells outside of the grid are in state 0.
+///
+#[cfg(feature = "alloc")]
+fn step_states<const W: usize, const H: usize>(
+    neighborhood: Neighborhood,
+    states: &[[u8; W]; H],
+    next_state: impl Fn(u8, &[u8]) -> u8,
+) -> [[u8; W]; H] {
+    let offsets = neighborhood.offsets();
+    let mut neighbors = [0; 8];
+
+    let mut next = [[0; W]; H];
+    for y in 0..H {
+        for x in 0..W {
+            for (neighbor, &(dx, dy)) in neighbors.iter_mut().zip(offsets) {
+                *neighbor = x
+                    .checked_add_signed(dx)
+                    .zip(y.checked_add_signed(dy))
+                    .filter(|&(x, y)| x < W && y < H)
+                    .map_or(0, |(x, y)| states[y][x]);
+            }
+            next[y][x] = next_state(states[y][x], &neighbors[..offsets.len()]);
+        }
+    }
+
+    next
+}
+
+#[cfg(feature = "alloc")]
+impl<const W: usize, const H: usize> Grid<W, H, State> {
+    /// Calculate the next generation of this grid by stepping its states.
+    fn step_states(&self, step: impl FnOnce(&[[u8; W]; H]) -> [[u8; W]; H]) -> Self {
+        let states = self.cells.map(|row| row.map(|State(state)| state));
+        Self {
+            cells: step(&states).map(|row| row.map(State)),
+            generation: self.generation + 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// HighLife, or B36/S23.
+    struct HighLife;
+
+    impl Rule for HighLife {
+        fn next(&self, cell: Cell, alive_neighbors: usize) -> Cell {
+            match (cell, alive_neighbors) {
+                (Cell::Dead, 6) => Cell::Alive,
+                _ => Life.next(cell, alive_neighbors),
+            }
+        }
+    }
+
+    #[test]
+    fn step_with() {
+        let mut grid = Grid::<5, 5>::new();
+        for coord in [
+            Coord(1, 1),
+            Coord(2, 1),
+            Coord(3, 1),
+            Coord(1, 3),
+            Coord(2, 3),
+            Coord(3, 3),
+        ] {
+            grid[coord] = Cell::Alive;
+        }
+
+        assert_eq!(grid.step_with(&Life).cells, grid.step().cells);
+        assert_eq!(grid[Coord(2, 2)], Cell::Dead);
+        assert_eq!(grid.step_with(&HighLife)[Coord(2, 2)], Cell::Alive);
+        assert_eq!(grid.step_with(&Life)[Coord(2, 2)], Cell::Dead);
+
+        let mut next = Grid::new();
+        grid.step_with_into(&HighLife, &mut next);
+        assert_eq!(next.cells, grid.step_with(&HighLife).cells);
+        assert_eq!(next.generation, 1);
+    }
+
+    #[test]
+    fn step_with_stats() {
+        let mut grid = Grid::<6, 6>::new();
+        for coord in [
+            Coord(1, 0),
+            Coord(2, 1),
+            Coord(0, 2),
+            Coord(1, 2),
+            Coord(2, 2),
+            Coord(5, 5),
+        ] {
+            grid[coord] = Cell::Alive;
+        }
+
+        for _ in 0..4 {
+            let (next, stats) = grid.step_with_stats(&Life);
+            assert_eq!(next.cells, grid.step().cells);
+            assert_eq!(stats, StepStats::between(&grid, &next));
+            grid = next;
+        }
+    }
+
+    #[test]
+    fn cell_state() {
+        /// A cell counting the generations it has been alive for.
+        #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+        struct Aged(Option<u8>);
+
+        impl CellState for Aged {
+            const DEAD: Self = Self(None);
+
+            fn is_alive(self) -> bool {
+                self.0.is_some()
+            }
+        }
+
+        impl Rule<Aged> for Life {
+            fn next(&self, cell: Aged, alive_neighbors: usize) -> Aged {
+                match Cell::from(cell.is_alive()).next(alive_neighbors) {
+                    Cell::Alive => Aged(Some(cell.0.map_or(0, |age| age + 1))),
+                    Cell::Dead => Aged(None),
+                }
+            }
+        }
+
+        let mut grid = Grid::<3, 3, Aged>::new();
+        for x in 0..3 {
+            grid[Coord(x, 1)] = Aged(Some(0));
+        }
+
+        let grid = grid.step_with(&Life).step_with(&Life);
+        assert_eq!(grid.population(), 3);
+        assert_eq!(grid[Coord(0, 1)], Aged(Some(0)));
+        assert_eq!(grid[Coord(1, 1)], Aged(Some(2)));
+    }
+
+    #[test]
+    fn step_anisotropic() {
+        /// A cell is born or survives only if its north neighbor is alive.
+        struct North;
+
+        impl AnisotropicRule for North {
+            fn next_masked(&self, _: Cell, neighbors: u8) -> Cell {
+                Cell::from(neighbors & 1 != 0)
+            }
+        }
+
+        let mut grid = Grid::<3, 3>::new();
+        grid[Coord(1, 0)] = Cell::Alive;
+        grid[Coord(2, 1)] = Cell::Alive;
+        assert_eq!(
+            grid.neighbor_mask(Coord(1, 1), Topology::Bounded),
+            0b0000_0101
+        );
+        assert_eq!(
+            grid.neighbor_mask(Coord(1, 2), Topology::Torus),
+            0b0001_0010
+        );
+
+        let next = grid.step_anisotropic(&North, Topology::Bounded);
+        assert_eq!(next.population(), 2);
+        assert_eq!(next[Coord(1, 1)], Cell::Alive);
+        assert_eq!(next[Coord(2, 2)], Cell::Alive);
+
+        let next = grid.step_anisotropic(&North, Topology::Torus);
+        assert_eq!(next.population(), 2);
+        assert_eq!(next[Coord(1, 0)], Cell::Dead);
+
+        for topology in [Topology::Bounded, Topology::Torus] {
+            assert_eq!(
+                grid.step_anisotropic(&Life, topology).cells,
+                grid.step_on(&Life, topology).cells
+            );
+        }
+    }
+
+    #[test]
+    fn peek_with() {
+        let mut grid = Grid::<8, 8>::new();
+        for coord in [
+            Coord(1, 0),
+            Coord(2, 1),
+            Coord(0, 2),
+            Coord(1, 2),
+            Coord(2, 2),
+        ] {
+            grid[coord] = Cell::Alive;
+        }
+        grid.generation = 5;
+        let before = grid.clone();
+
+        let (mut scratch, mut spare) = (Grid::new(), Grid::new());
+        grid.peek_with(&Life, 4, &mut scratch, &mut spare);
+        assert_eq!((grid.cells, grid.generation), (before.cells, 5));
+        assert_eq!(scratch.generation, 9);
+        assert_eq!(scratch.cells, grid.roll(1, 1).cells);
+
+        grid.peek(3, &mut scratch, &mut spare);
+        assert_eq!(scratch.generation, 8);
+        assert_eq!(scratch.cells, grid.step().step().step().cells);
+
+        grid.peek(0, &mut scratch, &mut spare);
+        assert_eq!((scratch.cells, scratch.generation), (grid.cells, 5));
+    }
+
+    #[test]
+    fn step_on() {
+        let mut grid = Grid::<5, 5>::new();
+        for y in 1..4 {
+            grid[Coord(0, y)] = Cell::Alive;
+        }
+
+        let bounded = grid.step_on(&Life, Topology::Bounded);
+        assert_eq!(bounded.cells, grid.step().cells);
+        assert_eq!(bounded.population(), 2);
+
+        let torus = grid.step_on(&Life, Topology::Torus);
+        assert_eq!(torus.population(), 3);
+        assert_eq!(torus[Coord(4, 2)], Cell::Alive);
+        assert_eq!(torus.step_on(&Life, Topology::Torus).cells, grid.cells);
+    }
+
+    #[test]
+    fn cell_or_boundary() {
+        let mut grid = Grid::<3, 2>::new();
+        grid[Coord(2, 0)] = Cell::Alive;
+        grid[Coord(0, 1)] = Cell::Alive;
+
+        assert_eq!(grid.cell_or_boundary(2, 0, Topology::Bounded), Cell::Alive);
+        assert_eq!(grid.cell_or_boundary(-1, 0, Topology::Bounded), Cell::Dead);
+        assert_eq!(grid.cell_or_boundary(0, 2, Topology::Bounded), Cell::Dead);
+
+        assert_eq!(grid.cell_or_boundary(-1, 0, Topology::Torus), Cell::Alive);
+        assert_eq!(grid.cell_or_boundary(3, -1, Topology::Torus), Cell::Alive);
+        assert_eq!(grid.cell_or_boundary(-1, -1, Topology::Torus), Cell::Dead);
+    }
+}
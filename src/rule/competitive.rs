@@ -0,0 +1,241 @@
+//!
+//! Competitive Life between several players, whose live cells each have an
+//! owner, as in [Immigration](https://conwaylife.com/wiki/Immigration).
+//!
+//! See [`OwnedCell`] and [`Competitive`].
+//!
+
+use core::cmp::Reverse;
+
+use super::{Neighborhood, Rule, Topology};
+use crate::{
+    cell::{Cell, CellState},
+    grid::Grid,
+    math::Coord,
+};
+
+///
+/// A cell which is either dead, or alive and owned by one of up to 255
+/// players, numbered from 0.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct OwnedCell(u8);
+
+impl OwnedCell {
+    ///
+    /// Returns a live cell owned by `player`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `player` is 255.
+    ///
+    #[must_use]
+    pub const fn alive(player: u8) -> Self {
+        assert!(player < u8::MAX, "there are at most 255 players");
+        Self(player + 1)
+    }
+
+    /// Returns the player owning this cell, or [`None`] if it is dead.
+    #[must_use]
+    pub const fn owner(self) -> Option<u8> {
+        self.0.checked_sub(1)
+    }
+
+    /// Returns whether this cell is alive or dead, ignoring its owner.
+    #[must_use]
+    pub const fn cell(self) -> Cell {
+        if self.0 == 0 {
+            Cell::Dead
+        } else {
+            Cell::Alive
+        }
+    }
+}
+
+impl CellState for OwnedCell {
+    const DEAD: Self = Self(0);
+
+    fn is_alive(self) -> bool {
+        self.0 != 0
+    }
+}
+
+///
+/// A two-state rule played by `P` players, with the edges of the grid
+/// behaving according to `topology`.
+///
+/// Whether cells live, die or are born only depends on `rule`, as if
+/// ownership did not exist. Surviving cells keep their owner, and born
+/// cells go to the player owning the most of their live neighbors, with
+/// ties going to the lowest-numbered player.
+///
+/// Cells owned by players `P` or higher are alive, but only win births
+/// none of players `0` to `P - 1` take part in, and cells born without any
+/// live neighbors go to player 0. `P` must be at least 1, which is checked
+/// at compile time.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Competitive<R, const P: usize> {
+    pub rule: R,
+    pub topology: Topology,
+}
+
+impl<R: Rule, const P: usize> Competitive<R, P> {
+    /// Construct a new [`Competitive`] rule from a two-state rule.
+    #[must_use]
+    pub const fn new(rule: R, topology: Topology) -> Self {
+        const { assert!(P > 0, "there must be at least one player") };
+        Self { rule, topology }
+    }
+
+    /// Calculates the next generation of `grid`.
+    #[must_use]
+    pub fn step<const W: usize, const H: usize>(
+        &self,
+        grid: &Grid<W, H, OwnedCell>,
+    ) -> Grid<W, H, OwnedCell> {
+        Grid {
+            cells: core::array::from_fn(|y| {
+                core::array::from_fn(|x| self.state_next(grid, Coord(x, y)))
+            }),
+            generation: grid.generation + 1,
+        }
+    }
+
+    ///
+    /// Calculates the next generation of `grid` like [`Competitive::step()`],
+    /// along with each player's territory in it.
+    ///
+    #[must_use]
+    pub fn step_scored<const W: usize, const H: usize>(
+        &self,
+        grid: &Grid<W, H, OwnedCell>,
+    ) -> (Grid<W, H, OwnedCell>, [usize; P]) {
+        let next = self.step(grid);
+        let territory = next.territory();
+        (next, territory)
+    }
+
+    /// Calculates the next state of the cell at `coord` in `grid`.
+    fn state_next<const W: usize, const H: usize>(
+        &self,
+        grid: &Grid<W, H, OwnedCell>,
+        coord: Coord,
+    ) -> OwnedCell {
+        const { assert!(P > 0, "there must be at least one player") };
+        let mut parents = [0; 8];
+        let mut alive_neighbors = 0;
+        for &(dx, dy) in Neighborhood::Moore.offsets() {
+            let Some(neighbor) = self.topology.offset(coord, dx, dy, Coord(W, H)) else {
+                continue;
+            };
+            if let Some(owner) = grid[neighbor].owner() {
+                parents[alive_neighbors] = owner;
+                alive_neighbors += 1;
+            }
+        }
+
+        let cell = grid[coord];
+        match (cell.cell(), self.rule.next(cell.cell(), alive_neighbors)) {
+            (_, Cell::Dead) => OwnedCell::DEAD,
+            (Cell::Alive, Cell::Alive) => cell,
+            (Cell::Dead, Cell::Alive) => {
+                let parents = &parents[..alive_neighbors];
+                // players below P first, then the most parents, then the
+                // lowest-numbered player
+                let player = parents
+                    .iter()
+                    .copied()
+                    .max_by_key(|&player| {
+                        let count = parents.iter().filter(|&&owner| owner == player).count();
+                        (usize::from(player) < P, count, Reverse(player))
+                    })
+                    .unwrap_or(0);
+                OwnedCell::alive(player)
+            }
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H, OwnedCell> {
+    ///
+    /// Returns the territory of each of `P` players, which is the number of
+    /// live cells they own.
+    ///
+    /// Cells owned by players `P` or higher are not counted.
+    ///
+    #[must_use]
+    pub fn territory<const P: usize>(&self) -> [usize; P] {
+        let mut territory = [0; P];
+        for owner in self.cells.iter().flatten().filter_map(|cell| cell.owner()) {
+            if let Some(territory) = territory.get_mut(usize::from(owner)) {
+                *territory += 1;
+            }
+        }
+        territory
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rule::{Life, LifeLike};
+
+    #[test]
+    fn step() {
+        let [red, blue] = [0, 1].map(OwnedCell::alive);
+        assert_eq!(blue.owner(), Some(1));
+        assert_eq!(OwnedCell::DEAD.owner(), None);
+
+        // a blinker with two red cells and one blue cell
+        let mut grid = Grid::<5, 5, OwnedCell>::new();
+        grid[Coord(1, 2)] = red;
+        grid[Coord(2, 2)] = blue;
+        grid[Coord(3, 2)] = red;
+
+        let rule = Competitive::<_, 2>::new(Life, Topology::Bounded);
+        let (grid, territory) = rule.step_scored(&grid);
+        assert_eq!(grid.population(), 3);
+        assert_eq!(grid[Coord(2, 2)], blue);
+        assert_eq!(grid[Coord(2, 1)], red);
+        assert_eq!(grid[Coord(2, 3)], red);
+        assert_eq!(territory, [2, 1]);
+
+        let grid = rule.step(&grid);
+        assert_eq!(grid.generation, 2);
+        assert_eq!(grid[Coord(1, 2)], red);
+        assert_eq!(grid.territory(), [2, 1]);
+
+        // a born cell with one parent of each of three players
+        let mut grid = Grid::<3, 3, OwnedCell>::new();
+        grid[Coord(0, 0)] = OwnedCell::alive(2);
+        grid[Coord(2, 0)] = blue;
+        grid[Coord(0, 2)] = red;
+        let grid = Competitive::<_, 3>::new(Life, Topology::Torus).step(&grid);
+        assert_eq!(grid[Coord(1, 1)], red);
+        assert_eq!(grid.territory::<3>(), [7, 1, 1]);
+        assert_eq!(grid.territory::<2>(), [7, 1]);
+
+        // a born cell whose parents are all owned by players P or higher
+        let mut grid = Grid::<3, 3, OwnedCell>::new();
+        grid[Coord(0, 0)] = OwnedCell::alive(4);
+        grid[Coord(2, 0)] = OwnedCell::alive(3);
+        grid[Coord(0, 2)] = OwnedCell::alive(3);
+        let grid = Competitive::<_, 2>::new(Life, Topology::Bounded).step(&grid);
+        assert_eq!(grid[Coord(1, 1)], OwnedCell::alive(3));
+        assert_eq!(grid.territory::<2>(), [0, 0]);
+
+        // players P or higher lose births to any player below P
+        let mut grid = Grid::<3, 3, OwnedCell>::new();
+        grid[Coord(0, 0)] = OwnedCell::alive(3);
+        grid[Coord(2, 0)] = OwnedCell::alive(3);
+        grid[Coord(0, 2)] = blue;
+        let grid = Competitive::<_, 2>::new(Life, Topology::Bounded).step(&grid);
+        assert_eq!(grid[Coord(1, 1)], blue);
+
+        // a cell born without any live neighbors
+        let rule = "B0/S".parse::<LifeLike>().unwrap();
+        let grid = Competitive::<_, 2>::new(rule, Topology::Bounded).step(&Grid::<2, 2, _>::new());
+        assert_eq!(grid.territory(), [4, 0]);
+    }
+}
@@ -0,0 +1,230 @@
+//!
+//! Cells with continuous states in fixed point, as groundwork for
+//! [SmoothLife](https://arxiv.org/abs/1111.1567) and
+//! [Lenia](https://en.wikipedia.org/wiki/Lenia)-like automata on targets
+//! without floating point hardware.
+//!
+//! See [`Level`] and [`ContinuousRule`].
+//!
+
+use core::ops::{Add, Mul, Sub};
+
+use super::Topology;
+use crate::{
+    cell::{Cell, CellState},
+    grid::Grid,
+    math::Coord,
+};
+
+///
+/// A fixed-point value in `[0, 1]`, where 0 is `Level(0)` and 1 is
+/// `Level(u16::MAX)`.
+///
+/// Arithmetic saturates at 0 and 1, and multiplication rounds to the
+/// nearest level.
+///
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct Level(pub u16);
+
+impl Level {
+    pub const ZERO: Self = Self(0);
+    pub const HALF: Self = Self(0x8000);
+    pub const ONE: Self = Self(u16::MAX);
+
+    ///
+    /// Returns the level closest to `num / den`, saturating at 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `den` is 0.
+    ///
+    #[must_use]
+    pub const fn from_ratio(num: u64, den: u64) -> Self {
+        assert!(den > 0, "denominator is 0");
+
+        if num >= den {
+            Self::ONE
+        } else {
+            Self(((num * u16::MAX as u64 + den / 2) / den) as u16)
+        }
+    }
+
+    /// Returns the level closest to `value`, clamped to `[0, 1]`.
+    #[must_use]
+    pub fn from_f32(value: f32) -> Self {
+        Self((value.clamp(0.0, 1.0) * f32::from(u16::MAX) + 0.5) as u16)
+    }
+
+    /// Returns this level as an `f32` in `[0, 1]`.
+    #[must_use]
+    pub fn to_f32(self) -> f32 {
+        f32::from(self.0) / f32::from(u16::MAX)
+    }
+}
+
+impl Add for Level {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl Sub for Level {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl Mul for Level {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::from_ratio(
+            u64::from(self.0) * u64::from(rhs.0),
+            u64::from(u16::MAX).pow(2),
+        )
+    }
+}
+
+impl CellState for Level {
+    const DEAD: Self = Self::ZERO;
+
+    /// Returns whether this level is at least [`Level::HALF`].
+    fn is_alive(self) -> bool {
+        self >= Self::HALF
+    }
+}
+
+impl From<Cell> for Level {
+    fn from(cell: Cell) -> Self {
+        match cell {
+            Cell::Alive => Self::ONE,
+            Cell::Dead => Self::ZERO,
+        }
+    }
+}
+
+///
+/// A rule for continuous cells, based on the mean levels of a disk around
+/// each cell, and of the ring around that disk out to 3 times its radius,
+/// as in SmoothLife.
+///
+pub trait ContinuousRule {
+    /// Returns the radius of the inner disk, in cells.
+    fn radius(&self) -> usize;
+
+    ///
+    /// Calculate the next level of a cell, given the mean levels of its
+    /// inner disk, which includes the cell itself, and of its outer ring.
+    ///
+    fn next(&self, level: Level, inner: Level, outer: Level) -> Level;
+}
+
+impl<R: ContinuousRule + ?Sized> ContinuousRule for &R {
+    fn radius(&self) -> usize {
+        (**self).radius()
+    }
+
+    fn next(&self, level: Level, inner: Level, outer: Level) -> Level {
+        (**self).next(level, inner, outer)
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H, Level> {
+    ///
+    /// Calculates the next generation of this grid according to `rule`, with
+    /// the edges of the grid behaving according to `topology`.
+    ///
+    /// Each cell takes time proportional to the area of the outer ring.
+    ///
+    #[must_use]
+    pub fn step_continuous(&self, rule: &impl ContinuousRule, topology: Topology) -> Self {
+        let inner_radius = rule.radius() as isize;
+        let outer_radius = inner_radius * 3;
+
+        Self {
+            cells: core::array::from_fn(|y| {
+                core::array::from_fn(|x| {
+                    let ([mut inner, mut outer], [mut inner_len, mut outer_len]) =
+                        ([0u64; 2], [0u64; 2]);
+
+                    for dy in -outer_radius..=outer_radius {
+                        for dx in -outer_radius..=outer_radius {
+                            let distance = dx * dx + dy * dy;
+                            let (sum, len) = if distance <= inner_radius * inner_radius {
+                                (&mut inner, &mut inner_len)
+                            } else if distance <= outer_radius * outer_radius {
+                                (&mut outer, &mut outer_len)
+                            } else {
+                                continue;
+                            };
+
+                            *len += 1;
+                            if let Some(coord) = topology.offset(Coord(x, y), dx, dy, Coord(W, H)) {
+                                *sum += u64::from(self[coord].0);
+                            }
+                        }
+                    }
+
+                    let mean = |sum, len| Level::from_ratio(sum, len * u64::from(u16::MAX));
+                    rule.next(
+                        self[Coord(x, y)],
+                        mean(inner, inner_len),
+                        mean(outer, outer_len.max(1)),
+                    )
+                })
+            }),
+            generation: self.generation + 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Every cell takes the mean level of its outer ring.
+    struct Blur;
+
+    impl ContinuousRule for Blur {
+        fn radius(&self) -> usize {
+            1
+        }
+
+        fn next(&self, _: Level, _: Level, outer: Level) -> Level {
+            outer
+        }
+    }
+
+    #[test]
+    fn level() {
+        assert_eq!(Level::from_ratio(1, 2), Level::HALF);
+        assert_eq!(Level::from_ratio(3, 2), Level::ONE);
+        assert_eq!(Level::HALF * Level::HALF, Level(0x4000));
+        assert_eq!(Level::ONE * Level::HALF, Level::HALF);
+        assert_eq!(Level::HALF + Level::HALF, Level::ONE);
+        assert_eq!(Level::HALF - Level::ONE, Level::ZERO);
+        assert_eq!(Level::from_f32(0.25), Level::from_ratio(1, 4));
+        assert_eq!(Level::ONE.to_f32(), 1.0);
+        assert!(Level::HALF.is_alive() && !Level(0x7fff).is_alive());
+    }
+
+    #[test]
+    fn step_continuous() {
+        let mut grid = Grid::<8, 8, Level>::new();
+        for cell in grid.cells.iter_mut().flatten() {
+            *cell = Level::HALF;
+        }
+
+        let next = grid.step_continuous(&Blur, Topology::Torus);
+        assert!(next.cells.iter().flatten().all(|&cell| cell == Level::HALF));
+        assert_eq!(next.generation, 1);
+
+        let next = grid.step_continuous(&Blur, Topology::Bounded);
+        assert_eq!(next[Coord(4, 4)], Level::HALF);
+        assert!(next[Coord(0, 0)] < Level::HALF);
+    }
+}
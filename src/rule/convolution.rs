@@ -0,0 +1,190 @@
+//!
+//! Stepping grids by convolving them with a kernel, for rules based on
+//! weighted sums of cells over arbitrary neighborhoods.
+//!
+//! See [`Grid::convolve()`] and [`Grid::step_convolved()`].
+//!
+
+use core::ops::Add;
+
+use super::{continuous::Level, Neighborhood, Topology};
+use crate::{
+    cell::{Cell, CellState},
+    grid::Grid,
+    math::Coord,
+};
+
+///
+/// The weight of a kernel tap, which can be summed over the cells under the
+/// kernel.
+///
+/// Integer weights count live cells, and `f32` weights also count the
+/// levels of continuous cells.
+///
+pub trait Weight<C>: Copy + Default + Add<Output = Self> {
+    /// Returns the contribution of `cell` under a tap of this weight.
+    fn weigh(self, cell: C) -> Self;
+}
+
+macro_rules! integer_weight {
+    ($($t:ty),*) => {$(
+        impl<C: CellState> Weight<C> for $t {
+            fn weigh(self, cell: C) -> Self {
+                if cell.is_alive() {
+                    self
+                } else {
+                    0
+                }
+            }
+        }
+    )*};
+}
+
+integer_weight!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl Weight<Cell> for f32 {
+    fn weigh(self, cell: Cell) -> Self {
+        if cell.is_alive() {
+            self
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Weight<Level> for f32 {
+    fn weigh(self, cell: Level) -> Self {
+        self * cell.to_f32()
+    }
+}
+
+///
+/// A rule mapping the state of a cell and its convolved sum to its next
+/// state.
+///
+pub trait ConvolutionRule<C, T> {
+    /// Calculate the next state of `cell`, given its convolved sum.
+    fn next(&self, cell: C, sum: T) -> C;
+}
+
+impl<C, T, F: Fn(C, T) -> C> ConvolutionRule<C, T> for F {
+    fn next(&self, cell: C, sum: T) -> C {
+        self(cell, sum)
+    }
+}
+
+impl<const W: usize, const H: usize, C: CellState> Grid<W, H, C> {
+    ///
+    /// Convolve this grid with `kernel`, given as the offsets and weights of
+    /// each tap, returning the weighted sum around every cell.
+    ///
+    /// Cells outside of the grid are skipped or wrapped around according to
+    /// `topology`.
+    ///
+    #[must_use]
+    pub fn convolve<T: Weight<C>>(
+        &self,
+        kernel: &[(isize, isize, T)],
+        topology: Topology,
+    ) -> [[T; W]; H] {
+        core::array::from_fn(|y| core::array::from_fn(|x| self.sum(Coord(x, y), kernel, topology)))
+    }
+
+    ///
+    /// Calculates the next generation of this grid according to `rule`,
+    /// given the convolution of each cell with `kernel`.
+    ///
+    /// This is the same as applying `rule` to the result of
+    /// [`Grid::convolve()`], without keeping the whole result.
+    ///
+    #[must_use]
+    pub fn step_convolved<T: Weight<C>>(
+        &self,
+        kernel: &[(isize, isize, T)],
+        topology: Topology,
+        rule: &impl ConvolutionRule<C, T>,
+    ) -> Self {
+        Self {
+            cells: core::array::from_fn(|y| {
+                core::array::from_fn(|x| {
+                    let coord = Coord(x, y);
+                    rule.next(self[coord], self.sum(coord, kernel, topology))
+                })
+            }),
+            generation: self.generation + 1,
+        }
+    }
+
+    /// Returns the weighted sum of `kernel` around `coord`.
+    fn sum<T: Weight<C>>(
+        &self,
+        coord: Coord,
+        kernel: &[(isize, isize, T)],
+        topology: Topology,
+    ) -> T {
+        kernel
+            .iter()
+            .filter_map(|&(dx, dy, weight)| {
+                let coord = topology.offset(coord, dx, dy, Coord(W, H))?;
+                Some(weight.weigh(self[coord]))
+            })
+            .fold(T::default(), Add::add)
+    }
+}
+
+///
+/// Returns the taps of the Moore neighborhood, each with the same `weight`.
+///
+/// Stepping with these taps and unit weights counts live neighbors, as
+/// [`Rule`](super::Rule)s do.
+///
+#[must_use]
+pub fn moore<T: Copy>(weight: T) -> [(isize, isize, T); 8] {
+    let offsets = Neighborhood::Moore.offsets();
+    core::array::from_fn(|i| (offsets[i].0, offsets[i].1, weight))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rule::{LifeLike, Rule};
+
+    #[test]
+    fn convolve() {
+        let mut grid = Grid::<3, 3>::new();
+        grid[Coord(0, 0)] = Cell::Alive;
+        grid[Coord(2, 2)] = Cell::Alive;
+
+        let kernel = [(1, 0, 1), (0, 1, 10)];
+        assert_eq!(
+            grid.convolve(&kernel, Topology::Bounded),
+            [[0, 0, 0], [0, 0, 10], [0, 1, 0]]
+        );
+        assert_eq!(
+            grid.convolve(&kernel, Topology::Torus),
+            [[0, 0, 1], [0, 0, 10], [10, 1, 0]]
+        );
+    }
+
+    #[test]
+    fn step_convolved() {
+        let mut grid = Grid::<6, 6>::new();
+        for coord in [
+            Coord(1, 0),
+            Coord(2, 1),
+            Coord(0, 2),
+            Coord(1, 2),
+            Coord(2, 2),
+        ] {
+            grid[coord] = Cell::Alive;
+        }
+
+        let rule = |cell, count: u8| LifeLike::CONWAY.next(cell, count.into());
+        let next = grid.step_convolved(&moore(1), Topology::Torus, &rule);
+        assert_eq!(
+            next.cells,
+            grid.step_on(&LifeLike::CONWAY, Topology::Torus).cells
+        );
+        assert_eq!(next.generation, 1);
+    }
+}
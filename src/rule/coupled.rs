@@ -0,0 +1,103 @@
+//!
+//! Several grids stepped together, where each one's rule can read the
+//! others.
+//!
+//! See [`CoupledRule`] and [`step_coupled()`].
+//!
+
+use crate::{cell::Cell, grid::Grid, math::Coord};
+
+///
+/// A rule for `N` layers of two-state cells stepped together, such as prey
+/// and predators, or Life with an inhibitor field.
+///
+pub trait CoupledRule<const N: usize> {
+    ///
+    /// Calculate the next state of a cell on `layer`, given its state on
+    /// every layer and its number of live neighbors on every layer.
+    ///
+    fn next(&self, layer: usize, cells: [Cell; N], alive_neighbors: [usize; N]) -> Cell;
+}
+
+///
+/// Calculates the next generation of `N` coupled layers.
+///
+/// Every layer is stepped from the same previous generation of all of them.
+///
+#[must_use]
+pub fn step_coupled<const W: usize, const H: usize, const N: usize>(
+    layers: &[Grid<W, H>; N],
+    rule: &impl CoupledRule<N>,
+) -> [Grid<W, H>; N] {
+    let alive_neighbors = |coord: Coord| {
+        layers.each_ref().map(|grid| {
+            coord
+                .neighbors(Coord(W, H))
+                .filter(|&coord| grid[coord] == Cell::Alive)
+                .count()
+        })
+    };
+
+    let mut next = layers.each_ref().map(|grid| Grid {
+        cells: [[Cell::Dead; W]; H],
+        generation: grid.generation + 1,
+    });
+    for y in 0..H {
+        for x in 0..W {
+            let coord = Coord(x, y);
+            let cells = layers.each_ref().map(|grid| grid[coord]);
+            let alive_neighbors = alive_neighbors(coord);
+            for (layer, grid) in next.iter_mut().enumerate() {
+                grid[coord] = rule.next(layer, cells, alive_neighbors);
+            }
+        }
+    }
+
+    next
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Static prey, eaten by predators which spread into neighboring prey.
+    struct PredatorPrey;
+
+    impl CoupledRule<2> for PredatorPrey {
+        fn next(
+            &self,
+            layer: usize,
+            [prey, predator]: [Cell; 2],
+            [_, predators]: [usize; 2],
+        ) -> Cell {
+            match layer {
+                0 if predator == Cell::Alive => Cell::Dead,
+                0 => prey,
+                _ if prey == Cell::Alive && (predator == Cell::Alive || predators > 0) => {
+                    Cell::Alive
+                }
+                _ => Cell::Dead,
+            }
+        }
+    }
+
+    #[test]
+    fn predator_prey() {
+        let mut prey = Grid::<5, 5>::new();
+        let mut predators = Grid::<5, 5>::new();
+        for coord in [Coord(1, 1), Coord(2, 1), Coord(3, 1)] {
+            prey[coord] = Cell::Alive;
+        }
+        predators[Coord(0, 1)] = Cell::Alive;
+
+        let [prey, predators] = step_coupled(&[prey, predators], &PredatorPrey);
+        assert_eq!(predators[Coord(1, 1)], Cell::Alive);
+        assert_eq!(predators[Coord(0, 1)], Cell::Dead);
+        assert_eq!(prey[Coord(2, 1)], Cell::Alive);
+        assert_eq!(prey.generation, 1);
+
+        let [prey, predators] = step_coupled(&[prey, predators], &PredatorPrey);
+        assert_eq!(prey[Coord(1, 1)], Cell::Dead);
+        assert_eq!(predators[Coord(2, 1)], Cell::Alive);
+    }
+}
@@ -0,0 +1,111 @@
+//!
+//! Cells which fade out after dying, leaving trails behind moving patterns.
+//!
+//! See [`FadingCell`] and [`Fading`].
+//!
+
+use super::Rule;
+use crate::cell::{Cell, CellState};
+
+///
+/// A cell with a brightness, which is [`FadingCell::ALIVE`] for live cells,
+/// and falls each generation after they die until it reaches 0.
+///
+/// Dead cells which died recently are still bright, so that renderers can
+/// draw trails by brightness alone.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct FadingCell(pub u8);
+
+impl FadingCell {
+    /// A live cell.
+    pub const ALIVE: Self = Self(u8::MAX);
+
+    /// Returns whether this cell is alive or dead, ignoring its brightness.
+    #[must_use]
+    pub const fn cell(self) -> Cell {
+        if self.0 == Self::ALIVE.0 {
+            Cell::Alive
+        } else {
+            Cell::Dead
+        }
+    }
+}
+
+impl CellState for FadingCell {
+    const DEAD: Self = Self(0);
+
+    fn is_alive(self) -> bool {
+        self == Self::ALIVE
+    }
+}
+
+impl From<Cell> for FadingCell {
+    fn from(cell: Cell) -> Self {
+        match cell {
+            Cell::Alive => Self::ALIVE,
+            Cell::Dead => Self::DEAD,
+        }
+    }
+}
+
+///
+/// A two-state rule stepping [`FadingCell`]s, whose dead cells lose `decay`
+/// brightness every generation.
+///
+/// A `decay` of 0 is treated as 1, so that dead cells are never mistaken
+/// for live ones.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Fading<R> {
+    pub rule: R,
+    pub decay: u8,
+}
+
+impl<R: Rule> Fading<R> {
+    /// Construct a new [`Fading`] rule from a two-state rule.
+    #[must_use]
+    pub const fn new(rule: R, decay: u8) -> Self {
+        Self { rule, decay }
+    }
+}
+
+impl<R: Rule> Rule<FadingCell> for Fading<R> {
+    fn next(&self, cell: FadingCell, alive_neighbors: usize) -> FadingCell {
+        match self.rule.next(cell.cell(), alive_neighbors) {
+            Cell::Alive => FadingCell::ALIVE,
+            Cell::Dead => FadingCell(cell.0.saturating_sub(self.decay.max(1))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{grid::Grid, math::Coord, rule::Life};
+
+    #[test]
+    fn fading() {
+        let mut grid = Grid::<3, 3, FadingCell>::new();
+        for x in 0..3 {
+            grid[Coord(x, 1)] = FadingCell::ALIVE;
+        }
+
+        let rule = Fading::new(Life, 100);
+        let grid = grid.step_with(&rule);
+        assert_eq!(grid.population(), 3);
+        assert_eq!(grid[Coord(0, 1)], FadingCell(155));
+        assert_eq!(grid[Coord(1, 0)], FadingCell::ALIVE);
+
+        let grid = grid.step_with(&rule);
+        assert_eq!(grid[Coord(0, 1)], FadingCell::ALIVE);
+        assert_eq!(grid[Coord(1, 0)], FadingCell(155));
+        assert_eq!(grid[Coord(0, 0)], FadingCell::DEAD);
+
+        let mut grid = Grid::<1, 1, FadingCell>::new();
+        grid[Coord(0, 0)] = FadingCell::ALIVE;
+        let grid = grid.step_with(&rule).step_with(&rule);
+        assert_eq!(grid[Coord(0, 0)], FadingCell(55));
+        assert_eq!(grid.step_with(&rule)[Coord(0, 0)], FadingCell::DEAD);
+    }
+}
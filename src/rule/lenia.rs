@@ -0,0 +1,244 @@
+//!
+//! [Lenia](https://en.wikipedia.org/wiki/Lenia), a continuous automaton
+//! updated by convolving the grid with a ring-shaped kernel.
+//!
+//! See [`Lenia`] and [`Grid::step_lenia()`].
+//!
+
+use alloc::vec::Vec;
+
+use super::{continuous::Level, Topology};
+use crate::grid::Grid;
+
+///
+/// A convolution kernel, as the offsets and weights of each cell around the
+/// center, with weights summing to 1.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct Kernel {
+    taps: Vec<(isize, isize, f32)>,
+}
+
+impl Kernel {
+    ///
+    /// Construct a new [`Kernel`] of the given radius, with each cell weighted
+    /// by `shell` of its distance from the center, divided by `radius`.
+    ///
+    /// `shell` is only called for distances in `[0, 1)`, and its weights are
+    /// normalized to sum to 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if all weights are 0.
+    ///
+    #[must_use]
+    pub fn new(radius: usize, shell: impl Fn(f32) -> f32) -> Self {
+        let r = radius as isize;
+        let mut taps = Vec::new();
+        for dy in -r..=r {
+            for dx in -r..=r {
+                let distance = sqrt((dx * dx + dy * dy) as f32) / radius as f32;
+                if distance < 1.0 {
+                    let weight = shell(distance);
+                    if weight != 0.0 {
+                        taps.push((dx, dy, weight));
+                    }
+                }
+            }
+        }
+
+        let sum: f32 = taps.iter().map(|&(_, _, weight)| weight).sum();
+        assert!(sum != 0.0, "kernel is empty");
+        for (_, _, weight) in &mut taps {
+            *weight /= sum;
+        }
+
+        Self { taps }
+    }
+
+    /// A ring of the polynomial shell `(4r(1 - r))^4`.
+    #[must_use]
+    pub fn polynomial(radius: usize) -> Self {
+        Self::new(radius, |r| {
+            let x = 4.0 * r * (1.0 - r);
+            x * x * x * x
+        })
+    }
+
+    /// A ring of the exponential shell `exp(4 - 1 / (r(1 - r)))`.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn exponential(radius: usize) -> Self {
+        Self::new(radius, |r| {
+            if r > 0.0 {
+                (4.0 - 1.0 / (r * (1.0 - r))).exp()
+            } else {
+                0.0
+            }
+        })
+    }
+
+    /// Returns the offsets and weights of the cells of this kernel.
+    #[must_use]
+    pub fn taps(&self) -> &[(isize, isize, f32)] {
+        &self.taps
+    }
+}
+
+///
+/// A growth function, mapping the convolved potential of a cell in `[0, 1]`
+/// to its rate of change in `[-1, 1]`.
+///
+pub trait Growth {
+    /// Returns the growth at `potential`.
+    fn growth(&self, potential: f32) -> f32;
+}
+
+impl<F: Fn(f32) -> f32> Growth for F {
+    fn growth(&self, potential: f32) -> f32 {
+        self(potential)
+    }
+}
+
+///
+/// The polynomial bell `2(1 - (u - mu)^2 / 9sigma^2)^4 - 1`, which peaks at
+/// `mu` and is -1 further than `3sigma` from it.
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Bell {
+    pub mu: f32,
+    pub sigma: f32,
+}
+
+impl Growth for Bell {
+    fn growth(&self, potential: f32) -> f32 {
+        let d = (potential - self.mu) / (3.0 * self.sigma);
+        let x = 1.0 - d * d;
+        if x > 0.0 {
+            2.0 * x * x * x * x - 1.0
+        } else {
+            -1.0
+        }
+    }
+}
+
+///
+/// The Gaussian `2exp(-(u - mu)^2 / 2sigma^2) - 1`, which peaks at `mu`.
+///
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Gaussian {
+    pub mu: f32,
+    pub sigma: f32,
+}
+
+#[cfg(feature = "std")]
+impl Growth for Gaussian {
+    fn growth(&self, potential: f32) -> f32 {
+        let d = (potential - self.mu) / self.sigma;
+        2.0 * (-d * d / 2.0).exp() - 1.0
+    }
+}
+
+///
+/// A Lenia rule: each generation, every cell's level changes by `dt` times
+/// the growth of its potential, which is the grid convolved with `kernel`.
+///
+/// The glider Orbium lives with an exponential kernel of radius 13, a
+/// Gaussian growth with a `mu` of 0.15 and a `sigma` of 0.015, and a `dt` of
+/// 0.1. Both need the `std` feature.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct Lenia<G = Bell> {
+    pub kernel: Kernel,
+    pub growth: G,
+    pub dt: f32,
+}
+
+impl<G: Growth> Lenia<G> {
+    /// Construct a new [`Lenia`] rule.
+    #[must_use]
+    pub const fn new(kernel: Kernel, growth: G, dt: f32) -> Self {
+        Self { kernel, growth, dt }
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H, Level> {
+    ///
+    /// Calculates the next generation of this grid according to `lenia`, on
+    /// a torus.
+    ///
+    /// The kernel is convolved directly with [`Grid::step_convolved()`], so
+    /// each cell takes time proportional to the number of cells in the
+    /// kernel.
+    ///
+    #[must_use]
+    pub fn step_lenia(&self, lenia: &Lenia<impl Growth>) -> Self {
+        self.step_convolved(
+            lenia.kernel.taps(),
+            Topology::Torus,
+            &|level: Level, potential| {
+                Level::from_f32(level.to_f32() + lenia.dt * lenia.growth.growth(potential))
+            },
+        )
+    }
+}
+
+/// Returns the square root of `x`, which is not in `core`.
+fn sqrt(x: f32) -> f32 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+
+    // Newton's method, starting from a bit-level estimate.
+    let mut root = f32::from_bits((x.to_bits() >> 1) + 0x1fc0_0000);
+    for _ in 0..4 {
+        root = (root + x / root) / 2.0;
+    }
+    root
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn kernel() {
+        let kernel = Kernel::polynomial(5);
+        let sum: f32 = kernel.taps().iter().map(|&(_, _, weight)| weight).sum();
+        assert!((sum - 1.0).abs() < 1e-4);
+        assert!(kernel
+            .taps()
+            .iter()
+            .all(|&(dx, dy, _)| dx * dx + dy * dy < 25));
+        // the center of the ring has no weight
+        assert!(!kernel.taps().iter().any(|&(dx, dy, _)| (dx, dy) == (0, 0)));
+        assert!((sqrt(2.0) - core::f32::consts::SQRT_2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn step_lenia() {
+        let lenia = Lenia::new(
+            Kernel::polynomial(3),
+            Bell {
+                mu: 0.15,
+                sigma: 0.015,
+            },
+            0.1,
+        );
+
+        let grid = Grid::<8, 8, Level>::new().step_lenia(&lenia);
+        assert!(grid.cells.iter().flatten().all(|&cell| cell == Level::ZERO));
+
+        let mut grid = Grid::<8, 8, Level>::new();
+        for cell in grid.cells.iter_mut().flatten() {
+            *cell = Level::ONE;
+        }
+        let grid = grid.step_lenia(&lenia);
+        assert!(grid
+            .cells
+            .iter()
+            .flatten()
+            .all(|&cell| cell == Level::from_f32(0.9)));
+    }
+}
@@ -0,0 +1,134 @@
+//!
+//! [Life-like rules](https://conwaylife.com/wiki/Life-like_cellular_automaton),
+//! given in B/S notation.
+//!
+//! See [`LifeLike`].
+//!
+
+use core::{fmt, str::FromStr};
+
+use super::{ParseError, ParseErrorKind, Rule};
+use crate::cell::Cell;
+
+///
+/// An outer-totalistic rule on the Moore neighborhood, such as B3/S23 for
+/// Conway's Game of Life, or B36/S23 for HighLife.
+///
+/// Bit `n` of `birth` and `survival` is set if a dead cell is born, or a
+/// live cell survives, with `n` live neighbors.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct LifeLike {
+    pub birth: u16,
+    pub survival: u16,
+}
+
+impl LifeLike {
+    /// Conway's Game of Life, or B3/S23.
+    pub const CONWAY: Self = Self::new(1 << 3, 1 << 2 | 1 << 3);
+
+    /// HighLife, or B36/S23.
+    pub const HIGH_LIFE: Self = Self::new(1 << 3 | 1 << 6, 1 << 2 | 1 << 3);
+
+    /// Construct a new [`LifeLike`] rule from its bitmasks.
+    #[must_use]
+    pub const fn new(birth: u16, survival: u16) -> Self {
+        Self { birth, survival }
+    }
+}
+
+impl Default for LifeLike {
+    fn default() -> Self {
+        Self::CONWAY
+    }
+}
+
+impl Rule for LifeLike {
+    fn next(&self, cell: Cell, alive_neighbors: usize) -> Cell {
+        let mask = match cell {
+            Cell::Alive => self.survival,
+            Cell::Dead => self.birth,
+        };
+        Cell::from(alive_neighbors <= 8 && mask & 1 << alive_neighbors != 0)
+    }
+}
+
+impl FromStr for LifeLike {
+    type Err = ParseError;
+
+    ///
+    /// Parse a rule in `B3/S23` notation, in any case and in either order,
+    /// or in the older `23/3` survival/birth notation.
+    ///
+    fn from_str(src: &str) -> Result<Self, ParseError> {
+        let error = ParseError {
+            line: 1,
+            kind: ParseErrorKind::Syntax,
+        };
+        let digits = |part: &str| {
+            part.bytes().try_fold(0u16, |mask, digit| match digit {
+                b'0'..=b'8' => Ok(mask | 1 << (digit - b'0')),
+                _ => Err(error),
+            })
+        };
+
+        fn strip(part: &str, prefix: char) -> Option<&str> {
+            part.strip_prefix([prefix, prefix.to_ascii_uppercase()])
+        }
+
+        let (first, second) = src.trim().split_once('/').ok_or(error)?;
+        let (birth, survival) = match (strip(first, 'b'), strip(first, 's')) {
+            (Some(birth), _) => (birth, strip(second, 's').ok_or(error)?),
+            (_, Some(survival)) => (strip(second, 'b').ok_or(error)?, survival),
+            _ => (second, first),
+        };
+
+        Ok(Self::new(digits(birth)?, digits(survival)?))
+    }
+}
+
+impl fmt::Display for LifeLike {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = |f: &mut fmt::Formatter<'_>, mask: u16| {
+            (0..=8)
+                .filter(|n| mask & 1 << n != 0)
+                .try_for_each(|n| write!(f, "{n}"))
+        };
+
+        f.write_str("B")?;
+        digits(f, self.birth)?;
+        f.write_str("/S")?;
+        digits(f, self.survival)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use std::format;
+
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!("B3/S23".parse(), Ok(LifeLike::CONWAY));
+        assert_eq!("s23/b36".parse(), Ok(LifeLike::HIGH_LIFE));
+        assert_eq!("23/3".parse(), Ok(LifeLike::CONWAY));
+        assert_eq!("B/S".parse(), Ok(LifeLike::new(0, 0)));
+        assert!("B9/S23".parse::<LifeLike>().is_err());
+        assert!("B3S23".parse::<LifeLike>().is_err());
+        assert!("B3/B23".parse::<LifeLike>().is_err());
+
+        assert_eq!(format!("{}", LifeLike::HIGH_LIFE), "B36/S23");
+    }
+
+    #[test]
+    fn next() {
+        let rule = LifeLike::HIGH_LIFE;
+        assert_eq!(rule.next(Cell::Dead, 6), Cell::Alive);
+        assert_eq!(rule.next(Cell::Alive, 6), Cell::Dead);
+        assert_eq!(rule.next(Cell::Alive, 2), Cell::Alive);
+        assert_eq!(rule.next(Cell::Dead, 9), Cell::Dead);
+    }
+}
@@ -0,0 +1,198 @@
+//!
+//! Reversible block rules on the
+//! [Margolus neighborhood](https://en.wikipedia.org/wiki/Block_cellular_automaton).
+//!
+//! The grid is split into 2 by 2 blocks, which are offset by one cell in
+//! both directions on odd generations, and each block is replaced according
+//! to a [`BlockRule`]. Blocks wrap around the edges of the grid, so that
+//! every step can be exactly undone with [`Grid::step_margolus_backward()`].
+//!
+
+use crate::{cell::Cell, grid::Grid, math::Coord};
+
+///
+/// A reversible rule for 2 by 2 blocks.
+///
+/// Blocks are numbered by their live cells, with bits 0 to 3 being the
+/// top-left, top-right, bottom-left and bottom-right cells respectively.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BlockRule {
+    forward: [u8; 16],
+    backward: [u8; 16],
+}
+
+impl BlockRule {
+    ///
+    /// [Critters](https://conwaylife.com/wiki/Critters): blocks with two
+    /// live cells are unchanged, and all others are inverted, then rotated
+    /// by a half turn if they had three live cells.
+    ///
+    pub const CRITTERS: Self = Self::new([
+        0b1111, 0b1110, 0b1101, 0b0011, 0b1011, 0b0101, 0b0110, 0b0001, //
+        0b0111, 0b1001, 0b1010, 0b0010, 0b1100, 0b0100, 0b1000, 0b0000,
+    ])
+    .expect("critters is reversible");
+
+    ///
+    /// The [billiard ball machine](https://en.wikipedia.org/wiki/Billiard-ball_computer):
+    /// a lone live cell moves to the opposite corner, two diagonally opposite
+    /// live cells move to the other diagonal, and all other blocks are
+    /// unchanged.
+    ///
+    pub const BILLIARD_BALL: Self = Self::new([
+        0b0000, 0b1000, 0b0100, 0b0011, 0b0010, 0b0101, 0b1001, 0b0111, //
+        0b0001, 0b0110, 0b1010, 0b1011, 0b1100, 0b1101, 0b1110, 0b1111,
+    ])
+    .expect("billiard ball machine is reversible");
+
+    ///
+    /// Construct a new [`BlockRule`] replacing each block `i` with
+    /// `table[i]`.
+    ///
+    /// Returns [`None`] if the rule is not reversible, that is if `table`
+    /// is not a permutation of `0..16`.
+    ///
+    #[must_use]
+    pub const fn new(table: [u8; 16]) -> Option<Self> {
+        let mut backward = [16; 16];
+        let mut block = 0;
+        while block < 16 {
+            let next = table[block] as usize;
+            if next >= 16 || backward[next] != 16 {
+                return None;
+            }
+            backward[next] = block as u8;
+            block += 1;
+        }
+
+        Some(Self {
+            forward: table,
+            backward,
+        })
+    }
+
+    /// Returns the block replacing `block`.
+    #[must_use]
+    pub const fn apply(&self, block: u8) -> u8 {
+        self.forward[block as usize & 0xf]
+    }
+
+    /// Returns the block replaced by `block`.
+    #[must_use]
+    pub const fn invert(&self, block: u8) -> u8 {
+        self.backward[block as usize & 0xf]
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Calculates the next generation of this grid according to a
+    /// [`BlockRule`], using the block offset of the current generation.
+    ///
+    /// The grid's extents must be even, which is checked at compile time.
+    ///
+    #[must_use]
+    pub fn step_margolus(&self, rule: &BlockRule) -> Self {
+        let mut next = self.clone();
+        next.replace_blocks(self.generation % 2 == 1, |block| rule.apply(block));
+        next.generation += 1;
+        next
+    }
+
+    ///
+    /// Calculates the previous generation of this grid according to a
+    /// [`BlockRule`], exactly undoing [`Grid::step_margolus()`].
+    ///
+    /// The grid's extents must be even, which is checked at compile time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this grid is at generation 0.
+    ///
+    #[must_use]
+    pub fn step_margolus_backward(&self, rule: &BlockRule) -> Self {
+        let mut prev = self.clone();
+        prev.generation = self
+            .generation
+            .checked_sub(1)
+            .expect("no generation before 0");
+        prev.replace_blocks(prev.generation % 2 == 1, |block| rule.invert(block));
+        prev
+    }
+
+    /// Replace every block, offset by one cell if `odd`.
+    fn replace_blocks(&mut self, odd: bool, replace: impl Fn(u8) -> u8) {
+        const {
+            assert!(
+                W.is_multiple_of(2) && H.is_multiple_of(2),
+                "extents must be even"
+            )
+        };
+
+        let offset = usize::from(odd);
+        for y in (offset..H + offset).step_by(2) {
+            for x in (offset..W + offset).step_by(2) {
+                let coords = [(x, y), (x + 1, y), (x, y + 1), (x + 1, y + 1)]
+                    .map(|(x, y)| Coord(x % W, y % H));
+
+                let block = coords
+                    .iter()
+                    .enumerate()
+                    .fold(0, |block, (i, &coord)| block | (self[coord] as u8) << i);
+                let block = replace(block);
+
+                for (i, coord) in coords.into_iter().enumerate() {
+                    self[coord] = Cell::from(block & 1 << i != 0);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reversible() {
+        assert!(BlockRule::new([0; 16]).is_none());
+
+        let mut grid = Grid::<8, 6>::new();
+        for coord in [
+            Coord(1, 1),
+            Coord(2, 1),
+            Coord(2, 2),
+            Coord(5, 4),
+            Coord(7, 5),
+        ] {
+            grid[coord] = Cell::Alive;
+        }
+
+        for rule in [BlockRule::CRITTERS, BlockRule::BILLIARD_BALL] {
+            let mut next = grid.clone();
+            for _ in 0..25 {
+                next = next.step_margolus(&rule);
+            }
+            assert_ne!(next.cells, grid.cells);
+
+            for _ in 0..25 {
+                next = next.step_margolus_backward(&rule);
+            }
+            assert_eq!(next.cells, grid.cells);
+            assert_eq!(next.generation, 0);
+        }
+    }
+
+    #[test]
+    fn billiard_ball() {
+        let mut grid = Grid::<4, 4>::new();
+        grid[Coord(0, 0)] = Cell::Alive;
+
+        let grid = grid.step_margolus(&BlockRule::BILLIARD_BALL);
+        assert_eq!(grid[Coord(1, 1)], Cell::Alive);
+        let grid = grid.step_margolus(&BlockRule::BILLIARD_BALL);
+        assert_eq!(grid[Coord(2, 2)], Cell::Alive);
+        assert_eq!(grid.population(), 1);
+    }
+}
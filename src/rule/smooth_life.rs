@@ -0,0 +1,145 @@
+//!
+//! [SmoothLife](https://arxiv.org/abs/1111.1567), a continuous
+//! generalization of the Game of Life.
+//!
+//! See [`SmoothLife`].
+//!
+
+use super::continuous::{ContinuousRule, Level};
+
+///
+/// SmoothLife with a discrete time step, where each cell's next level is a
+/// smooth function of the mean levels of its inner disk and outer ring.
+///
+/// A cell is born if the outer mean is between the birth bounds, and
+/// survives if it is between the death bounds, with the bounds blended by
+/// how alive the inner disk is. The edges of both intervals are sigmoids of
+/// width `alpha_n`, and the blending is a sigmoid of width `alpha_m`.
+///
+/// With the `std` feature, the transition is computed in `f32`. Otherwise,
+/// it is computed in fixed point, which differs from `f32` by less than
+/// 0.2%.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct SmoothLife {
+    /// The radius of the inner disk, in cells.
+    pub radius: usize,
+    pub birth: (Level, Level),
+    pub death: (Level, Level),
+    pub alpha_n: Level,
+    pub alpha_m: Level,
+}
+
+impl SmoothLife {
+    /// The parameters of the original paper, with an inner radius of 7.
+    pub const RAFLER: Self = Self {
+        radius: 7,
+        birth: (Level::from_ratio(278, 1000), Level::from_ratio(365, 1000)),
+        death: (Level::from_ratio(267, 1000), Level::from_ratio(445, 1000)),
+        alpha_n: Level::from_ratio(28, 1000),
+        alpha_m: Level::from_ratio(147, 1000),
+    };
+}
+
+impl Default for SmoothLife {
+    fn default() -> Self {
+        Self::RAFLER
+    }
+}
+
+impl ContinuousRule for SmoothLife {
+    fn radius(&self) -> usize {
+        self.radius
+    }
+
+    #[cfg(feature = "std")]
+    fn next(&self, _: Level, inner: Level, outer: Level) -> Level {
+        let sigmoid = |x: f32, a: Level, alpha: Level| {
+            1.0 / (1.0 + (-(x - a.to_f32()) * 4.0 / alpha.to_f32()).exp())
+        };
+        let mix = |lo: Level, hi: Level, t: f32| lo.to_f32() * (1.0 - t) + hi.to_f32() * t;
+
+        let (n, m) = (outer.to_f32(), inner.to_f32());
+        let aliveness = sigmoid(m, Level::HALF, self.alpha_m);
+        let lo = Level::from_f32(mix(self.birth.0, self.death.0, aliveness));
+        let hi = Level::from_f32(mix(self.birth.1, self.death.1, aliveness));
+
+        Level::from_f32(sigmoid(n, lo, self.alpha_n) * (1.0 - sigmoid(n, hi, self.alpha_n)))
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn next(&self, _: Level, inner: Level, outer: Level) -> Level {
+        let mix = |lo: Level, hi: Level, t: i64| {
+            Level::from_ratio(
+                (q16(lo) * (ONE - t) + q16(hi) * t) as u64,
+                (ONE * ONE) as u64,
+            )
+        };
+
+        let aliveness = sigmoid(inner, Level::HALF, self.alpha_m);
+        let lo = mix(self.birth.0, self.death.0, aliveness);
+        let hi = mix(self.birth.1, self.death.1, aliveness);
+
+        let level = sigmoid(outer, lo, self.alpha_n) * (ONE - sigmoid(outer, hi, self.alpha_n));
+        Level::from_ratio(level as u64, (ONE * ONE) as u64)
+    }
+}
+
+/// 1 in Q16 fixed point.
+#[cfg(not(feature = "std"))]
+const ONE: i64 = 1 << 16;
+
+/// Converts a level to Q16 fixed point.
+#[cfg(not(feature = "std"))]
+fn q16(level: Level) -> i64 {
+    (i64::from(level.0) * ONE + i64::from(u16::MAX) / 2) / i64::from(u16::MAX)
+}
+
+///
+/// Returns the logistic function of `4 * (x - a) / alpha` in Q16 fixed point,
+/// which rises from 0 to 1 around `a` over a width of about `alpha`.
+///
+#[cfg(not(feature = "std"))]
+fn sigmoid(x: Level, a: Level, alpha: Level) -> i64 {
+    /// log2(e) in Q16 fixed point.
+    const LOG2_E: i64 = 94_548;
+
+    let t = 4 * (q16(x) - q16(a)) * ONE / q16(alpha).max(1);
+    // e^-|t| = 2^-(|t| log2(e)), split into whole and fractional powers of 2,
+    // with 2^-f for the fractional power f by its Taylor series.
+    let power = t.abs().min(32 * ONE) * LOG2_E / ONE;
+    let f = power % ONE;
+    let [c1, c2, c3, c4] = [45_426, 15_743, 3_638, 630];
+    let taylor = ONE - f * (c1 - f * (c2 - f * (c3 - f * c4 / ONE) / ONE) / ONE) / ONE;
+    let exp = taylor >> (power / ONE);
+
+    let logistic = ONE * ONE / (ONE + exp);
+    if t >= 0 {
+        logistic
+    } else {
+        ONE - logistic
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn next() {
+        let rule = SmoothLife::default();
+        let next = |inner, outer| rule.next(Level::ZERO, inner, outer).to_f32();
+
+        // empty and crowded cells stay or become dead
+        assert!(next(Level::ZERO, Level::ZERO) < 0.01);
+        assert!(next(Level::ONE, Level::ONE) < 0.01);
+        // live cells survive and dead cells are born at moderate densities
+        let outer = Level::from_ratio(3, 10);
+        assert!((next(Level::ONE, outer) - 0.991).abs() < 0.01);
+        assert!((next(Level::ZERO, outer) - 0.958).abs() < 0.01);
+        // but only live cells survive at higher ones
+        let outer = Level::from_ratio(4, 10);
+        assert!(next(Level::ONE, outer) > 0.9);
+        assert!(next(Level::ZERO, outer) < 0.01);
+    }
+}
@@ -0,0 +1,502 @@
+//!
+//! [Golly rule tables](https://golly.sourceforge.io/Help/formats.html#table).
+//!
+//! See [`RuleTable::parse()`] to load the `@TABLE` section of a `.rule` file.
+//!
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec,
+    vec::Vec,
+};
+
+use super::{section, step_states, Neighborhood, ParseError, ParseErrorKind};
+use crate::{cell::State, grid::Grid};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Symmetries {
+    None,
+    Rotate4,
+    Rotate4Reflect,
+    Rotate8,
+    Rotate8Reflect,
+    ReflectHorizontal,
+    Permute,
+}
+
+/// A set of states, as a 256-bit bitmask.
+type States = [u64; 4];
+
+///
+/// A compiled Golly rule table.
+///
+/// Transitions are matched with one bitmask lookup per neighbor, rather than
+/// by trying each transition in turn.
+///
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RuleTable {
+    name: Option<String>,
+    states: usize,
+    neighborhood: Neighborhood,
+    outputs: Vec<u8>,
+    /// For each input position and state, the set of transitions accepting
+    /// that state at that position.
+    masks: Vec<u64>,
+}
+
+impl RuleTable {
+    ///
+    /// Parse a rule table, either from the `@TABLE` section of a `.rule`
+    /// file, or from a bare `.table` file.
+    ///
+    /// Only the `Moore` and `vonNeumann` neighborhoods are supported.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if the rule table is malformed or uses an
+    /// unsupported feature.
+    ///
+    pub fn parse(src: &str) -> Result<Self, ParseError> {
+        let name = src
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("@RULE"))
+            .map(|name| name.trim().into());
+
+        let mut states = None;
+        let mut neighborhood = Neighborhood::Moore;
+        let mut symmetries = Symmetries::None;
+        let mut vars = BTreeMap::<&str, States>::new();
+        let mut transitions = Vec::<(Vec<States>, u8)>::new();
+        let mut seen = BTreeSet::new();
+
+        for (line, text) in section(src, "@TABLE")? {
+            let error = |kind| ParseError { line, kind };
+
+            if let Some((key, value)) = text.split_once(':') {
+                let value = value.trim();
+                match key.trim() {
+                    "n_states" => {
+                        states = value.parse().ok().filter(|n| (2..=256).contains(n));
+                        states.ok_or(error(ParseErrorKind::InvalidStates))?;
+                    }
+                    "neighborhood" => {
+                        neighborhood = match value {
+                            "Moore" => Neighborhood::Moore,
+                            "vonNeumann" => Neighborhood::VonNeumann,
+                            _ => return Err(error(ParseErrorKind::UnsupportedNeighborhood)),
+                        }
+                    }
+                    "symmetries" => {
+                        symmetries = match (value, neighborhood) {
+                            ("none", _) => Symmetries::None,
+                            ("rotate4", _) => Symmetries::Rotate4,
+                            ("rotate4reflect", _) => Symmetries::Rotate4Reflect,
+                            ("rotate8", Neighborhood::Moore) => Symmetries::Rotate8,
+                            ("rotate8reflect", Neighborhood::Moore) => Symmetries::Rotate8Reflect,
+                            ("reflect_horizontal", _) => Symmetries::ReflectHorizontal,
+                            ("permute", _) => Symmetries::Permute,
+                            _ => return Err(error(ParseErrorKind::UnsupportedSymmetries)),
+                        }
+                    }
+                    _ => return Err(error(ParseErrorKind::Syntax)),
+                }
+                continue;
+            }
+
+            let states = states.ok_or(error(ParseErrorKind::InvalidStates))?;
+            let parse_states = |field: &str, vars: &BTreeMap<&str, States>| {
+                if let Some(&set) = vars.get(field) {
+                    return Ok(set);
+                }
+                match field.parse::<usize>() {
+                    Ok(state) if state < states => Ok(singleton(state as u8)),
+                    Ok(_) => Err(error(ParseErrorKind::InvalidState)),
+                    Err(_) if field.starts_with(|c: char| c.is_ascii_digit()) => {
+                        Err(error(ParseErrorKind::InvalidState))
+                    }
+                    Err(_) => Err(error(ParseErrorKind::UndefinedVariable)),
+                }
+            };
+
+            if let Some(var) = text
+                .strip_prefix("var")
+                .filter(|var| var.starts_with(char::is_whitespace))
+            {
+                let (name, values) = var.split_once('=').ok_or(error(ParseErrorKind::Syntax))?;
+                let values = values
+                    .trim()
+                    .strip_prefix('{')
+                    .and_then(|values| values.strip_suffix('}'))
+                    .ok_or(error(ParseErrorKind::Syntax))?;
+
+                let mut set = States::default();
+                for value in values.split(',') {
+                    let other = parse_states(value.trim(), &vars)?;
+                    set.iter_mut().zip(other).for_each(|(a, b)| *a |= b);
+                }
+                vars.insert(name.trim(), set);
+                continue;
+            }
+
+            let fields = if text.contains(',') {
+                text.split(',').map(str::trim).collect::<Vec<_>>()
+            } else {
+                text.char_indices()
+                    .filter(|(_, c)| !c.is_whitespace())
+                    .map(|(i, c)| &text[i..i + c.len_utf8()])
+                    .collect()
+            };
+            if fields.len() != neighborhood.count() + 2 {
+                return Err(error(ParseErrorKind::WrongArity));
+            }
+
+            // variables appearing more than once in a transition are bound,
+            // so expand them into one transition per value.
+            let (inputs, output) = fields.split_at(fields.len() - 1);
+            let bound = fields
+                .iter()
+                .filter(|field| vars.contains_key(*field))
+                .filter(|field| fields.iter().filter(|other| other == field).count() > 1)
+                .fold(Vec::new(), |mut bound, &field| {
+                    if !bound.contains(&field) {
+                        bound.push(field);
+                    }
+                    bound
+                });
+            if vars.contains_key(output[0]) && !bound.contains(&output[0]) {
+                return Err(error(ParseErrorKind::UndefinedVariable));
+            }
+
+            let mut bindings = vec![BTreeMap::new()];
+            for &var in &bound {
+                bindings = bindings
+                    .into_iter()
+                    .flat_map(|binding| {
+                        members(vars[var]).map(move |state| {
+                            let mut binding = binding.clone();
+                            binding.insert(var, state);
+                            binding
+                        })
+                    })
+                    .collect();
+            }
+
+            for binding in bindings {
+                let resolve = |field: &str| match binding.get(field) {
+                    Some(&state) => Ok(singleton(state)),
+                    None => parse_states(field, &vars),
+                };
+
+                let inputs = inputs
+                    .iter()
+                    .map(|field| resolve(field))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let output = members(resolve(output[0])?)
+                    .next()
+                    .ok_or(error(ParseErrorKind::InvalidState))?;
+
+                let (center, neighbors) = inputs.split_first().expect("inputs are not empty");
+                for neighbors in symmetric(neighbors, symmetries) {
+                    let mut inputs = vec![*center];
+                    inputs.extend(neighbors);
+                    if seen.insert((inputs.clone(), output)) {
+                        transitions.push((inputs, output));
+                    }
+                }
+            }
+        }
+
+        let states = states.ok_or(ParseError {
+            line: 0,
+            kind: ParseErrorKind::InvalidStates,
+        })?;
+
+        let words = transitions.len().div_ceil(64);
+        let mut masks = vec![0; (neighborhood.count() + 1) * states * words];
+        for (i, (inputs, _)) in transitions.iter().enumerate() {
+            for (position, &set) in inputs.iter().enumerate() {
+                for state in members(set) {
+                    masks[(position * states + state as usize) * words + i / 64] |= 1 << (i % 64);
+                }
+            }
+        }
+
+        Ok(Self {
+            name,
+            states,
+            neighborhood,
+            outputs: transitions.into_iter().map(|(_, output)| output).collect(),
+            masks,
+        })
+    }
+
+    /// Returns the name of this rule, if given by a `@RULE` line.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the number of states of this rule.
+    #[must_use]
+    pub const fn states(&self) -> usize {
+        self.states
+    }
+
+    /// Returns the neighborhood of this rule.
+    #[must_use]
+    pub const fn neighborhood(&self) -> Neighborhood {
+        self.neighborhood
+    }
+
+    ///
+    /// Calculate the next state of a cell, given its state and those of its
+    /// neighbors in the order of [`Neighborhood::offsets()`].
+    ///
+    /// If no transition matches, the state is unchanged.
+    ///
+    #[must_use]
+    pub fn next_state(&self, center: u8, neighbors: &[u8]) -> u8 {
+        let words = self.outputs.len().div_ceil(64);
+        let inputs = [center].into_iter().chain(neighbors.iter().copied());
+        if neighbors.len() != self.neighborhood.count()
+            || inputs.clone().any(|state| state as usize >= self.states)
+        {
+            return center;
+        }
+
+        (0..words)
+            .find_map(|word| {
+                let matches = inputs
+                    .clone()
+                    .enumerate()
+                    .fold(!0, |acc, (position, state)| {
+                        acc & self.masks[(position * self.states + state as usize) * words + word]
+                    });
+                (matches != 0).then(|| self.outputs[word * 64 + matches.trailing_zeros() as usize])
+            })
+            .unwrap_or(center)
+    }
+
+    ///
+    /// Calculate the next generation of a grid of states.
+    ///
+    /// Cells outside of the grid are in state 0.
+    ///
+    #[must_use]
+    pub fn step<const W: usize, const H: usize>(&self, states: &[[u8; W]; H]) -> [[u8; W]; H] {
+        step_states(self.neighborhood, states, |center, neighbors| {
+            self.next_state(center, neighbors)
+        })
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H, State> {
+    /// Calculates the next generation of this grid according to a [`RuleTable`].
+    #[must_use]
+    pub fn step_table(&self, table: &RuleTable) -> Self {
+        self.step_states(|states| table.step(states))
+    }
+}
+
+fn singleton(state: u8) -> States {
+    let mut set = States::default();
+    set[state as usize / 64] |= 1 << (state % 64);
+    set
+}
+
+fn members(set: States) -> impl Iterator<Item = u8> + Clone {
+    (0..=u8::MAX).filter(move |&state| set[state as usize / 64] & (1 << (state % 64)) != 0)
+}
+
+/// Returns the distinct permutations of `neighbors` under some symmetries.
+fn symmetric(neighbors: &[States], symmetries: Symmetries) -> Vec<Vec<States>> {
+    let n = neighbors.len();
+    let rotate = |k: usize| (0..n).map(|i| neighbors[(i + k) % n]).collect::<Vec<_>>();
+    let reflect = |v: Vec<States>| (0..n).map(|i| v[(n - i) % n]).collect::<Vec<_>>();
+
+    let (step, reflected) = match symmetries {
+        Symmetries::None => (n, false),
+        Symmetries::Rotate4 => (n / 4, false),
+        Symmetries::Rotate4Reflect => (n / 4, true),
+        Symmetries::Rotate8 => (1, false),
+        Symmetries::Rotate8Reflect => (1, true),
+        Symmetries::ReflectHorizontal => (n, true),
+        Symmetries::Permute => return permutations(neighbors),
+    };
+
+    let mut all = Vec::new();
+    for rotated in (0..n).step_by(step).map(rotate) {
+        if reflected {
+            all.push(reflect(rotated.clone()));
+        }
+        all.push(rotated);
+    }
+
+    all.sort_unstable();
+    all.dedup();
+    all
+}
+
+/// Returns the distinct permutations of `items`, in lexicographic order.
+fn permutations(items: &[States]) -> Vec<Vec<States>> {
+    let mut current = items.to_vec();
+    current.sort_unstable();
+
+    let mut all = vec![current.clone()];
+    loop {
+        let Some(i) = current.windows(2).rposition(|pair| pair[0] < pair[1]) else {
+            break all;
+        };
+        let j = current
+            .iter()
+            .rposition(|item| *item > current[i])
+            .expect("a larger item exists");
+        current.swap(i, j);
+        current[i + 1..].reverse();
+        all.push(current.clone());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{cell::Cell, math::Coord};
+
+    const LIFE: &str = "
+@RULE LifeTable
+
+@TABLE
+n_states:2
+neighborhood:Moore
+symmetries:permute
+var a={0,1}
+var b={a}
+var c={0,1}
+var d={0,1}
+var e={0,1}
+var f={0,1}
+var g={0,1}
+var h={0,1}
+0,1,1,1,0,0,0,0,0,1 # births
+1,1,1,0,0,0,0,0,0,1
+1111000001          # compact syntax
+1,a,b,c,d,e,f,g,h,0
+
+@COLORS
+1 255 255 255
+";
+
+    const WIREWORLD: &str = "
+n_states:4
+neighborhood:Moore
+symmetries:permute
+var a={0,1,2,3}
+var b={a}
+var c={a}
+var d={a}
+var e={a}
+var f={a}
+var g={a}
+var h={a}
+var i={0,2,3}
+var j={i}
+var k={i}
+var l={i}
+var m={i}
+var n={i}
+var o={i}
+1,a,b,c,d,e,f,g,h,2
+2,a,b,c,d,e,f,g,h,3
+3,1,i,j,k,l,m,n,o,1
+3,1,1,i,j,k,l,m,n,1
+";
+
+    #[test]
+    fn life() {
+        let table = RuleTable::parse(LIFE).unwrap();
+        assert_eq!(table.name(), Some("LifeTable"));
+        assert_eq!(table.states(), 2);
+
+        let mut expected = Grid::<8, 8>::new();
+        for coord in [
+            Coord(1, 0),
+            Coord(2, 1),
+            Coord(0, 2),
+            Coord(1, 2),
+            Coord(2, 2),
+        ] {
+            expected[coord] = Cell::Alive;
+        }
+
+        let mut grid = Grid {
+            cells: expected.cells.map(|row| row.map(State::from)),
+            generation: 0,
+        };
+        for _ in 0..8 {
+            grid = grid.step_table(&table);
+            expected = expected.step();
+            assert_eq!(grid.cells.map(|row| row.map(State::cell)), expected.cells);
+        }
+    }
+
+    #[test]
+    fn wireworld() {
+        let table = RuleTable::parse(WIREWORLD).unwrap();
+        assert_eq!(table.name(), None);
+
+        let mut states = [[2, 1, 3, 3, 3]];
+        for expected in [[3, 2, 1, 3, 3], [3, 3, 2, 1, 3], [3, 3, 3, 2, 1]] {
+            states = table.step(&states);
+            assert_eq!(states, [expected]);
+        }
+
+        // an electron going around a loop of wire on a grid
+        let mut grid = Grid::<4, 3, State>::new();
+        for coord in [Coord(1, 0), Coord(2, 0), Coord(0, 1), Coord(3, 1)] {
+            grid[coord] = State(3);
+        }
+        grid[Coord(2, 2)] = State(2);
+        grid[Coord(1, 2)] = State(1);
+        for _ in 0..3 {
+            grid = grid.step_table(&table);
+        }
+        assert_eq!(grid.generation, 3);
+        assert_eq!(grid[Coord(2, 0)], State(1));
+        assert_eq!(grid[Coord(1, 0)], State(2));
+        assert_eq!(grid[Coord(1, 2)], State(3));
+        assert_eq!(grid.population(), 6);
+    }
+
+    #[test]
+    fn bound_variables() {
+        let table = RuleTable::parse(
+            "n_states:3\nneighborhood:vonNeumann\nsymmetries:rotate4\nvar a={1,2}\n0,a,0,a,0,a",
+        )
+        .unwrap();
+
+        assert_eq!(table.next_state(0, &[2, 0, 2, 0]), 2);
+        assert_eq!(table.next_state(0, &[0, 1, 0, 1]), 1);
+        assert_eq!(table.next_state(0, &[1, 0, 2, 0]), 0);
+    }
+
+    #[test]
+    fn errors() {
+        let kind = |src| RuleTable::parse(src).unwrap_err().kind;
+        assert_eq!(kind("@RULE x\n@COLORS"), ParseErrorKind::MissingSection);
+        assert_eq!(kind("0,0,0,0,0,0,0,0,0,0"), ParseErrorKind::InvalidStates);
+        assert_eq!(
+            kind("n_states:2\n0,0,0,0,0,0,0,0,0,2"),
+            ParseErrorKind::InvalidState
+        );
+        assert_eq!(
+            kind("n_states:2\n0,0,0,0,0,0,0,0,0,x"),
+            ParseErrorKind::UndefinedVariable
+        );
+        assert_eq!(kind("n_states:2\n0,0,1"), ParseErrorKind::WrongArity);
+        assert_eq!(
+            kind("n_states:2\nneighborhood:hexagonal"),
+            ParseErrorKind::UnsupportedNeighborhood
+        );
+    }
+}
@@ -0,0 +1,289 @@
+//!
+//! [Golly rule trees](https://golly.sourceforge.io/Help/formats.html#tree).
+//!
+//! See [`RuleTree::parse()`] to load the `@TREE` section of a `.rule` file.
+//!
+
+use alloc::{string::String, vec::Vec};
+
+use super::{section, step_states, Neighborhood, ParseError, ParseErrorKind};
+use crate::{cell::State, grid::Grid};
+
+///
+/// A compiled Golly rule tree.
+///
+/// The tree is flattened into a single array, where each node is a run of
+/// one entry per state: either the offset of a child node, or a resulting
+/// state for the nodes at the lowest level. Evaluating a cell then takes one
+/// array lookup per neighbor.
+///
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RuleTree {
+    name: Option<String>,
+    states: usize,
+    neighborhood: Neighborhood,
+    nodes: Vec<u32>,
+    root: u32,
+}
+
+impl RuleTree {
+    ///
+    /// Parse a rule tree, either from the `@TREE` section of a `.rule` file,
+    /// or from a bare `.tree` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if the rule tree is malformed or uses an
+    /// unsupported feature.
+    ///
+    pub fn parse(src: &str) -> Result<Self, ParseError> {
+        let name = src
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("@RULE"))
+            .map(|name| name.trim().into());
+
+        let mut states = None;
+        let mut neighborhood = None;
+        let mut expected_nodes = None;
+        // the level of each node, and its offset into `nodes`.
+        let mut levels = Vec::<(usize, u32)>::new();
+        let mut nodes = Vec::new();
+
+        for (line, text) in section(src, "@TREE")? {
+            let error = |kind| ParseError { line, kind };
+
+            if let Some((key, value)) = text.split_once('=') {
+                let value = value.trim().parse::<usize>().ok();
+                match key.trim() {
+                    "num_states" => {
+                        states = value.filter(|n| (2..=256).contains(n));
+                        states.ok_or(error(ParseErrorKind::InvalidStates))?;
+                    }
+                    "num_neighbors" => {
+                        neighborhood = match value {
+                            Some(8) => Some(Neighborhood::Moore),
+                            Some(4) => Some(Neighborhood::VonNeumann),
+                            _ => return Err(error(ParseErrorKind::UnsupportedNeighborhood)),
+                        }
+                    }
+                    "num_nodes" => {
+                        expected_nodes = Some(value.ok_or(error(ParseErrorKind::Syntax))?);
+                    }
+                    _ => return Err(error(ParseErrorKind::Syntax)),
+                }
+                continue;
+            }
+
+            let states = states.ok_or(error(ParseErrorKind::InvalidStates))?;
+            let neighborhood =
+                neighborhood.ok_or(error(ParseErrorKind::UnsupportedNeighborhood))?;
+
+            let mut fields = text.split_whitespace().map(str::parse::<usize>);
+            let level = match fields.next() {
+                Some(Ok(level)) if (1..=neighborhood.count() + 1).contains(&level) => level,
+                _ => return Err(error(ParseErrorKind::InvalidNode)),
+            };
+
+            let offset = nodes.len() as u32;
+            for field in fields.by_ref().take(states) {
+                let entry = match field {
+                    Ok(state) if level == 1 && state < states => state as u32,
+                    Ok(child) if level > 1 => match levels.get(child) {
+                        Some(&(child_level, offset)) if child_level == level - 1 => offset,
+                        _ => return Err(error(ParseErrorKind::InvalidNode)),
+                    },
+                    Ok(_) => return Err(error(ParseErrorKind::InvalidState)),
+                    Err(_) => return Err(error(ParseErrorKind::Syntax)),
+                };
+                nodes.push(entry);
+            }
+            if nodes.len() != offset as usize + states || fields.next().is_some() {
+                return Err(error(ParseErrorKind::WrongArity));
+            }
+
+            levels.push((level, offset));
+        }
+
+        let error = |kind| ParseError { line: 0, kind };
+        let states = states.ok_or(error(ParseErrorKind::InvalidStates))?;
+        let neighborhood = neighborhood.ok_or(error(ParseErrorKind::UnsupportedNeighborhood))?;
+        let root = match levels.last() {
+            Some(&(level, offset))
+                if level == neighborhood.count() + 1
+                    && expected_nodes.is_none_or(|n| n == levels.len()) =>
+            {
+                offset
+            }
+            _ => return Err(error(ParseErrorKind::InvalidNode)),
+        };
+
+        Ok(Self {
+            name,
+            states,
+            neighborhood,
+            nodes,
+            root,
+        })
+    }
+
+    /// Returns the name of this rule, if given by a `@RULE` line.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the number of states of this rule.
+    #[must_use]
+    pub const fn states(&self) -> usize {
+        self.states
+    }
+
+    /// Returns the neighborhood of this rule.
+    #[must_use]
+    pub const fn neighborhood(&self) -> Neighborhood {
+        self.neighborhood
+    }
+
+    ///
+    /// Calculate the next state of a cell, given its state and those of its
+    /// neighbors in the order of [`Neighborhood::offsets()`].
+    ///
+    #[must_use]
+    pub fn next_state(&self, center: u8, neighbors: &[u8]) -> u8 {
+        // rule trees take the neighbors in a different order than ours.
+        let order: &[usize] = match self.neighborhood {
+            Neighborhood::Moore => &[7, 1, 5, 3, 0, 6, 2, 4],
+            Neighborhood::VonNeumann => &[0, 3, 1, 2],
+        };
+
+        if neighbors.len() != order.len()
+            || neighbors
+                .iter()
+                .chain([&center])
+                .any(|&state| state as usize >= self.states)
+        {
+            return center;
+        }
+
+        let node = order.iter().fold(self.root, |node, &i| {
+            self.nodes[(node + u32::from(neighbors[i])) as usize]
+        });
+        self.nodes[(node + u32::from(center)) as usize] as u8
+    }
+
+    ///
+    /// Calculate the next generation of a grid of states.
+    ///
+    /// Cells outside of the grid are in state 0.
+    ///
+    #[must_use]
+    pub fn step<const W: usize, const H: usize>(&self, states: &[[u8; W]; H]) -> [[u8; W]; H] {
+        step_states(self.neighborhood, states, |center, neighbors| {
+            self.next_state(center, neighbors)
+        })
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H, State> {
+    /// Calculates the next generation of this grid according to a [`RuleTree`].
+    #[must_use]
+    pub fn step_tree(&self, tree: &RuleTree) -> Self {
+        self.step_states(|states| tree.step(states))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{cell::Cell, math::Coord};
+
+    const LIFE: &str = "
+@RULE LifeTree
+
+@TREE
+num_states=2
+num_neighbors=8
+num_nodes=32
+1 0 0
+2 0 0
+1 0 1
+2 0 2
+3 1 3
+1 1 1
+2 2 5
+3 3 6
+4 4 7
+2 5 0
+3 6 9
+4 7 10
+5 8 11
+3 9 1
+4 10 13
+5 11 14
+6 12 15
+3 1 1
+4 13 17
+5 14 18
+6 15 19
+7 16 20
+4 17 17
+5 18 22
+6 19 23
+7 20 24
+8 21 25
+5 22 22
+6 23 27
+7 24 28
+8 25 29
+9 26 30
+";
+
+    #[test]
+    fn life() {
+        let tree = RuleTree::parse(LIFE).unwrap();
+        assert_eq!(tree.name(), Some("LifeTree"));
+        assert_eq!(tree.states(), 2);
+
+        let mut expected = Grid::<8, 8>::new();
+        for coord in [
+            Coord(1, 0),
+            Coord(2, 1),
+            Coord(0, 2),
+            Coord(1, 2),
+            Coord(2, 2),
+        ] {
+            expected[coord] = Cell::Alive;
+        }
+
+        let mut grid = Grid {
+            cells: expected.cells.map(|row| row.map(State::from)),
+            generation: 0,
+        };
+        for _ in 0..8 {
+            grid = grid.step_tree(&tree);
+            expected = expected.step();
+            assert_eq!(grid.cells.map(|row| row.map(State::cell)), expected.cells);
+        }
+    }
+
+    #[test]
+    fn von_neumann() {
+        // a cell becomes the state of its north neighbor.
+        let tree = RuleTree::parse(
+            "num_states=3\nnum_neighbors=4\nnum_nodes=5\n\
+             1 0 0 0\n1 1 1 1\n1 2 2 2\n2 0 0 0\n5 3 3 3",
+        );
+        assert_eq!(tree.unwrap_err().kind, ParseErrorKind::InvalidNode);
+
+        let tree = RuleTree::parse(
+            "num_states=3\nnum_neighbors=4\n\
+             1 0 0 0\n1 1 1 1\n1 2 2 2\n\
+             2 0 0 0\n3 3 3 3\n4 4 4 4\n\
+             2 1 1 1\n3 6 6 6\n4 7 7 7\n\
+             2 2 2 2\n3 9 9 9\n4 10 10 10\n\
+             5 5 8 11",
+        )
+        .unwrap();
+        assert_eq!(tree.step(&[[2, 1], [0, 0]]), [[0, 0], [2, 1]]);
+    }
+}
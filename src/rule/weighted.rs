@@ -0,0 +1,212 @@
+//!
+//! [Weighted Life](https://conwaylife.com/wiki/Weighted_Life) rules, where
+//! each neighbor counts with its own integer weight.
+//!
+//! See [`Weighted`].
+//!
+
+use core::{fmt, str::FromStr};
+
+use super::{convolution::ConvolutionRule, Neighborhood, ParseError, ParseErrorKind, Topology};
+use crate::{cell::Cell, grid::Grid};
+
+///
+/// A two-state rule on the Moore neighborhood, where each neighbor and the
+/// cell itself have an integer weight, and a cell's next state depends on
+/// the sum of the weights of its live neighbors.
+///
+/// Bit `n` of `birth` and `survival` is set if a dead cell is born, or a
+/// live cell survives, with a weighted sum of `n`. Sums outside of `0..128`
+/// always lead to a dead cell.
+///
+/// Rules are written in the MCell notation used by Golly, such as
+/// `NW1,NN2,NE1,WW2,ME0,EE2,SW1,SS2,SE1,HI2,RS5,RS6,RB6`.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Weighted {
+    /// The weights of the neighbors, in the order of
+    /// [`Neighborhood::Moore`]: N, NE, E, SE, S, SW, W, NW.
+    pub weights: [i8; 8],
+    /// The weight of the cell itself.
+    pub center: i8,
+    pub birth: u128,
+    pub survival: u128,
+}
+
+impl Weighted {
+    /// Construct a new [`Weighted`] rule.
+    #[must_use]
+    pub const fn new(weights: [i8; 8], center: i8, birth: u128, survival: u128) -> Self {
+        Self {
+            weights,
+            center,
+            birth,
+            survival,
+        }
+    }
+
+    ///
+    /// Returns the taps of this rule, as the offsets and weights of each
+    /// neighbor and then of the cell itself, for [`Grid::step_convolved()`].
+    ///
+    #[must_use]
+    pub fn taps(&self) -> [(isize, isize, i32); 9] {
+        let offsets = Neighborhood::Moore.offsets();
+        core::array::from_fn(|i| match offsets.get(i) {
+            Some(&(dx, dy)) => (dx, dy, i32::from(self.weights[i])),
+            None => (0, 0, i32::from(self.center)),
+        })
+    }
+}
+
+impl ConvolutionRule<Cell, i32> for Weighted {
+    fn next(&self, cell: Cell, sum: i32) -> Cell {
+        let mask = match cell {
+            Cell::Alive => self.survival,
+            Cell::Dead => self.birth,
+        };
+        Cell::from(u32::try_from(sum).is_ok_and(|sum| sum < 128 && mask & 1 << sum != 0))
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Calculates the next generation of this grid according to the weighted
+    /// `rule`, with the edges of the grid behaving according to `topology`.
+    ///
+    #[must_use]
+    pub fn step_weighted(&self, rule: &Weighted, topology: Topology) -> Self {
+        self.step_convolved(&rule.taps(), topology, rule)
+    }
+}
+
+/// The names of the weights in MCell notation, in the order of [`Weighted::weights`].
+const NAMES: [&str; 8] = ["NN", "NE", "EE", "SE", "SS", "SW", "WW", "NW"];
+
+impl FromStr for Weighted {
+    type Err = ParseError;
+
+    ///
+    /// Parse a rule in MCell notation: comma-separated weights `NW`, `NN`,
+    /// `NE`, `WW`, `ME`, `EE`, `SW`, `SS` and `SE`, survival sums `RS` and
+    /// birth sums `RB`, in any order and case. Unspecified weights are 0.
+    ///
+    /// A number of states `HI` other than 2 is rejected.
+    ///
+    fn from_str(src: &str) -> Result<Self, ParseError> {
+        let error = |kind| ParseError { line: 1, kind };
+        let mut rule = Self::default();
+
+        for field in src.trim().split(',').map(str::trim) {
+            let (name, value) = field
+                .split_at_checked(2)
+                .ok_or(error(ParseErrorKind::Syntax))?;
+            let name = [0, 1].map(|i| name.as_bytes()[i].to_ascii_uppercase());
+            let weight = || {
+                value
+                    .parse::<i8>()
+                    .map_err(|_| error(ParseErrorKind::Syntax))
+            };
+            let sum = || {
+                value
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|&sum| sum < 128)
+                    .map(|sum| 1 << sum)
+                    .ok_or(error(ParseErrorKind::Syntax))
+            };
+
+            match &name {
+                b"ME" => rule.center = weight()?,
+                b"RB" => rule.birth |= sum()?,
+                b"RS" => rule.survival |= sum()?,
+                b"HI" if value == "2" => {}
+                b"HI" => return Err(error(ParseErrorKind::InvalidStates)),
+                _ => {
+                    let i = NAMES
+                        .iter()
+                        .position(|other| other.as_bytes() == name)
+                        .ok_or(error(ParseErrorKind::Syntax))?;
+                    rule.weights[i] = weight()?;
+                }
+            }
+        }
+
+        Ok(rule)
+    }
+}
+
+impl fmt::Display for Weighted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, i) in [("NW", 7), ("NN", 0), ("NE", 1), ("WW", 6)] {
+            write!(f, "{name}{},", self.weights[i])?;
+        }
+        write!(f, "ME{},", self.center)?;
+        for (name, i) in [("EE", 2), ("SW", 5), ("SS", 4), ("SE", 3)] {
+            write!(f, "{name}{},", self.weights[i])?;
+        }
+        f.write_str("HI2")?;
+
+        for (name, mask) in [("RS", self.survival), ("RB", self.birth)] {
+            for sum in (0..128).filter(|sum| mask & 1 << sum != 0) {
+                write!(f, ",{name}{sum}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use std::format;
+
+    use super::*;
+    use crate::{math::Coord, rule::LifeLike};
+
+    #[test]
+    fn step_weighted() {
+        let mut grid = Grid::<6, 6>::new();
+        for coord in [
+            Coord(1, 0),
+            Coord(2, 1),
+            Coord(0, 2),
+            Coord(1, 2),
+            Coord(2, 2),
+        ] {
+            grid[coord] = Cell::Alive;
+        }
+
+        let conway = Weighted::new([1; 8], 0, 1 << 3, 1 << 2 | 1 << 3);
+        assert_eq!(
+            grid.step_weighted(&conway, Topology::Torus).cells,
+            grid.step_on(&LifeLike::CONWAY, Topology::Torus).cells
+        );
+
+        // only orthogonal neighbors count, and a cell with a live one is born
+        let rule = Weighted::new([1, 0, 1, 0, 1, 0, 1, 0], 0, 1 << 1, 0);
+        let next = grid.step_weighted(&rule, Topology::Bounded);
+        assert_eq!(next[Coord(0, 0)], Cell::Alive);
+        assert_eq!(next[Coord(1, 1)], Cell::Dead);
+    }
+
+    #[test]
+    fn parse() {
+        let src = "NW1,NN2,NE1,WW2,ME0,EE2,SW1,SS2,SE1,HI2,RS5,RS6,RB6";
+        let rule = src.parse::<Weighted>().unwrap();
+        assert_eq!(rule.weights, [2, 1, 2, 1, 2, 1, 2, 1]);
+        assert_eq!(rule.survival, 1 << 5 | 1 << 6);
+        assert_eq!(rule.birth, 1 << 6);
+        assert_eq!(format!("{rule}"), src);
+
+        assert_eq!("nn-3, rb0".parse::<Weighted>().unwrap().weights[0], -3);
+        assert_eq!(
+            "NN1,HI3".parse::<Weighted>().unwrap_err().kind,
+            ParseErrorKind::InvalidStates
+        );
+        for src in ["", "XX1", "NN", "RB128", "NN1,"] {
+            assert!(src.parse::<Weighted>().is_err(), "{src}");
+        }
+    }
+}
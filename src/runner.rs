@@ -0,0 +1,189 @@
+//!
+//! Headless batch runs of pattern files, as done by the `lifeless` binary.
+//!
+//! See [`load()`] and [`run()`].
+//!
+
+use core::fmt;
+
+use crate::{
+    grid::Grid,
+    io::{rle::Rle, SyntaxError},
+    math::Coord,
+    pattern::{Pattern, PatternBuf},
+    rule::{LifeLike, ParseError, Topology},
+    sim::StepStats,
+};
+
+///
+/// A loaded pattern file.
+///
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Input {
+    pub pattern: PatternBuf,
+    /// The rule given by the file, if any.
+    pub rule: Option<LifeLike>,
+}
+
+///
+/// An error encountered while loading a pattern file.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LoadError {
+    /// The pattern is malformed.
+    Syntax(SyntaxError),
+    /// The rule is malformed, or is not a Life-like rule.
+    Rule(ParseError),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax(err) => write!(f, "invalid pattern: {err}"),
+            Self::Rule(_) => f.write_str("unsupported rule"),
+        }
+    }
+}
+
+impl core::error::Error for LoadError {}
+
+///
+/// Load a pattern file, either in RLE format, or in plaintext format if it
+/// has no RLE header line.
+///
+/// # Errors
+///
+/// Returns a [`LoadError`] if the pattern or its rule is malformed.
+///
+pub fn load(src: &str) -> Result<Input, LoadError> {
+    let is_rle = src
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('!'))
+        .is_some_and(|line| line.starts_with('x') && line.contains('='));
+
+    if is_rle {
+        let rle = Rle::parse(src).map_err(LoadError::Syntax)?;
+        let rule = rle
+            .rule
+            .map(|rule| rule.parse().map_err(LoadError::Rule))
+            .transpose()?;
+        Ok(Input {
+            pattern: rle.pattern,
+            rule,
+        })
+    } else {
+        Ok(Input {
+            pattern: PatternBuf::from_plaintext(src).map_err(LoadError::Syntax)?,
+            rule: None,
+        })
+    }
+}
+
+///
+/// Options for [`run()`].
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Options {
+    pub rule: LifeLike,
+    pub topology: Topology,
+    pub generations: u64,
+}
+
+///
+/// A summary of a [`run()`].
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Summary {
+    pub generations: u64,
+    pub population: usize,
+    /// The total number of births over the run.
+    pub births: u64,
+    /// The total number of deaths over the run.
+    pub deaths: u64,
+    /// The largest population over the run, including the initial one.
+    pub max_population: usize,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "generations: {}", self.generations)?;
+        writeln!(f, "population: {}", self.population)?;
+        writeln!(f, "max population: {}", self.max_population)?;
+        writeln!(f, "births: {}", self.births)?;
+        writeln!(f, "deaths: {}", self.deaths)
+    }
+}
+
+///
+/// Run `pattern`, centered on a new grid, according to `options`.
+///
+/// Returns `None` if the pattern does not fit in the grid.
+///
+#[must_use]
+pub fn run<const W: usize, const H: usize>(
+    pattern: &Pattern,
+    options: &Options,
+) -> Option<(Grid<W, H>, Summary)> {
+    let Coord(width, height) = pattern.extents();
+    if width > W || height > H {
+        return None;
+    }
+
+    let mut grid = Grid::new();
+    grid.paste(pattern, Coord((W - width) / 2, (H - height) / 2));
+
+    let mut summary = Summary {
+        max_population: grid.population(),
+        ..Summary::default()
+    };
+    for _ in 0..options.generations {
+        let next = grid.step_on(&options.rule, options.topology);
+        let stats = StepStats::between(&grid, &next);
+        summary.births += stats.births as u64;
+        summary.deaths += stats.deaths as u64;
+        summary.max_population = summary.max_population.max(stats.population);
+        grid = next;
+    }
+    summary.generations = grid.generation;
+    summary.population = grid.population();
+
+    Some((grid, summary))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn run() {
+        let input = load("#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!").unwrap();
+        assert_eq!(input.rule, Some(LifeLike::CONWAY));
+
+        let options = Options {
+            generations: 8,
+            ..Options::default()
+        };
+        let (grid, summary) = super::run::<8, 8>(&input.pattern.as_pattern(), &options).unwrap();
+        assert_eq!(grid.generation, 8);
+        assert_eq!(summary.population, 5);
+        assert_eq!(summary.births, summary.deaths);
+        assert!(super::run::<2, 2>(&input.pattern.as_pattern(), &options).is_none());
+
+        let input = load("!Name: Blinker\nOOO").unwrap();
+        assert_eq!(input.rule, None);
+        let options = Options {
+            topology: Topology::Torus,
+            generations: 1,
+            ..options
+        };
+        let (_, summary) = super::run::<3, 3>(&input.pattern.as_pattern(), &options).unwrap();
+        assert_eq!(summary.population, 9);
+        assert_eq!(summary.births, 6);
+
+        assert!(matches!(
+            load("x = 1, rule = Wireworld\no!"),
+            Err(LoadError::Rule(_))
+        ));
+    }
+}
@@ -0,0 +1,208 @@
+//!
+//! Searching random soups for the objects they leave behind.
+//!
+//! See [`Search`].
+//!
+
+use crate::{
+    analysis::{census, Census},
+    cell::Cell,
+    grid::Grid,
+    math::Coord,
+    pattern::Orientation,
+    random::Rng,
+    rule::Life,
+    sim::{Simulation, Stability},
+};
+
+///
+/// The symmetry of the soups generated by a [`Search`].
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Symmetry {
+    /// No symmetry.
+    #[default]
+    Asymmetric,
+    /// Mirrored left to right.
+    Mirror,
+    /// Unchanged by a half turn.
+    Rotate180,
+    /// Unchanged by a quarter turn.
+    Rotate90,
+    /// Unchanged by any rotation or reflection.
+    Full,
+}
+
+impl Symmetry {
+    /// Returns the orientations a soup of this symmetry is unchanged by.
+    #[must_use]
+    pub const fn orientations(self) -> &'static [Orientation] {
+        use Orientation::*;
+
+        match self {
+            Self::Asymmetric => &[Identity],
+            Self::Mirror => &[Identity, FlipHorizontal],
+            Self::Rotate180 => &[Identity, Rotate180],
+            Self::Rotate90 => &[Identity, Rotate90, Rotate180, Rotate270],
+            Self::Full => &Orientation::ALL,
+        }
+    }
+}
+
+///
+/// The results of a [`Search`].
+///
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Summary {
+    /// The number of soups run.
+    pub soups: usize,
+    /// The number of soups that did not stabilize in time, and were not
+    /// counted in the census.
+    pub unstable: usize,
+    /// The objects left behind by the soups that stabilized.
+    pub census: Census,
+}
+
+///
+/// A search which runs random soups in the middle of a grid until they
+/// stabilize, and takes a [census] of what they leave behind.
+///
+/// Soups are `soup_size` cells square, and each cell is alive with
+/// probability `density`. Objects that escape the soup are stopped by the
+/// edges of the grid, so it should be much larger than the soups.
+///
+#[derive(Clone, Debug)]
+pub struct Search<R> {
+    pub rng: R,
+    pub symmetry: Symmetry,
+    pub density: f64,
+    pub soup_size: usize,
+    /// The number of generations a soup may take to stabilize.
+    pub max_gens: u64,
+}
+
+impl<R: Rng> Search<R> {
+    ///
+    /// Construct a new [`Search`] of asymmetric 16 by 16 soups of density
+    /// 0.5, which may take up to 4000 generations to stabilize.
+    ///
+    #[must_use]
+    pub fn new(rng: R) -> Self {
+        Self {
+            rng,
+            symmetry: Symmetry::default(),
+            density: 0.5,
+            soup_size: 16,
+            max_gens: 4000,
+        }
+    }
+
+    ///
+    /// Generate a random soup in the middle of a grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the soup does not fit in the grid.
+    ///
+    pub fn soup<const W: usize, const H: usize>(&mut self) -> Grid<W, H> {
+        let size = self.soup_size;
+        assert!(size <= W && size <= H, "soup does not fit in the grid");
+
+        let extents = Coord(size, size);
+        let offset = Coord((W - size) / 2, (H - size) / 2);
+        let mut grid = Grid::new();
+
+        // each cell takes the state of the first cell of its orbit under
+        // the symmetry, in row-major order.
+        for y in 0..size {
+            for x in 0..size {
+                let Coord(x0, y0) = self
+                    .symmetry
+                    .orientations()
+                    .iter()
+                    .map(|orientation| orientation.transform(Coord(x, y), extents))
+                    .min_by_key(|&Coord(x, y)| (y, x))
+                    .expect("symmetry has orientations");
+
+                grid[offset + Coord(x, y)] = if (x0, y0) == (x, y) {
+                    Cell::from(self.rng.chance(self.density))
+                } else {
+                    grid[offset + Coord(x0, y0)]
+                };
+            }
+        }
+
+        grid
+    }
+
+    ///
+    /// Run `soups` soups on grids of the given extents, calling `progress`
+    /// with the results so far after each of them.
+    ///
+    pub fn run<const W: usize, const H: usize>(
+        &mut self,
+        soups: usize,
+        mut progress: impl FnMut(&Summary),
+    ) -> Summary {
+        let mut summary = Summary::default();
+        for _ in 0..soups {
+            let mut sim = Simulation::new(self.soup::<W, H>(), Life);
+            match sim.run_until_stable(self.max_gens) {
+                Stability::Limit => summary.unstable += 1,
+                _ => summary.census.merge(&census(sim.grid())),
+            }
+
+            summary.soups += 1;
+            progress(&summary);
+        }
+
+        summary
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::SplitMix64;
+
+    #[test]
+    fn symmetric_soups() {
+        let mut search = Search::new(SplitMix64::new(1));
+        search.soup_size = 8;
+
+        for symmetry in [Symmetry::Mirror, Symmetry::Rotate90, Symmetry::Full] {
+            search.symmetry = symmetry;
+            let grid = search.soup::<10, 10>();
+            let soup = Coord(8, 8);
+
+            for y in 0..8 {
+                for x in 0..8 {
+                    for orientation in symmetry.orientations() {
+                        let image = orientation.transform(Coord(x, y), soup);
+                        assert_eq!(grid[Coord(x + 1, y + 1)], grid[image + Coord(1, 1)]);
+                    }
+                }
+            }
+        }
+
+        search.density = 0.0;
+        assert_eq!(search.soup::<10, 10>().population(), 0);
+    }
+
+    #[test]
+    fn run() {
+        let mut search = Search::new(SplitMix64::new(2));
+        search.soup_size = 8;
+        search.max_gens = 500;
+
+        let mut calls = 0;
+        let summary = search.run::<48, 48>(4, |summary| {
+            calls += 1;
+            assert_eq!(summary.soups, calls);
+        });
+
+        assert_eq!(summary.soups, 4);
+        assert!(summary.unstable < 4);
+        assert!(summary.census.total() > 0);
+    }
+}
@@ -0,0 +1,424 @@
+//!
+//! [`serde`] support for [`Cell`], [`Coord`] and [`Grid`].
+//!
+//! - [`Cell`]s are serialized as `0` or `1`.
+//! - [`Coord`]s are serialized as `(x, y)` tuples.
+//! - [`Grid`]s are serialized as a struct of their `generation` and `cells`,
+//!   where the cells are in the [`rows`] format for human-readable formats,
+//!   and in the [`nested`] format otherwise.
+//!
+//! To always use one of the formats, use its module with
+//! `#[serde(with = "...")]`:
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Save {
+//!     #[serde(with = "lifeless::serde::nested")]
+//!     grid: Grid<8, 8>,
+//! }
+//! ```
+//!
+
+use core::{
+    fmt::{self, Write},
+    marker::PhantomData,
+};
+
+use ::serde::{
+    de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeSeq, SerializeStruct},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    cell::{Cell, Charset},
+    grid::Grid,
+    math::Coord,
+};
+
+impl Serialize for Cell {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cell {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match u8::deserialize(deserializer)? {
+            0 => Ok(Self::Dead),
+            1 => Ok(Self::Alive),
+            n => Err(de::Error::invalid_value(
+                de::Unexpected::Unsigned(n.into()),
+                &"0 or 1",
+            )),
+        }
+    }
+}
+
+impl Serialize for Coord {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.0, self.1).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Coord {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <(usize, usize)>::deserialize(deserializer).map(|(x, y)| Self(x, y))
+    }
+}
+
+impl<const W: usize, const H: usize> Serialize for Grid<W, H> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let format = Format::for_serializer(&serializer);
+        serialize(self, serializer, format)
+    }
+}
+
+impl<'de, const W: usize, const H: usize> Deserialize<'de> for Grid<W, H> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let format = Format::for_deserializer(&deserializer);
+        deserialize(deserializer, format)
+    }
+}
+
+///
+/// Serialize a [`Grid`]'s cells as one string per row, where `#` is a live
+/// cell and `.` is a dead cell.
+///
+pub mod rows {
+    use super::{Deserializer, Format, Grid, Serializer};
+
+    /// Serialize a [`Grid`] in this format.
+    ///
+    /// # Errors
+    ///
+    /// Returns the serializer's error, if any.
+    pub fn serialize<S: Serializer, const W: usize, const H: usize>(
+        grid: &Grid<W, H>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        super::serialize(grid, serializer, Format::Rows)
+    }
+
+    /// Deserialize a [`Grid`] in this format.
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer's error, if any.
+    pub fn deserialize<'de, D: Deserializer<'de>, const W: usize, const H: usize>(
+        deserializer: D,
+    ) -> Result<Grid<W, H>, D::Error> {
+        super::deserialize(deserializer, Format::Rows)
+    }
+}
+
+///
+/// Serialize a [`Grid`]'s cells as nested arrays of rows of cells.
+///
+pub mod nested {
+    use super::{Deserializer, Format, Grid, Serializer};
+
+    /// Serialize a [`Grid`] in this format.
+    ///
+    /// # Errors
+    ///
+    /// Returns the serializer's error, if any.
+    pub fn serialize<S: Serializer, const W: usize, const H: usize>(
+        grid: &Grid<W, H>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        super::serialize(grid, serializer, Format::Nested)
+    }
+
+    /// Deserialize a [`Grid`] in this format.
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer's error, if any.
+    pub fn deserialize<'de, D: Deserializer<'de>, const W: usize, const H: usize>(
+        deserializer: D,
+    ) -> Result<Grid<W, H>, D::Error> {
+        super::deserialize(deserializer, Format::Nested)
+    }
+}
+
+const FIELDS: &[&str] = &["generation", "cells"];
+
+#[derive(Clone, Copy)]
+enum Format {
+    Rows,
+    Nested,
+}
+
+impl Format {
+    fn for_serializer<S: Serializer>(serializer: &S) -> Self {
+        if serializer.is_human_readable() {
+            Self::Rows
+        } else {
+            Self::Nested
+        }
+    }
+
+    fn for_deserializer<'de, D: Deserializer<'de>>(deserializer: &D) -> Self {
+        if deserializer.is_human_readable() {
+            Self::Rows
+        } else {
+            Self::Nested
+        }
+    }
+}
+
+fn serialize<S: Serializer, const W: usize, const H: usize>(
+    grid: &Grid<W, H>,
+    serializer: S,
+    format: Format,
+) -> Result<S::Ok, S::Error> {
+    let mut state = serializer.serialize_struct("Grid", FIELDS.len())?;
+    state.serialize_field("generation", &grid.generation)?;
+    state.serialize_field("cells", &Cells(&grid.cells, format))?;
+    state.end()
+}
+
+fn deserialize<'de, D: Deserializer<'de>, const W: usize, const H: usize>(
+    deserializer: D,
+    format: Format,
+) -> Result<Grid<W, H>, D::Error> {
+    deserializer.deserialize_struct("Grid", FIELDS, GridVisitor::<W, H>(format))
+}
+
+struct Cells<'a, const W: usize, const H: usize>(&'a [[Cell; W]; H], Format);
+
+impl<const W: usize, const H: usize> Serialize for Cells<'_, W, H> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(H))?;
+        for row in self.0 {
+            match self.1 {
+                Format::Rows => seq.serialize_element(&RowStr(row))?,
+                Format::Nested => seq.serialize_element(row.as_slice())?,
+            }
+        }
+        seq.end()
+    }
+}
+
+struct RowStr<'a, const W: usize>(&'a [Cell; W]);
+
+impl<const W: usize> Serialize for RowStr<'_, W> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<const W: usize> fmt::Display for RowStr<'_, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0
+            .iter()
+            .try_for_each(|cell| f.write_char(cell.to_char(Charset::default())))
+    }
+}
+
+struct GridVisitor<const W: usize, const H: usize>(Format);
+
+impl<'de, const W: usize, const H: usize> Visitor<'de> for GridVisitor<W, H> {
+    type Value = Grid<W, H>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a {W}x{H} grid")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let generation = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let cells = seq
+            .next_element_seed(CellsSeed(self.0))?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok(Grid { cells, generation })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let (mut generation, mut cells) = (None, None);
+        while let Some(key) = map.next_key::<Field>()? {
+            match key {
+                Field::Generation if generation.is_none() => generation = Some(map.next_value()?),
+                Field::Cells if cells.is_none() => {
+                    cells = Some(map.next_value_seed(CellsSeed(self.0))?);
+                }
+                Field::Generation => return Err(de::Error::duplicate_field("generation")),
+                Field::Cells => return Err(de::Error::duplicate_field("cells")),
+            }
+        }
+
+        Ok(Grid {
+            cells: cells.ok_or_else(|| de::Error::missing_field("cells"))?,
+            generation: generation.ok_or_else(|| de::Error::missing_field("generation"))?,
+        })
+    }
+}
+
+enum Field {
+    Generation,
+    Cells,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldVisitor;
+
+        impl Visitor<'_> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("`generation` or `cells`")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                match value {
+                    "generation" => Ok(Field::Generation),
+                    "cells" => Ok(Field::Cells),
+                    _ => Err(de::Error::unknown_field(value, FIELDS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct CellsSeed<const W: usize, const H: usize>(Format);
+
+impl<'de, const W: usize, const H: usize> DeserializeSeed<'de> for CellsSeed<W, H> {
+    type Value = [[Cell; W]; H];
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(ArrayVisitor::<H, _>(RowSeed::<W>(self.0), [Cell::Dead; W]))
+    }
+}
+
+#[derive(Clone, Copy)]
+struct RowSeed<const W: usize>(Format);
+
+impl<'de, const W: usize> DeserializeSeed<'de> for RowSeed<W> {
+    type Value = [Cell; W];
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        match self.0 {
+            Format::Rows => deserializer.deserialize_str(RowVisitor::<W>),
+            Format::Nested => {
+                deserializer.deserialize_seq(ArrayVisitor::<W, _>(PhantomData::<Cell>, Cell::Dead))
+            }
+        }
+    }
+}
+
+/// Deserializes a sequence of exactly `N` elements into an array.
+struct ArrayVisitor<const N: usize, T: Seed>(T, T::Element);
+
+/// A [`DeserializeSeed`] for array elements, independent of lifetimes.
+trait Seed: Copy {
+    type Element: Copy;
+}
+
+impl Seed for PhantomData<Cell> {
+    type Element = Cell;
+}
+
+impl<const W: usize> Seed for RowSeed<W> {
+    type Element = [Cell; W];
+}
+
+impl<'de, const N: usize, T> Visitor<'de> for ArrayVisitor<N, T>
+where
+    T: Seed + DeserializeSeed<'de, Value = T::Element>,
+{
+    type Value = [T::Element; N];
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a sequence of {N} elements")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut array = [self.1; N];
+        for (i, element) in array.iter_mut().enumerate() {
+            *element = seq
+                .next_element_seed(self.0)?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(N + 1, &self));
+        }
+        Ok(array)
+    }
+}
+
+struct RowVisitor<const W: usize>;
+
+impl<const W: usize> Visitor<'_> for RowVisitor<W> {
+    type Value = [Cell; W];
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a string of {W} `#` or `.` characters")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        if value.len() != W {
+            return Err(de::Error::invalid_length(value.len(), &self));
+        }
+
+        let mut row = [Cell::Dead; W];
+        for (cell, byte) in row.iter_mut().zip(value.bytes()) {
+            *cell = Cell::from_char(char::from(byte), Charset::default())
+                .ok_or_else(|| de::Error::invalid_value(de::Unexpected::Str(value), &self))?;
+        }
+        Ok(row)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rows() {
+        let mut grid = Grid::<3, 2>::new();
+        grid[Coord(0, 0)] = Cell::Alive;
+        grid[Coord(2, 1)] = Cell::Alive;
+        grid.generation = 7;
+
+        let json = serde_json::to_string(&grid).unwrap();
+        assert_eq!(json, r###"{"generation":7,"cells":["#..","..#"]}"###);
+
+        let de = serde_json::from_str::<Grid<3, 2>>(&json).unwrap();
+        assert_eq!((de.cells, de.generation), (grid.cells, grid.generation));
+
+        assert!(serde_json::from_str::<Grid<2, 2>>(&json).is_err());
+        assert!(serde_json::from_str::<Grid<3, 3>>(&json).is_err());
+    }
+
+    #[test]
+    fn nested() {
+        #[derive(Serialize, Deserialize)]
+        struct Save {
+            at: Coord,
+            #[serde(with = "super::nested")]
+            grid: Grid<2, 2>,
+        }
+
+        let mut grid = Grid::new();
+        grid[Coord(1, 0)] = Cell::Alive;
+
+        let json = serde_json::to_string(&Save {
+            at: Coord(4, 2),
+            grid,
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            r#"{"at":[4,2],"grid":{"generation":0,"cells":[[0,1],[0,0]]}}"#
+        );
+
+        let save = serde_json::from_str::<Save>(&json).unwrap();
+        assert_eq!(save.at, Coord(4, 2));
+        assert_eq!(save.grid[Coord(1, 0)], Cell::Alive);
+    }
+}
@@ -0,0 +1,962 @@
+//!
+//! A simulation controller around a [`Grid`].
+//!
+//! See:
+//! - [`Simulation`] for the controller.
+//! - [`Observer`] for watching each step of a simulation.
+//! - [`history`] for stepping back through past generations.
+//! - [`recorder`] for recording population and other statistics over time.
+//! - `Snapshot` for compressed checkpoints, and `cow` for histories sharing
+//!   unchanged tiles, with the `alloc` feature.
+//! - `schedule` for scripted actions performed at given generations, with
+//!   the `alloc` feature.
+//! - `driver` for running simulations as async tasks, with the `async`
+//!   feature.
+//! - `timing` for measuring how fast steps are, with the `std` feature.
+//!
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{
+    cell::Cell,
+    grid::{Edge, Grid},
+    layer::Layer,
+    math::Coord,
+    rule::{Life, Rule},
+};
+
+#[cfg(feature = "alloc")]
+pub mod cow;
+#[cfg(feature = "async")]
+pub mod driver;
+pub mod history;
+pub mod recorder;
+#[cfg(feature = "alloc")]
+pub mod schedule;
+#[cfg(feature = "std")]
+pub mod timing;
+
+use history::History;
+#[cfg(feature = "std")]
+use timing::StepTimer;
+
+///
+/// The outcome of [`Simulation::run_until_stable()`].
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Stability {
+    /// The grid stopped changing.
+    StillLife,
+    /// The grid entered a cycle of `period` generations.
+    Cycle { period: u64 },
+    ///
+    /// The grid's live cells came back every `period` generations, but
+    /// moved, like a spaceship.
+    ///
+    Spaceship { period: u64 },
+    /// The generation limit was reached first.
+    Limit,
+}
+
+///
+/// Statistics about a single step of a grid.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct StepStats {
+    /// The number of cells that became alive.
+    pub births: usize,
+    /// The number of cells that died.
+    pub deaths: usize,
+    /// The number of live cells after the step.
+    pub population: usize,
+    /// The number of cells that changed, which is `births + deaths`.
+    pub changed: usize,
+}
+
+impl StepStats {
+    /// Calculate the statistics of a step from `prev` to `next`.
+    #[must_use]
+    pub fn between<const W: usize, const H: usize>(prev: &Grid<W, H>, next: &Grid<W, H>) -> Self {
+        let (births, deaths) =
+            prev.diff(next)
+                .fold((0, 0), |(births, deaths), (_, cell)| match cell {
+                    Cell::Alive => (births + 1, deaths),
+                    Cell::Dead => (births, deaths + 1),
+                });
+
+        Self {
+            births,
+            deaths,
+            population: next.population(),
+            changed: births + deaths,
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Layer<W, H> for StepStats {
+    fn update(&mut self, _: Coord, prev: Cell, next: Cell) {
+        match (prev, next) {
+            (Cell::Dead, Cell::Alive) => self.births += 1,
+            (Cell::Alive, Cell::Dead) => self.deaths += 1,
+            _ => {}
+        }
+        self.changed = self.births + self.deaths;
+        self.population += next as usize;
+    }
+}
+
+///
+/// Live cells reaching an edge of a grid, as passed to
+/// [`Observer::on_boundary()`].
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BoundaryEvent {
+    pub edge: Edge,
+    /// The first live cell found on the edge.
+    pub coord: Coord,
+    /// The generation in which the edge was reached.
+    pub generation: u64,
+}
+
+///
+/// Something that watches each step of a [`Simulation`], such as a logger,
+/// a statistics collector or a renderer.
+///
+/// Implemented for closures taking the same arguments as
+/// [`Observer::on_step()`].
+///
+pub trait Observer<const W: usize, const H: usize> {
+    /// Called after each step, with the new grid and statistics about the step.
+    fn on_step(&mut self, grid: &Grid<W, H>, stats: StepStats);
+
+    ///
+    /// Called after [`Observer::on_step()`] if the simulation has timing
+    /// enabled, with the timer the step was recorded into.
+    ///
+    #[cfg(feature = "std")]
+    fn on_timing(&mut self, _timer: &StepTimer) {}
+
+    ///
+    /// Called after [`Observer::on_step()`] the first time live cells are
+    /// found on each edge of the grid.
+    ///
+    /// On a bounded grid, this is when the simulation stops being faithful
+    /// to an infinite one.
+    ///
+    fn on_boundary(&mut self, _event: BoundaryEvent) {}
+}
+
+impl<const W: usize, const H: usize, F: FnMut(&Grid<W, H>, StepStats)> Observer<W, H> for F {
+    fn on_step(&mut self, grid: &Grid<W, H>, stats: StepStats) {
+        self(grid, stats);
+    }
+}
+
+///
+/// A compressed checkpoint of a grid, taken by [`Simulation::checkpoint()`].
+///
+/// Snapshots are stored in the [`compress`](crate::io::compress) format, so
+/// mostly empty grids take up little memory.
+///
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Snapshot<const W: usize, const H: usize> {
+    bytes: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl<const W: usize, const H: usize> Snapshot<W, H> {
+    /// Take a snapshot of `grid`.
+    #[must_use]
+    pub fn new(grid: &Grid<W, H>) -> Self {
+        let mut bytes = alloc::vec![0; Grid::<W, H>::COMPRESSED_MAX_LEN];
+        let len = grid
+            .compress(&mut bytes)
+            .expect("buffer is large enough")
+            .len();
+        bytes.truncate(len);
+        bytes.shrink_to_fit();
+
+        Self { bytes }
+    }
+
+    /// Returns the grid this snapshot was taken of.
+    #[must_use]
+    pub fn to_grid(&self) -> Grid<W, H> {
+        Grid::decompress(&self.bytes).expect("snapshot is valid")
+    }
+
+    /// Returns the generation of the grid this snapshot was taken of.
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        let header = self.bytes[..8].try_into().expect("slice is 8 bytes");
+        u64::from_le_bytes(header)
+    }
+
+    /// Returns the compressed grid.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+///
+/// A grid being run under a rule.
+///
+/// A simulation remembers the grid it was started from, so that it can be
+/// [reset](Simulation::reset()), and can be paused, in which case
+/// [`Simulation::tick()`] does nothing.
+///
+/// It also remembers up to `N` past generations in a [`History`], so that
+/// it can be stepped back with [`Simulation::undo()`] and
+/// [`Simulation::rewind()`]. By default, `N` is 0 and nothing is remembered.
+///
+#[derive(Clone, Debug)]
+pub struct Simulation<const W: usize, const H: usize, R = Life, const N: usize = 0> {
+    grid: Grid<W, H>,
+    initial: Grid<W, H>,
+    rule: R,
+    paused: bool,
+    history: History<W, H, N>,
+    /// Which of the edges in [`Edge::ALL`] observers have been told about.
+    reached: [bool; 4],
+    #[cfg(feature = "std")]
+    timer: Option<StepTimer>,
+    #[cfg(feature = "alloc")]
+    schedule: Option<schedule::Attached<R>>,
+}
+
+impl<const W: usize, const H: usize, R: Rule> Simulation<W, H, R> {
+    /// Construct a new running [`Simulation`] starting from `grid`.
+    #[must_use]
+    pub fn new(grid: Grid<W, H>, rule: R) -> Self {
+        Self::with_history(grid, rule)
+    }
+}
+
+impl<const W: usize, const H: usize, R: Rule, const N: usize> Simulation<W, H, R, N> {
+    ///
+    /// Construct a new running [`Simulation`] starting from `grid`, which
+    /// remembers up to `N` past generations.
+    ///
+    #[must_use]
+    pub fn with_history(grid: Grid<W, H>, rule: R) -> Self {
+        Self {
+            initial: grid.clone(),
+            grid,
+            rule,
+            paused: false,
+            history: History::new(),
+            reached: [false; 4],
+            #[cfg(feature = "std")]
+            timer: None,
+            #[cfg(feature = "alloc")]
+            schedule: None,
+        }
+    }
+
+    /// Returns the current grid.
+    #[must_use]
+    pub fn grid(&self) -> &Grid<W, H> {
+        &self.grid
+    }
+
+    ///
+    /// Returns the current grid mutably, for editing.
+    ///
+    /// Edits are not remembered by [`Simulation::reset()`].
+    ///
+    #[must_use]
+    pub fn grid_mut(&mut self) -> &mut Grid<W, H> {
+        &mut self.grid
+    }
+
+    /// Returns the grid this simulation was started from.
+    #[must_use]
+    pub fn initial(&self) -> &Grid<W, H> {
+        &self.initial
+    }
+
+    /// Returns the rule.
+    #[must_use]
+    pub fn rule(&self) -> &R {
+        &self.rule
+    }
+
+    /// Replaces the rule, keeping the current grid.
+    pub fn set_rule(&mut self, rule: R) {
+        self.rule = rule;
+    }
+
+    /// Returns the generation of the current grid.
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.grid.generation
+    }
+
+    /// Returns whether this simulation is paused.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Pauses or unpauses this simulation.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Pauses this simulation, so that [`Simulation::tick()`] does nothing.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Unpauses this simulation.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Returns the remembered past generations.
+    #[must_use]
+    pub fn history(&self) -> &History<W, H, N> {
+        &self.history
+    }
+
+    ///
+    /// Enables or disables timing of each step.
+    ///
+    /// Enabling timing when it is already enabled keeps the steps recorded
+    /// so far.
+    ///
+    #[cfg(feature = "std")]
+    pub fn set_timing(&mut self, enabled: bool) {
+        if !enabled {
+            self.timer = None;
+        } else if self.timer.is_none() {
+            self.timer = Some(StepTimer::new());
+        }
+    }
+
+    /// Returns the step timer, if timing is enabled.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn timing(&self) -> Option<&StepTimer> {
+        self.timer.as_ref()
+    }
+
+    /// Steps the grid by one generation, whether paused or not.
+    pub fn step(&mut self) -> &Grid<W, H> {
+        let next = self.timed(|grid, rule| grid.step_with(rule));
+        self.advance(next);
+        &self.grid
+    }
+
+    ///
+    /// Steps the grid by one generation like [`Simulation::step()`], then
+    /// notifies `observer`.
+    ///
+    /// Only observed steps check whether live cells have reached the edges
+    /// of the grid for [`Observer::on_boundary()`].
+    ///
+    pub fn step_observed(&mut self, observer: &mut impl Observer<W, H>) -> &Grid<W, H> {
+        let (next, stats) = self.timed(|grid, rule| grid.step_with_stats(rule));
+        self.advance(next);
+
+        observer.on_step(&self.grid, stats);
+        #[cfg(feature = "std")]
+        if let Some(timer) = &self.timer {
+            observer.on_timing(timer);
+        }
+        for (edge, reached) in Edge::ALL.into_iter().zip(&mut self.reached) {
+            if *reached {
+                continue;
+            }
+            if let Some(coord) = self.grid.first_on_edge(edge) {
+                *reached = true;
+                observer.on_boundary(BoundaryEvent {
+                    edge,
+                    coord,
+                    generation: self.grid.generation,
+                });
+            }
+        }
+        &self.grid
+    }
+
+    ///
+    /// Steps the grid by one generation like [`Simulation::step()`],
+    /// updating `layer` along the way.
+    ///
+    pub fn step_with_layer(&mut self, layer: impl Layer<W, H>) -> &Grid<W, H> {
+        let next = self.timed(|grid, rule| grid.step_with_layer(rule, layer));
+        self.advance(next);
+        &self.grid
+    }
+
+    ///
+    /// Replaces the current grid with the next generation, remembering it,
+    /// then performs any scheduled actions.
+    ///
+    fn advance(&mut self, next: Grid<W, H>) {
+        self.history.push(core::mem::replace(&mut self.grid, next));
+        #[cfg(feature = "alloc")]
+        self.perform_scheduled();
+    }
+
+    /// Runs `step` on the current grid, timing it if timing is enabled.
+    fn timed<T>(&mut self, step: impl FnOnce(&Grid<W, H>, &R) -> T) -> T {
+        #[cfg(feature = "std")]
+        if let Some(timer) = &mut self.timer {
+            return timer.time(|| step(&self.grid, &self.rule));
+        }
+
+        step(&self.grid, &self.rule)
+    }
+
+    ///
+    /// Steps the grid by `n` generations like [`Simulation::run_for()`],
+    /// notifying `observer` after each of them.
+    ///
+    pub fn run_for_observed(&mut self, n: u64, observer: &mut impl Observer<W, H>) -> &Grid<W, H> {
+        for _ in 0..n {
+            self.step_observed(observer);
+        }
+        &self.grid
+    }
+
+    ///
+    /// Steps the grid back by one generation, returning whether there was a
+    /// remembered generation to step back to.
+    ///
+    pub fn undo(&mut self) -> bool {
+        self.rewind(1) == 1
+    }
+
+    ///
+    /// Steps the grid back by up to `n` generations, returning how many it
+    /// was stepped back by.
+    ///
+    /// Stepping back forgets the generations stepped back over, so they
+    /// cannot be redone other than by stepping forward again.
+    ///
+    pub fn rewind(&mut self, n: usize) -> usize {
+        let mut rewound = 0;
+        while rewound < n {
+            let Some(grid) = self.history.pop() else {
+                break;
+            };
+            self.grid = grid;
+            rewound += 1;
+        }
+
+        #[cfg(feature = "alloc")]
+        self.sync_scheduled_rule();
+        rewound
+    }
+
+    ///
+    /// Steps the grid by one generation unless paused, returning whether it
+    /// was stepped.
+    ///
+    /// This is meant to be called once per frame by frontends.
+    ///
+    pub fn tick(&mut self) -> bool {
+        if !self.paused {
+            self.step();
+        }
+        !self.paused
+    }
+
+    /// Steps the grid by `n` generations, whether paused or not.
+    pub fn run_for(&mut self, n: u64) -> &Grid<W, H> {
+        for _ in 0..n {
+            self.step();
+        }
+        &self.grid
+    }
+
+    ///
+    /// Moves the grid to `generation`, whether paused or not, returning
+    /// whether it could be reached.
+    ///
+    /// Later generations are stepped to. Earlier ones are taken from the
+    /// history if remembered, like [`Simulation::rewind()`], or otherwise
+    /// stepped to again from the grid this simulation was started from,
+    /// like [`Simulation::reset()`], which loses any edits made since.
+    /// Generations before the initial grid cannot be reached.
+    ///
+    pub fn seek(&mut self, generation: u64) -> bool {
+        if generation < self.grid.generation {
+            let remembered = self
+                .history
+                .iter()
+                .rev()
+                .position(|grid| grid.generation == generation);
+            match remembered {
+                Some(back) => {
+                    self.rewind(back + 1);
+                }
+                None if self.initial.generation <= generation => self.reset(),
+                None => return false,
+            }
+        }
+
+        while self.grid.generation < generation {
+            self.step();
+        }
+        true
+    }
+
+    ///
+    /// Steps the grid until it becomes a still life or enters a cycle, or
+    /// until `max_gens` generations have been stepped.
+    ///
+    /// The cycle is found with [`Simulation::detect_cycle()`] first, and the
+    /// grid is then stepped once around it, so it is stepped for at most
+    /// `max_gens` generations either way. Cycles whose live cells come back
+    /// moved are spaceships.
+    ///
+    pub fn run_until_stable(&mut self, max_gens: u64) -> Stability {
+        let Some((start, period)) = self.detect_cycle(max_gens) else {
+            self.run_for(max_gens);
+            return Stability::Limit;
+        };
+
+        self.run_for(start);
+        let first = self.grid.cells;
+        self.run_for(period);
+        match period {
+            _ if self.grid.cells != first => Stability::Spaceship { period },
+            1 => Stability::StillLife,
+            period => Stability::Cycle { period },
+        }
+    }
+
+    /// Detects a cycle starting from the current grid; see [`Grid::detect_cycle()`].
+    #[must_use]
+    pub fn detect_cycle(&self, max_gens: u64) -> Option<(u64, u64)> {
+        detect_cycle(
+            &self.grid,
+            |grid| grid.step_with(&self.rule),
+            Grid::canonical_fingerprint,
+            max_gens,
+        )
+    }
+
+    /// Takes a compressed checkpoint of the current grid.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn checkpoint(&self) -> Snapshot<W, H> {
+        Snapshot::new(&self.grid)
+    }
+
+    ///
+    /// Restores the grid from a checkpoint, forgetting all past
+    /// generations.
+    ///
+    /// Whether the simulation is paused is kept, and so is the rule, unless
+    /// a [schedule](Simulation::set_schedule()) changes it.
+    ///
+    #[cfg(feature = "alloc")]
+    pub fn restore(&mut self, snapshot: &Snapshot<W, H>) {
+        self.grid = snapshot.to_grid();
+        self.history.clear();
+        self.reached = [false; 4];
+        self.sync_scheduled_rule();
+    }
+
+    /// Restores the grid this simulation was started from, forgetting all past generations.
+    pub fn reset(&mut self) {
+        self.grid = self.initial.clone();
+        self.history.clear();
+        self.reached = [false; 4];
+        #[cfg(feature = "alloc")]
+        self.sync_scheduled_rule();
+    }
+}
+
+impl<const W: usize, const H: usize> Grid<W, H> {
+    ///
+    /// Detects whether this grid eventually enters a cycle, returning the
+    /// number of generations before the cycle starts and its period.
+    ///
+    /// Returns [`None`] if no cycle is found within `max_gens` generations.
+    /// Still lifes are cycles of period 1, and spaceships are cycles of the
+    /// period after which they have moved but are otherwise unchanged.
+    ///
+    /// Only [canonical fingerprints](Grid::canonical_fingerprint()) of past
+    /// generations are compared, using
+    /// [Brent's algorithm](https://en.wikipedia.org/wiki/Cycle_detection#Brent's_algorithm),
+    /// so memory use does not grow with the number of generations.
+    ///
+    #[must_use]
+    pub fn detect_cycle(&self, max_gens: u64) -> Option<(u64, u64)> {
+        detect_cycle(
+            self,
+            |grid| grid.step_with(&Life),
+            Grid::canonical_fingerprint,
+            max_gens,
+        )
+    }
+}
+
+///
+/// Finds the start and period of the cycle the states stepped from `initial`
+/// enter, comparing states by their `key`, with Brent's algorithm.
+///
+/// A cycle of period `p` starting `s` states in is found within `2s + 3p`
+/// steps.
+///
+pub(crate) fn detect_cycle<T: Clone, K: PartialEq>(
+    initial: &T,
+    step: impl Fn(&T) -> T,
+    key: impl Fn(&T) -> K,
+    max_gens: u64,
+) -> Option<(u64, u64)> {
+    let mut saved = key(initial);
+    let mut hare = initial.clone();
+    let (mut power, mut period) = (1, 0);
+
+    for _ in 0..max_gens {
+        hare = step(&hare);
+        period += 1;
+
+        if key(&hare) == saved {
+            let mut tortoise = initial.clone();
+            let mut hare = initial.clone();
+            for _ in 0..period {
+                hare = step(&hare);
+            }
+
+            let mut start = 0;
+            while key(&tortoise) != key(&hare) {
+                tortoise = step(&tortoise);
+                hare = step(&hare);
+                start += 1;
+            }
+            return Some((start, period));
+        }
+        if period == power {
+            saved = key(&hare);
+            power *= 2;
+            period = 0;
+        }
+    }
+
+    None
+}
+
+impl<const W: usize, const H: usize> From<Grid<W, H>> for Simulation<W, H> {
+    fn from(grid: Grid<W, H>) -> Self {
+        Self::new(grid, Life)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{cell::Cell, math::Coord};
+
+    #[test]
+    fn run() {
+        let mut grid = Grid::<5, 5>::new();
+        grid[Coord(2, 1)] = Cell::Alive;
+        grid[Coord(2, 2)] = Cell::Alive;
+        grid[Coord(2, 3)] = Cell::Alive;
+
+        let mut sim = Simulation::from(grid.clone());
+        assert_eq!(sim.step().cells, grid.step().cells);
+        assert_eq!(sim.run_for(3).cells, grid.cells);
+        assert_eq!(sim.generation(), 4);
+
+        sim.set_paused(true);
+        assert!(!sim.tick());
+        assert_eq!(sim.generation(), 4);
+        sim.set_paused(false);
+        assert!(sim.tick());
+        assert_eq!(sim.generation(), 5);
+
+        sim.reset();
+        assert_eq!(sim.grid().cells, grid.cells);
+        assert_eq!(sim.generation(), 0);
+    }
+
+    #[test]
+    fn observe() {
+        let mut grid = Grid::<5, 5>::new();
+        grid[Coord(2, 1)] = Cell::Alive;
+        grid[Coord(2, 2)] = Cell::Alive;
+        grid[Coord(2, 3)] = Cell::Alive;
+
+        let mut steps = 0;
+        let mut last = StepStats::default();
+        let mut observer = |grid: &Grid<5, 5>, stats| {
+            steps += 1;
+            assert_eq!(grid.generation, steps);
+            last = stats;
+        };
+
+        let mut sim = Simulation::from(grid);
+        sim.run_for_observed(3, &mut observer);
+        assert_eq!(steps, 3);
+        assert_eq!(
+            last,
+            StepStats {
+                births: 2,
+                deaths: 2,
+                population: 3,
+                changed: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn boundary() {
+        #[derive(Default)]
+        struct Edges {
+            events: [Option<BoundaryEvent>; 4],
+            len: usize,
+        }
+
+        impl Observer<8, 8> for Edges {
+            fn on_step(&mut self, _: &Grid<8, 8>, _: StepStats) {}
+
+            fn on_boundary(&mut self, event: BoundaryEvent) {
+                self.events[self.len] = Some(event);
+                self.len += 1;
+            }
+        }
+
+        // a glider heading for the bottom right corner
+        let mut grid = Grid::<8, 8>::new();
+        for coord in [
+            Coord(3, 2),
+            Coord(4, 3),
+            Coord(2, 4),
+            Coord(3, 4),
+            Coord(4, 4),
+        ] {
+            grid[coord] = Cell::Alive;
+        }
+
+        let mut edges = Edges::default();
+        let mut sim = Simulation::from(grid);
+        sim.run_for_observed(40, &mut edges);
+        assert_eq!(edges.len, 2);
+        assert_eq!(
+            edges.events[..2],
+            [
+                Some(BoundaryEvent {
+                    edge: Edge::Bottom,
+                    coord: Coord(5, 7),
+                    generation: 9,
+                }),
+                Some(BoundaryEvent {
+                    edge: Edge::Right,
+                    coord: Coord(7, 6),
+                    generation: 11,
+                }),
+            ]
+        );
+
+        sim.reset();
+        sim.run_for_observed(10, &mut edges);
+        assert_eq!(edges.len, 3);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn timing() {
+        struct Counter(u64);
+
+        impl Observer<4, 4> for Counter {
+            fn on_step(&mut self, _: &Grid<4, 4>, _: StepStats) {}
+
+            fn on_timing(&mut self, timer: &StepTimer) {
+                self.0 = timer.steps();
+                assert!(timer.last().is_some());
+            }
+        }
+
+        let mut sim = Simulation::from(Grid::<4, 4>::new());
+        sim.step();
+        assert!(sim.timing().is_none());
+
+        sim.set_timing(true);
+        sim.run_for(2);
+        let mut counter = Counter(0);
+        sim.step_observed(&mut counter);
+        assert_eq!(counter.0, 3);
+        assert_eq!(sim.timing().unwrap().steps(), 3);
+
+        sim.set_timing(false);
+        sim.step();
+        assert!(sim.timing().is_none());
+    }
+
+    #[test]
+    fn rewind() {
+        let mut grid = Grid::<8, 8>::new();
+        for coord in [
+            Coord(1, 0),
+            Coord(2, 1),
+            Coord(0, 2),
+            Coord(1, 2),
+            Coord(2, 2),
+        ] {
+            grid[coord] = Cell::Alive;
+        }
+
+        let mut sim = Simulation::<8, 8, Life, 3>::with_history(grid.clone(), Life);
+        assert!(!sim.undo());
+        sim.run_for(5);
+        assert!(sim.undo());
+        assert_eq!(sim.grid().cells, grid.step().step().step().step().cells);
+        assert_eq!(sim.generation(), 4);
+
+        assert_eq!(sim.rewind(5), 2);
+        assert_eq!(sim.generation(), 2);
+        assert!(sim.history().is_empty());
+
+        let mut sim = Simulation::from(grid);
+        sim.step();
+        assert!(!sim.undo());
+    }
+
+    #[test]
+    fn seek() {
+        let mut grid = Grid::<8, 8>::new();
+        for coord in [
+            Coord(1, 0),
+            Coord(2, 1),
+            Coord(0, 2),
+            Coord(1, 2),
+            Coord(2, 2),
+        ] {
+            grid[coord] = Cell::Alive;
+        }
+        grid.generation = 2;
+
+        let mut sim = Simulation::<8, 8, Life, 3>::with_history(grid.clone(), Life);
+        sim.pause();
+        assert!(sim.seek(10));
+        assert_eq!(sim.generation(), 10);
+        assert!(sim.is_paused());
+
+        // remembered
+        assert!(sim.seek(8));
+        assert_eq!(sim.history().len(), 1);
+        assert_eq!(sim.grid().cells, grid.roll(1, 1).step().step().cells);
+
+        // stepped to again from the start
+        assert!(sim.seek(3));
+        assert_eq!(sim.grid().cells, grid.step().cells);
+        assert!(sim.seek(2));
+        assert!(!sim.seek(1));
+        assert_eq!(sim.generation(), 2);
+
+        sim.resume();
+        assert!(sim.tick());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn checkpoint() {
+        let mut grid = Grid::<64, 64>::new();
+        grid[Coord(2, 1)] = Cell::Alive;
+        grid[Coord(2, 2)] = Cell::Alive;
+        grid[Coord(2, 3)] = Cell::Alive;
+
+        let mut sim = Simulation::from(grid);
+        sim.step();
+        let snapshot = sim.checkpoint();
+        assert_eq!(snapshot.generation(), 1);
+        assert!(snapshot.as_bytes().len() < 64);
+
+        sim.run_for(10);
+        sim.grid_mut()[Coord(40, 40)] = Cell::Alive;
+        sim.restore(&snapshot);
+        assert_eq!(sim.generation(), 1);
+        assert_eq!(sim.grid().cells, snapshot.to_grid().cells);
+        assert_eq!(sim.grid()[Coord(40, 40)], Cell::Dead);
+
+        // packed bytes A, B, B, C, D, D, ... which compress worst of all
+        let mut grid = Grid::<64, 64>::new();
+        for (i, cell) in grid.cells.iter_mut().flatten().enumerate() {
+            let byte = [0b1010_1010, 0, 0][i / 8 % 3] ^ (i / 24 % 2 * 0xff);
+            if byte & (1 << (i % 8)) != 0 {
+                *cell = Cell::Alive;
+            }
+        }
+        let snapshot = Snapshot::new(&grid);
+        assert_eq!(snapshot.to_grid().cells, grid.cells);
+    }
+
+    #[test]
+    fn run_until_stable() {
+        let mut grid = Grid::<8, 8>::new();
+        let mut sim = Simulation::from(grid.clone());
+        assert_eq!(sim.run_until_stable(10), Stability::StillLife);
+
+        // a blinker
+        grid[Coord(2, 1)] = Cell::Alive;
+        grid[Coord(2, 2)] = Cell::Alive;
+        grid[Coord(2, 3)] = Cell::Alive;
+        let mut sim = Simulation::from(grid.clone());
+        assert_eq!(sim.run_until_stable(10), Stability::Cycle { period: 2 });
+
+        // which then settles into a still life
+        grid[Coord(3, 1)] = Cell::Alive;
+        let mut sim = Simulation::from(grid.clone());
+        assert_eq!(sim.run_until_stable(10), Stability::StillLife);
+        assert_eq!(sim.grid().cells, sim.grid().step().cells);
+
+        // a glider, which moves by a cell every 4 generations
+        let mut grid = Grid::<8, 8>::new();
+        for coord in [
+            Coord(1, 0),
+            Coord(2, 1),
+            Coord(0, 2),
+            Coord(1, 2),
+            Coord(2, 2),
+        ] {
+            grid[coord] = Cell::Alive;
+        }
+        let mut sim = Simulation::from(grid.clone());
+        assert_eq!(sim.run_until_stable(4), Stability::Limit);
+        assert_eq!(sim.generation(), 4);
+        assert_eq!(
+            sim.run_until_stable(100),
+            Stability::Spaceship { period: 4 }
+        );
+        assert_eq!(grid.detect_cycle(10), Some((0, 4)));
+    }
+
+    #[test]
+    fn detect_cycle() {
+        let mut grid = Grid::<8, 8>::new();
+        assert_eq!(grid.detect_cycle(10), Some((0, 1)));
+
+        // a blinker
+        grid[Coord(2, 1)] = Cell::Alive;
+        grid[Coord(2, 2)] = Cell::Alive;
+        grid[Coord(2, 3)] = Cell::Alive;
+        assert_eq!(grid.detect_cycle(10), Some((0, 2)));
+
+        // a 3x2 rectangle, which becomes a beehive after a generation
+        let mut grid = Grid::<8, 8>::new();
+        for coord in [
+            Coord(2, 2),
+            Coord(3, 2),
+            Coord(4, 2),
+            Coord(2, 3),
+            Coord(3, 3),
+            Coord(4, 3),
+        ] {
+            grid[coord] = Cell::Alive;
+        }
+        assert_eq!(grid.detect_cycle(1), None);
+        assert_eq!(grid.detect_cycle(10), Some((1, 1)));
+        assert_eq!(Simulation::from(grid).detect_cycle(10), Some((1, 1)));
+    }
+}
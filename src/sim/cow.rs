@@ -0,0 +1,286 @@
+//!
+//! Copy-on-write snapshots, sharing unchanged tiles between generations.
+//!
+//! See:
+//! - [`CowSnapshot`] for a single snapshot.
+//! - [`CowHistory`] for an unbounded history built from them.
+//!
+
+use alloc::{sync::Arc, vec::Vec};
+
+use crate::{cell::Cell, grid::Grid};
+
+/// The width and height of a tile, in cells.
+pub const TILE: usize = 16;
+
+/// A square of cells, shared between snapshots while it stays unchanged.
+pub type Tile = [[Cell; TILE]; TILE];
+
+/// The number of bytes each tile's allocation takes up, counting the
+/// reference counts stored alongside it.
+const TILE_FOOTPRINT: usize = size_of::<Tile>() + 2 * size_of::<usize>();
+
+///
+/// A snapshot of a grid, split into [`TILE`]-sized tiles.
+///
+/// A snapshot taken with [`CowSnapshot::next()`] shares every tile that did
+/// not change with the snapshot it was taken after, so a history of a mostly
+/// static grid only pays for the tiles that actually changed. Tiles past the
+/// edges of the grid are padded with dead cells.
+///
+#[derive(Clone, Debug)]
+pub struct CowSnapshot<const W: usize, const H: usize> {
+    tiles: Vec<Arc<Tile>>,
+    generation: u64,
+}
+
+impl<const W: usize, const H: usize> CowSnapshot<W, H> {
+    /// The number of tiles across a row.
+    const COLUMNS: usize = W.div_ceil(TILE);
+    /// The number of tiles down a column.
+    const ROWS: usize = H.div_ceil(TILE);
+
+    /// Take a snapshot of `grid`, sharing no tiles.
+    #[must_use]
+    pub fn new(grid: &Grid<W, H>) -> Self {
+        let tiles = (0..Self::ROWS * Self::COLUMNS)
+            .map(|index| Arc::new(Self::tile(grid, index)))
+            .collect();
+
+        Self {
+            tiles,
+            generation: grid.generation,
+        }
+    }
+
+    ///
+    /// Take a snapshot of `grid`, sharing the tiles that are unchanged since
+    /// this snapshot.
+    ///
+    #[must_use]
+    pub fn next(&self, grid: &Grid<W, H>) -> Self {
+        let tiles = self
+            .tiles
+            .iter()
+            .enumerate()
+            .map(|(index, old)| {
+                let tile = Self::tile(grid, index);
+                if **old == tile {
+                    Arc::clone(old)
+                } else {
+                    Arc::new(tile)
+                }
+            })
+            .collect();
+
+        Self {
+            tiles,
+            generation: grid.generation,
+        }
+    }
+
+    /// Returns the grid this snapshot was taken of.
+    #[must_use]
+    pub fn to_grid(&self) -> Grid<W, H> {
+        let mut grid = Grid::new();
+        grid.generation = self.generation;
+        for (y, row) in grid.cells.iter_mut().enumerate() {
+            for (x, cell) in row.iter_mut().enumerate() {
+                let tile = &self.tiles[y / TILE * Self::COLUMNS + x / TILE];
+                *cell = tile[y % TILE][x % TILE];
+            }
+        }
+
+        grid
+    }
+
+    /// Returns the generation of the grid this snapshot was taken of.
+    #[must_use]
+    pub const fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns the tiles, row by row.
+    #[must_use]
+    pub fn tiles(&self) -> &[Arc<Tile>] {
+        &self.tiles
+    }
+
+    ///
+    /// Returns the number of bytes this snapshot takes up, counting every
+    /// tile as its own, whether it is shared or not.
+    ///
+    #[must_use]
+    pub fn memory_footprint(&self) -> usize {
+        self.spine_footprint() + self.tiles.len() * TILE_FOOTPRINT
+    }
+
+    /// Returns the number of tiles this snapshot shares with `other`.
+    #[must_use]
+    pub fn shared_with(&self, other: &Self) -> usize {
+        self.tiles
+            .iter()
+            .zip(&other.tiles)
+            .filter(|(a, b)| Arc::ptr_eq(a, b))
+            .count()
+    }
+
+    /// Returns the number of bytes this snapshot takes up, excluding tiles.
+    fn spine_footprint(&self) -> usize {
+        size_of::<Self>() + self.tiles.capacity() * size_of::<Arc<Tile>>()
+    }
+
+    /// Copies the tile at `index` out of `grid`.
+    fn tile(grid: &Grid<W, H>, index: usize) -> Tile {
+        let [left, top] = [index % Self::COLUMNS, index / Self::COLUMNS].map(|n| n * TILE);
+        let mut tile = [[Cell::Dead; TILE]; TILE];
+        for (row, cells) in tile.iter_mut().zip(grid.cells.iter().skip(top)) {
+            for (cell, &from) in row.iter_mut().zip(cells.iter().skip(left)) {
+                *cell = from;
+            }
+        }
+
+        tile
+    }
+}
+
+///
+/// An unbounded history of past grids, from oldest to newest.
+///
+/// Each grid is stored as a [`CowSnapshot`] of the one before it, so tiles
+/// that stay unchanged are only stored once however many generations are
+/// remembered.
+///
+#[derive(Clone, Debug, Default)]
+pub struct CowHistory<const W: usize, const H: usize> {
+    snapshots: Vec<CowSnapshot<W, H>>,
+}
+
+impl<const W: usize, const H: usize> CowHistory<W, H> {
+    /// Construct a new empty [`CowHistory`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            snapshots: Vec::new(),
+        }
+    }
+
+    /// Returns the number of grids remembered.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// Returns whether no grids are remembered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Remembers `grid` as the newest grid.
+    pub fn push(&mut self, grid: &Grid<W, H>) {
+        let snapshot = match self.snapshots.last() {
+            Some(last) => last.next(grid),
+            None => CowSnapshot::new(grid),
+        };
+        self.snapshots.push(snapshot);
+    }
+
+    /// Removes and returns the newest grid.
+    pub fn pop(&mut self) -> Option<Grid<W, H>> {
+        self.snapshots.pop().map(|snapshot| snapshot.to_grid())
+    }
+
+    /// Returns the snapshot at `index`, counting from the oldest.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&CowSnapshot<W, H>> {
+        self.snapshots.get(index)
+    }
+
+    /// Returns the newest snapshot.
+    #[must_use]
+    pub fn last(&self) -> Option<&CowSnapshot<W, H>> {
+        self.snapshots.last()
+    }
+
+    /// Forgets all grids.
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+
+    /// Returns an iterator over the remembered snapshots, from oldest to newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &CowSnapshot<W, H>> {
+        self.snapshots.iter()
+    }
+
+    ///
+    /// Returns the number of distinct tiles stored, counting each shared tile
+    /// once.
+    ///
+    #[must_use]
+    pub fn distinct_tiles(&self) -> usize {
+        // Shared tiles are only ever shared between neighbouring snapshots of
+        // the same position, so comparing with the previous snapshot suffices.
+        let mut previous: Option<&CowSnapshot<W, H>> = None;
+        let mut count = 0;
+        for snapshot in &self.snapshots {
+            count += snapshot.tiles.len() - previous.map_or(0, |p| snapshot.shared_with(p));
+            previous = Some(snapshot);
+        }
+
+        count
+    }
+
+    ///
+    /// Returns the number of bytes this history takes up, counting each
+    /// shared tile once.
+    ///
+    #[must_use]
+    pub fn memory_footprint(&self) -> usize {
+        let spines = self.snapshots.iter().map(CowSnapshot::spine_footprint);
+        size_of::<Self>()
+            + (self.snapshots.capacity() - self.snapshots.len()) * size_of::<CowSnapshot<W, H>>()
+            + spines.sum::<usize>()
+            + self.distinct_tiles() * TILE_FOOTPRINT
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::Coord;
+
+    #[test]
+    fn shares_unchanged_tiles() {
+        let mut grid = Grid::<40, 20>::new();
+        // A blinker in the first tile and a block in the last.
+        for coord in [Coord(1, 2), Coord(2, 2), Coord(3, 2)] {
+            grid[coord] = Cell::Alive;
+        }
+        for coord in [Coord(36, 17), Coord(37, 17), Coord(36, 18), Coord(37, 18)] {
+            grid[coord] = Cell::Alive;
+        }
+
+        let mut history = CowHistory::new();
+        for _ in 0..10 {
+            history.push(&grid);
+            grid = grid.step();
+        }
+
+        // 3x2 tiles, of which only the blinker's changes each generation.
+        assert_eq!(history.last().unwrap().tiles().len(), 6);
+        assert_eq!(history.distinct_tiles(), 6 + 9);
+        for (a, b) in history.iter().zip(history.iter().skip(1)) {
+            assert_eq!(b.shared_with(a), 5);
+        }
+
+        let unshared = history.iter().map(CowSnapshot::memory_footprint);
+        assert!(history.memory_footprint() * 3 < unshared.sum::<usize>());
+
+        let oldest = history.get(0).unwrap().to_grid();
+        assert_eq!(oldest.generation, 0);
+        assert_eq!(oldest.population(), 7);
+        assert_eq!(history.pop().unwrap().generation, 9);
+        assert_eq!(history.len(), 9);
+    }
+}
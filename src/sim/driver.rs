@@ -0,0 +1,120 @@
+//!
+//! Executor-agnostic async drivers, for running a simulation as a task
+//! under Embassy or any other executor.
+//!
+//! See [`Simulation::run_async()`] and [`Simulation::run_for_async()`].
+//!
+
+use super::{Observer, Simulation};
+use crate::{grid::Grid, rule::Rule};
+
+impl<const W: usize, const H: usize, R: Rule, const N: usize> Simulation<W, H, R, N> {
+    ///
+    /// Runs the simulation forever, awaiting `tick` before each generation,
+    /// and notifying `observer` after each of them.
+    ///
+    /// Ticks while paused do not step the grid, as with
+    /// [`Simulation::tick()`]. With Embassy, `tick` is typically a
+    /// closure around a ticker:
+    ///
+    /// ```ignore
+    /// let mut ticker = Ticker::every(Duration::from_millis(100));
+    /// sim.run_async(async || ticker.next().await, &mut |grid, _| draw(grid)).await
+    /// ```
+    ///
+    pub async fn run_async(
+        &mut self,
+        mut tick: impl AsyncFnMut(),
+        observer: &mut impl Observer<W, H>,
+    ) -> ! {
+        loop {
+            tick().await;
+            if !self.is_paused() {
+                self.step_observed(observer);
+            }
+        }
+    }
+
+    ///
+    /// Steps the grid by `n` generations like
+    /// [`Simulation::run_for_observed()`], awaiting `tick` before each of
+    /// them.
+    ///
+    pub async fn run_for_async(
+        &mut self,
+        n: u64,
+        mut tick: impl AsyncFnMut(),
+        observer: &mut impl Observer<W, H>,
+    ) -> &Grid<W, H> {
+        for _ in 0..n {
+            tick().await;
+            self.step_observed(observer);
+        }
+        self.grid()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::{
+        future::Future,
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    use super::*;
+    use crate::{cell::Cell, math::Coord, sim::StepStats};
+
+    /// Polls `future` to completion, yielding `Pending` on every other poll.
+    fn block_on<T>(future: impl Future<Output = T>) -> T {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    /// A future that is pending once, like a timer.
+    async fn yield_now() {
+        let mut yielded = false;
+        core::future::poll_fn(|_| {
+            if yielded {
+                Poll::Ready(())
+            } else {
+                yielded = true;
+                Poll::Pending
+            }
+        })
+        .await;
+    }
+
+    #[test]
+    fn run_for_async() {
+        let mut grid = Grid::<5, 5>::new();
+        for x in 1..4 {
+            grid[Coord(x, 2)] = Cell::Alive;
+        }
+
+        let mut sim = Simulation::from(grid);
+        let mut ticks = 0;
+        let mut populations = [0; 3];
+        let mut observer = |grid: &Grid<5, 5>, stats: StepStats| {
+            populations[grid.generation as usize - 1] = stats.population;
+        };
+
+        let grid = block_on(sim.run_for_async(
+            3,
+            async || {
+                ticks += 1;
+                yield_now().await;
+            },
+            &mut observer,
+        ));
+        assert_eq!(grid.generation, 3);
+        assert_eq!(grid[Coord(2, 1)], Cell::Alive);
+        assert_eq!(ticks, 3);
+        assert_eq!(populations, [3; 3]);
+    }
+}
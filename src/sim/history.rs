@@ -0,0 +1,137 @@
+//!
+//! A fixed-capacity history of past generations.
+//!
+//! See [`History`].
+//!
+
+use core::{array, mem};
+
+use crate::grid::Grid;
+
+///
+/// A ring buffer of up to `N` past grids, from oldest to newest.
+///
+/// Once full, pushing a grid forgets the oldest one. With `N = 0`, nothing
+/// is remembered and the history takes up no space.
+///
+#[derive(Clone, Debug)]
+pub struct History<const W: usize, const H: usize, const N: usize> {
+    grids: [Grid<W, H>; N],
+    start: usize,
+    len: usize,
+}
+
+impl<const W: usize, const H: usize, const N: usize> History<W, H, N> {
+    /// Construct a new empty [`History`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            grids: array::from_fn(|_| Grid::new()),
+            start: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the maximum number of grids remembered.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of grids remembered.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether no grids are remembered.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remembers `grid` as the newest grid, forgetting the oldest if full.
+    pub fn push(&mut self, grid: Grid<W, H>) {
+        if N == 0 {
+            return;
+        }
+
+        self.grids[(self.start + self.len) % N] = grid;
+        if self.len == N {
+            self.start = (self.start + 1) % N;
+        } else {
+            self.len += 1;
+        }
+    }
+
+    /// Removes and returns the newest grid.
+    pub fn pop(&mut self) -> Option<Grid<W, H>> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        Some(mem::take(&mut self.grids[(self.start + self.len) % N]))
+    }
+
+    /// Returns the newest grid.
+    #[must_use]
+    pub fn last(&self) -> Option<&Grid<W, H>> {
+        self.len
+            .checked_sub(1)
+            .map(|i| &self.grids[(self.start + i) % N])
+    }
+
+    /// Forgets all grids.
+    pub fn clear(&mut self) {
+        self.start = 0;
+        self.len = 0;
+    }
+
+    /// Returns an iterator over the remembered grids, from oldest to newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Grid<W, H>> {
+        (0..self.len).map(|i| &self.grids[(self.start + i) % N])
+    }
+}
+
+impl<const W: usize, const H: usize, const N: usize> Default for History<W, H, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ring() {
+        let mut history = History::<2, 2, 3>::new();
+        for generation in 0..5 {
+            history.push(Grid {
+                generation,
+                ..Grid::new()
+            });
+        }
+
+        assert_eq!(history.len(), 3);
+        assert!(history.iter().map(|grid| grid.generation).eq([2, 3, 4]));
+        assert_eq!(history.last().unwrap().generation, 4);
+        assert_eq!(history.pop().unwrap().generation, 4);
+        assert_eq!(history.pop().unwrap().generation, 3);
+
+        history.push(Grid {
+            generation: 5,
+            ..Grid::new()
+        });
+        assert!(history.iter().map(|grid| grid.generation).eq([2, 5]));
+
+        history.clear();
+        assert!(history.pop().is_none());
+
+        let mut none = History::<2, 2, 0>::new();
+        none.push(Grid::new());
+        assert!(none.is_empty());
+        assert!(none.last().is_none());
+    }
+}
@@ -0,0 +1,217 @@
+//!
+//! A fixed-capacity recorder of per-generation statistics.
+//!
+//! See [`Recorder`].
+//!
+
+use super::{Observer, StepStats};
+use crate::grid::Grid;
+
+///
+/// The statistics of one step, as recorded by a [`Recorder`].
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Sample {
+    /// The generation of the grid after the step.
+    pub generation: u64,
+    pub stats: StepStats,
+}
+
+///
+/// Records the statistics of up to `N` of the most recent steps, from
+/// oldest to newest, along with the minimum, maximum and mean population of
+/// every step ever recorded.
+///
+/// A recorder is an [`Observer`], so it can be passed to
+/// [`Simulation::step_observed()`](super::Simulation::step_observed()).
+///
+#[derive(Clone, Debug)]
+pub struct Recorder<const N: usize> {
+    samples: [Sample; N],
+    start: usize,
+    len: usize,
+    /// The number of samples ever recorded.
+    count: u64,
+    min: usize,
+    max: usize,
+    sum: u128,
+}
+
+impl<const N: usize> Recorder<N> {
+    /// Construct a new empty [`Recorder`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            samples: [Sample {
+                generation: 0,
+                stats: StepStats {
+                    births: 0,
+                    deaths: 0,
+                    population: 0,
+                    changed: 0,
+                },
+            }; N],
+            start: 0,
+            len: 0,
+            count: 0,
+            min: usize::MAX,
+            max: 0,
+            sum: 0,
+        }
+    }
+
+    /// Returns the maximum number of samples remembered.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of samples remembered.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether no samples are remembered.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    ///
+    /// Records the statistics of a step to `generation`, forgetting the
+    /// oldest sample if full.
+    ///
+    pub fn record(&mut self, generation: u64, stats: StepStats) {
+        self.count += 1;
+        self.min = self.min.min(stats.population);
+        self.max = self.max.max(stats.population);
+        self.sum += stats.population as u128;
+
+        if N == 0 {
+            return;
+        }
+
+        self.samples[(self.start + self.len) % N] = Sample { generation, stats };
+        if self.len == N {
+            self.start = (self.start + 1) % N;
+        } else {
+            self.len += 1;
+        }
+    }
+
+    ///
+    /// Records the population of `grid` as though it were stepped to, such
+    /// as for the initial grid of a simulation.
+    ///
+    pub fn record_grid<const W: usize, const H: usize>(&mut self, grid: &Grid<W, H>) {
+        let stats = StepStats {
+            population: grid.population(),
+            ..StepStats::default()
+        };
+        self.record(grid.generation, stats);
+    }
+
+    /// Returns an iterator over the remembered samples, from oldest to newest.
+    pub fn samples(&self) -> impl DoubleEndedIterator<Item = &Sample> {
+        (0..self.len).map(|i| &self.samples[(self.start + i) % N])
+    }
+
+    ///
+    /// Returns an iterator over the remembered populations, from oldest to
+    /// newest.
+    ///
+    pub fn populations(&self) -> impl DoubleEndedIterator<Item = usize> + '_ {
+        self.samples().map(|sample| sample.stats.population)
+    }
+
+    /// Returns the number of samples ever recorded, including forgotten ones.
+    #[must_use]
+    pub const fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the smallest population ever recorded.
+    #[must_use]
+    pub fn min_population(&self) -> Option<usize> {
+        (self.count > 0).then_some(self.min)
+    }
+
+    /// Returns the largest population ever recorded.
+    #[must_use]
+    pub fn max_population(&self) -> Option<usize> {
+        (self.count > 0).then_some(self.max)
+    }
+
+    /// Returns the mean of every population ever recorded.
+    #[must_use]
+    pub fn mean_population(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum as f64 / self.count as f64)
+    }
+
+    /// Forgets all samples.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl<const N: usize> Default for Recorder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize, const H: usize, const N: usize> Observer<W, H> for Recorder<N> {
+    fn on_step(&mut self, grid: &Grid<W, H>, stats: StepStats) {
+        self.record(grid.generation, stats);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{cell::Cell, math::Coord, sim::Simulation};
+
+    #[test]
+    fn record() {
+        // a blinker next to a domino, whose population changes
+        let mut grid = Grid::<8, 8>::new();
+        for coord in [
+            Coord(1, 1),
+            Coord(1, 2),
+            Coord(1, 3),
+            Coord(3, 2),
+            Coord(4, 2),
+        ] {
+            grid[coord] = Cell::Alive;
+        }
+
+        let mut recorder = Recorder::<3>::new();
+        assert_eq!(recorder.mean_population(), None);
+        recorder.record_grid(&grid);
+
+        let mut sim = Simulation::from(grid.clone());
+        sim.run_for_observed(4, &mut recorder);
+
+        let expected: [usize; 5] = core::array::from_fn(|n| {
+            let mut grid = grid.clone();
+            for _ in 0..n {
+                grid = grid.step();
+            }
+            grid.population()
+        });
+        assert_eq!(recorder.count(), 5);
+        assert_eq!(recorder.len(), 3);
+        assert!(recorder.populations().eq(expected[2..].iter().copied()));
+        assert!(recorder.samples().map(|sample| sample.generation).eq(2..5));
+
+        assert_eq!(recorder.min_population(), expected.iter().min().copied());
+        assert_eq!(recorder.max_population(), expected.iter().max().copied());
+        let mean = expected.iter().sum::<usize>() as f64 / 5.0;
+        assert!((recorder.mean_population().unwrap() - mean).abs() < 1e-9);
+
+        recorder.clear();
+        assert!(recorder.is_empty());
+        assert_eq!(recorder.count(), 0);
+    }
+}
@@ -0,0 +1,289 @@
+//!
+//! Scripted actions, performed by a simulation at given generations.
+//!
+//! See [`Schedule`] and [`Simulation::set_schedule()`].
+//!
+
+use alloc::vec::Vec;
+
+use super::Simulation;
+use crate::{
+    cell::Cell,
+    grid::Grid,
+    math::Coord,
+    pattern::PatternBuf,
+    random::{Rng, SplitMix64},
+    rule::Rule,
+};
+
+///
+/// Something done to a [`Simulation`] by a [`Schedule`].
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum Action<R> {
+    /// Pastes a pattern with its top-left corner at `at`, like [`Grid::paste()`].
+    Paste { pattern: PatternBuf, at: Coord },
+    /// Toggles a cell between alive and dead.
+    Toggle(Coord),
+    /// Replaces the rule, like [`Simulation::set_rule()`].
+    SetRule(R),
+    ///
+    /// Toggles every cell with the given probability, drawing from a
+    /// [`SplitMix64`] seeded with `seed`, so the noise is the same every run.
+    ///
+    Noise { probability: f64, seed: u64 },
+}
+
+impl<R: Rule + Clone> Action<R> {
+    /// Performs this action on `sim`.
+    pub fn apply<const W: usize, const H: usize, const N: usize>(
+        &self,
+        sim: &mut Simulation<W, H, R, N>,
+    ) {
+        match self {
+            Self::SetRule(rule) => sim.set_rule(rule.clone()),
+            action => action.apply_to_grid(sim.grid_mut()),
+        }
+    }
+}
+
+impl<R> Action<R> {
+    /// Performs this action on `grid`, unless it is [`Action::SetRule`].
+    fn apply_to_grid<const W: usize, const H: usize>(&self, grid: &mut Grid<W, H>) {
+        match self {
+            Self::Paste { pattern, at } => grid.paste(&pattern.as_pattern(), *at),
+            Self::Toggle(coord) => toggle(grid, *coord),
+            Self::SetRule(_) => {}
+            &Self::Noise { probability, seed } => {
+                let mut rng = SplitMix64::new(seed);
+                for y in 0..H {
+                    for x in 0..W {
+                        if rng.chance(probability) {
+                            toggle(grid, Coord(x, y));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Toggles the cell at `coord` if it lies inside of `grid`.
+fn toggle<const W: usize, const H: usize>(grid: &mut Grid<W, H>, Coord(x, y): Coord) {
+    if let Some(cell) = grid.cells.get_mut(y).and_then(|row| row.get_mut(x)) {
+        *cell = match cell {
+            Cell::Alive => Cell::Dead,
+            Cell::Dead => Cell::Alive,
+        };
+    }
+}
+
+///
+/// A list of [`Action`]s, each to be performed when a simulation reaches a
+/// given generation.
+///
+/// Actions are performed right after the step that reaches their
+/// generation, in the order they were added, so actions at generation 0 or
+/// at generations already passed are never performed.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct Schedule<R> {
+    /// The actions and their generations, sorted by generation.
+    actions: Vec<(u64, Action<R>)>,
+}
+
+impl<R> Schedule<R> {
+    /// Construct a new empty [`Schedule`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            actions: Vec::new(),
+        }
+    }
+
+    /// Returns the number of actions.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// Returns whether there are no actions.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    ///
+    /// Adds `action` to be performed at `generation`, after any actions
+    /// already added at that generation.
+    ///
+    pub fn push(&mut self, generation: u64, action: Action<R>) {
+        let index = self.actions.partition_point(|&(at, _)| at <= generation);
+        self.actions.insert(index, (generation, action));
+    }
+
+    /// Adds `action` to be performed at `generation`, like [`Schedule::push()`].
+    #[must_use]
+    pub fn at(mut self, generation: u64, action: Action<R>) -> Self {
+        self.push(generation, action);
+        self
+    }
+
+    /// Returns the actions to be performed at `generation`, in order.
+    pub fn actions_at(&self, generation: u64) -> impl Iterator<Item = &Action<R>> {
+        let start = self.actions.partition_point(|&(at, _)| at < generation);
+        self.actions[start..]
+            .iter()
+            .take_while(move |&&(at, _)| at == generation)
+            .map(|(_, action)| action)
+    }
+
+    /// Returns an iterator over all actions and their generations, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, &Action<R>)> {
+        self.actions.iter().map(|(at, action)| (*at, action))
+    }
+}
+
+impl<R> Default for Schedule<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///
+/// A [`Schedule`] set on a simulation, along with what is needed to perform
+/// [`Action::SetRule`] without the simulation's rule being [`Clone`].
+///
+#[derive(Clone, Debug)]
+pub(super) struct Attached<R> {
+    schedule: Schedule<R>,
+    /// The rule before the first [`Action::SetRule`].
+    rule: R,
+    clone: fn(&R) -> R,
+}
+
+impl<const W: usize, const H: usize, R: Rule + Clone, const N: usize> Simulation<W, H, R, N> {
+    ///
+    /// Sets the schedule of actions this simulation performs, replacing any
+    /// previous one.
+    ///
+    /// Every step performs the actions at the generation it reaches, whether
+    /// through [`Simulation::step()`], [`Simulation::tick()`],
+    /// [`Simulation::run_for()`] or any other method. Stepping back with
+    /// [`Simulation::rewind()`], [`Simulation::reset()`] or
+    /// [`Simulation::seek()`] sets the rule back to the one in effect at the
+    /// generation stepped back to, taking the current rule to be the one
+    /// before the first [`Action::SetRule`], so stepping forward again
+    /// replays the schedule.
+    ///
+    pub fn set_schedule(&mut self, schedule: Schedule<R>) {
+        self.schedule = Some(Attached {
+            schedule,
+            rule: self.rule().clone(),
+            clone: R::clone,
+        });
+    }
+}
+
+impl<const W: usize, const H: usize, R: Rule, const N: usize> Simulation<W, H, R, N> {
+    /// Returns the schedule of actions this simulation performs, if any.
+    #[must_use]
+    pub fn schedule(&self) -> Option<&Schedule<R>> {
+        self.schedule.as_ref().map(|attached| &attached.schedule)
+    }
+
+    /// Removes the schedule of actions, keeping the current rule.
+    pub fn take_schedule(&mut self) -> Option<Schedule<R>> {
+        self.schedule.take().map(|attached| attached.schedule)
+    }
+
+    /// Performs the scheduled actions at the current generation.
+    pub(super) fn perform_scheduled(&mut self) {
+        let Some(attached) = &self.schedule else {
+            return;
+        };
+        for action in attached.schedule.actions_at(self.grid.generation) {
+            match action {
+                Action::SetRule(rule) => self.rule = (attached.clone)(rule),
+                action => action.apply_to_grid(&mut self.grid),
+            }
+        }
+    }
+
+    /// Sets the rule to the one scheduled at the current generation.
+    pub(super) fn sync_scheduled_rule(&mut self) {
+        let Some(attached) = &self.schedule else {
+            return;
+        };
+        let rule = attached
+            .schedule
+            .iter()
+            .take_while(|&(at, _)| at <= self.grid.generation)
+            .filter_map(|(_, action)| match action {
+                Action::SetRule(rule) => Some(rule),
+                _ => None,
+            })
+            .last()
+            .unwrap_or(&attached.rule);
+        self.rule = (attached.clone)(rule);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{lexicon, rule::LifeLike};
+
+    #[test]
+    fn run() {
+        let glider = PatternBuf::from(lexicon::lookup("glider").unwrap().pattern);
+        let schedule = Schedule::new()
+            .at(
+                3,
+                Action::Paste {
+                    pattern: glider,
+                    at: Coord(10, 10),
+                },
+            )
+            .at(5, Action::SetRule(LifeLike::HIGH_LIFE))
+            .at(3, Action::Toggle(Coord(0, 0)))
+            .at(
+                7,
+                Action::Noise {
+                    probability: 0.5,
+                    seed: 1,
+                },
+            );
+        assert_eq!(schedule.len(), 4);
+        assert_eq!(schedule.actions_at(3).count(), 2);
+
+        let mut sim = Simulation::new(Grid::<16, 16>::new(), LifeLike::CONWAY);
+        sim.set_schedule(schedule);
+        sim.run_for(3);
+        assert_eq!(sim.grid().population(), 6);
+        assert_eq!(sim.grid()[Coord(0, 0)], Cell::Alive);
+        assert_eq!(*sim.rule(), LifeLike::CONWAY);
+
+        assert!(sim.tick());
+        sim.step();
+        assert_eq!(*sim.rule(), LifeLike::HIGH_LIFE);
+        assert_eq!(sim.grid().population(), 5);
+
+        sim.run_for(2);
+        let noisy = sim.grid().clone();
+        assert!(noisy.population() > 40);
+
+        sim.reset();
+        assert_eq!(*sim.rule(), LifeLike::CONWAY);
+        sim.run_for(7);
+        assert_eq!(sim.grid().cells, noisy.cells);
+
+        assert!(sim.seek(4));
+        assert_eq!(*sim.rule(), LifeLike::CONWAY);
+        assert!(sim.seek(5));
+        assert_eq!(*sim.rule(), LifeLike::HIGH_LIFE);
+
+        assert_eq!(sim.take_schedule().map(|schedule| schedule.len()), Some(4));
+        assert!(sim.schedule().is_none());
+    }
+}
@@ -0,0 +1,123 @@
+//!
+//! Per-step timing, for measuring how fast a grid is stepped.
+//!
+//! See [`StepTimer`].
+//!
+
+use core::time::Duration;
+use std::time::Instant;
+
+/// The number of most recent steps a [`StepTimer`] averages over.
+pub const WINDOW: usize = 32;
+
+///
+/// Measures how long steps take, keeping a rolling average over the last
+/// [`WINDOW`] steps.
+///
+/// A [`Simulation`](super::Simulation) with timing enabled records every
+/// step into its timer, but a timer can also time any other engine with
+/// [`StepTimer::time()`], so that different engines can be compared.
+///
+#[derive(Clone, Debug, Default)]
+pub struct StepTimer {
+    samples: [Duration; WINDOW],
+    /// The index the next sample is recorded at.
+    next: usize,
+    /// The sum of the samples in the window.
+    total: Duration,
+    steps: u64,
+}
+
+impl StepTimer {
+    /// Construct a new [`StepTimer`] with no steps recorded.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            samples: [Duration::ZERO; WINDOW],
+            next: 0,
+            total: Duration::ZERO,
+            steps: 0,
+        }
+    }
+
+    /// Records a step that took `duration`.
+    pub fn record(&mut self, duration: Duration) {
+        self.total = self.total - self.samples[self.next] + duration;
+        self.samples[self.next] = duration;
+        self.next = (self.next + 1) % WINDOW;
+        self.steps += 1;
+    }
+
+    /// Runs `step`, recording how long it took.
+    pub fn time<T>(&mut self, step: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let output = step();
+        self.record(start.elapsed());
+        output
+    }
+
+    /// Returns the number of steps recorded, including those out of the window.
+    #[must_use]
+    pub const fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Returns how long the most recent step took.
+    #[must_use]
+    pub fn last(&self) -> Option<Duration> {
+        (self.steps > 0).then(|| self.samples[(self.next + WINDOW - 1) % WINDOW])
+    }
+
+    /// Returns how long the steps in the window took on average.
+    #[must_use]
+    pub fn average(&self) -> Option<Duration> {
+        let len = self.steps.min(WINDOW as u64);
+        (len > 0).then(|| self.total / len as u32)
+    }
+
+    ///
+    /// Returns how many generations per second could be stepped at the
+    /// average speed of the steps in the window.
+    ///
+    /// This only counts time spent stepping, not rendering or waiting.
+    ///
+    #[must_use]
+    pub fn generations_per_second(&self) -> Option<f64> {
+        self.average().map(|average| 1.0 / average.as_secs_f64())
+    }
+
+    /// Forgets all recorded steps.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rolling_average() {
+        let mut timer = StepTimer::new();
+        assert_eq!(timer.last(), None);
+        assert_eq!(timer.average(), None);
+
+        timer.record(Duration::from_millis(10));
+        timer.record(Duration::from_millis(30));
+        assert_eq!(timer.last(), Some(Duration::from_millis(30)));
+        assert_eq!(timer.average(), Some(Duration::from_millis(20)));
+        assert_eq!(timer.generations_per_second(), Some(50.0));
+
+        for _ in 0..WINDOW {
+            timer.record(Duration::from_millis(4));
+        }
+        assert_eq!(timer.steps(), WINDOW as u64 + 2);
+        assert_eq!(timer.average(), Some(Duration::from_millis(4)));
+
+        assert_eq!(timer.time(|| 7), 7);
+        assert_eq!(timer.steps(), WINDOW as u64 + 3);
+
+        timer.reset();
+        assert_eq!(timer.steps(), 0);
+    }
+}
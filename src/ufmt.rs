@@ -0,0 +1,109 @@
+//!
+//! [`ufmt`] support for [`Cell`], [`Coord`] and [`Grid`], for targets where
+//! `core::fmt` is too large.
+//!
+//! - [`Cell`]s are displayed as `#` or `.`.
+//! - [`Coord`]s are displayed as `(x, y)`.
+//! - [`Grid`]s are displayed as one line of cells per row.
+//!
+//! Their debug output matches that of their [`core::fmt::Debug`] impls,
+//! except for [`Grid`]s, which only show their extents, generation and
+//! population.
+//!
+
+use ::ufmt::{uDebug, uDisplay, uWrite, uwrite, Formatter};
+
+use crate::{
+    cell::{Cell, Charset},
+    grid::Grid,
+    math::Coord,
+};
+
+impl uDisplay for Cell {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.write_char(self.to_char(Charset::default()))
+    }
+}
+
+impl uDebug for Cell {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.write_str(match self {
+            Self::Alive => "Alive",
+            Self::Dead => "Dead",
+        })
+    }
+}
+
+impl uDisplay for Coord {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        uwrite!(f, "({}, {})", self.0, self.1)
+    }
+}
+
+impl uDebug for Coord {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        uwrite!(f, "Coord({}, {})", self.0, self.1)
+    }
+}
+
+impl<const W: usize, const H: usize> uDisplay for Grid<W, H> {
+    fn fmt<F: uWrite + ?Sized>(&self, f: &mut Formatter<'_, F>) -> Result<(), F::Error> {
+        for row in &self.cells {
+            for cell in row {
+                uDisplay::fmt(cell, f)?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+impl<const W: usize, const H: usize> uDebug for Grid<W, H> {
+    fn fmt<F: uWrite + ?Sized>(&self, f: &mut Formatter<'_, F>) -> Result<(), F::Error> {
+        uwrite!(
+            f,
+            "Grid {{ extents: {}x{}, generation: {}, population: {} }}",
+            W,
+            H,
+            self.generation,
+            self.population()
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use std::string::String;
+
+    use ::ufmt::uwrite;
+
+    use super::*;
+
+    #[test]
+    fn ufmt() {
+        let mut grid = Grid::<3, 2>::new();
+        grid[Coord(1, 0)] = Cell::Alive;
+        grid.generation = 4;
+
+        let mut s = String::new();
+        uwrite!(
+            s,
+            "{} {:?} {} {:?}",
+            Cell::Alive,
+            Cell::Dead,
+            Coord(1, 2),
+            Coord(3, 4)
+        )
+        .unwrap();
+        assert_eq!(s, "# Dead (1, 2) Coord(3, 4)");
+
+        let mut s = String::new();
+        uwrite!(s, "{}{:?}", grid, grid).unwrap();
+        assert_eq!(
+            s,
+            ".#.\n...\nGrid { extents: 3x2, generation: 4, population: 1 }"
+        );
+    }
+}
@@ -0,0 +1,391 @@
+//!
+//! Borrowed views into rectangular regions of a grid.
+//!
+//! See [`GridView`] and [`GridViewMut`].
+//!
+
+use core::ops::{Index, IndexMut, Range};
+
+use crate::{
+    cell::{Cell, CellState},
+    grid::Grid,
+    math::{Coord, Rect},
+};
+
+///
+/// A view into a rectangular region of a [`Grid`], without copying it.
+///
+/// [`Coord`]-based indices are relative to the top-left corner of the
+/// region.
+///
+#[derive(Clone, Copy, Debug)]
+pub struct GridView<'a, C = Cell> {
+    /// The cells of the grid, starting at the view's top-left corner.
+    cells: &'a [C],
+    /// The distance between the starts of consecutive rows, in cells.
+    stride: usize,
+    extents: Coord,
+}
+
+impl<'a, C> GridView<'a, C> {
+    /// Returns the extents of this view.
+    #[must_use]
+    pub const fn extents(&self) -> Coord {
+        self.extents
+    }
+
+    /// Returns the cell at `coord`, or [`None`] if it is outside of this view.
+    #[must_use]
+    pub fn get(&self, Coord(x, y): Coord) -> Option<&'a C> {
+        let cells = self.cells;
+        (x < self.extents.0 && y < self.extents.1).then(|| &cells[y * self.stride + x])
+    }
+
+    /// Returns an iterator over the rows of this view, from top to bottom.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &'a [C]> + 'a {
+        let Self {
+            cells,
+            stride,
+            extents: Coord(w, h),
+        } = *self;
+        (0..h).map(move |y| &cells[y * stride..][..w])
+    }
+
+    ///
+    /// Returns a view into the region `rect` of this view.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rect` does not lie inside of this view.
+    ///
+    #[must_use]
+    pub fn region(&self, rect: Rect) -> Self {
+        let (Coord(x, y), Coord(w, h)) = (rect.origin, rect.extents);
+        assert!(
+            x + w <= self.extents.0 && y + h <= self.extents.1,
+            "region out of bounds"
+        );
+
+        Self {
+            cells: self.cells.get(y * self.stride + x..).unwrap_or_default(),
+            stride: self.stride,
+            extents: rect.extents,
+        }
+    }
+
+    ///
+    /// Splits this view into four at `at`, returning the top-left,
+    /// top-right, bottom-left and bottom-right parts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` lies outside of this view, other than on its right or
+    /// bottom edge.
+    ///
+    #[must_use]
+    pub fn split_at(&self, at: Coord) -> [Self; 4] {
+        quadrants(self.extents, at).map(|rect| self.region(rect))
+    }
+}
+
+impl<C: CellState> GridView<'_, C> {
+    /// Returns the number of live cells in this view.
+    #[must_use]
+    pub fn population(&self) -> usize {
+        self.rows()
+            .flatten()
+            .filter(|&&cell| cell.is_alive())
+            .count()
+    }
+}
+
+impl<C> Index<Coord> for GridView<'_, C> {
+    type Output = C;
+
+    fn index(&self, index: Coord) -> &Self::Output {
+        self.get(index).expect("coordinate out of bounds")
+    }
+}
+
+///
+/// A mutable view into a rectangular region of a [`Grid`] `H` cells high,
+/// without copying it.
+///
+/// Unlike a [`GridView`], a mutable view borrows each of its rows
+/// separately, so that it can be split into views that are disjoint and can
+/// be modified at the same time, such as on different threads.
+///
+#[derive(Debug)]
+pub struct GridViewMut<'a, const H: usize, C = Cell> {
+    /// The rows of the view, of which only the first `extents.1` are used.
+    rows: [&'a mut [C]; H],
+    extents: Coord,
+}
+
+impl<'a, const H: usize, C> GridViewMut<'a, H, C> {
+    /// Returns the extents of this view.
+    #[must_use]
+    pub const fn extents(&self) -> Coord {
+        self.extents
+    }
+
+    /// Returns the cell at `coord`, or [`None`] if it is outside of this view.
+    #[must_use]
+    pub fn get(&self, Coord(x, y): Coord) -> Option<&C> {
+        self.rows[..self.extents.1].get(y)?.get(x)
+    }
+
+    ///
+    /// Returns the cell at `coord` mutably, or [`None`] if it is outside of
+    /// this view.
+    ///
+    #[must_use]
+    pub fn get_mut(&mut self, Coord(x, y): Coord) -> Option<&mut C> {
+        self.rows[..self.extents.1].get_mut(y)?.get_mut(x)
+    }
+
+    /// Returns an iterator over the rows of this view, from top to bottom.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[C]> {
+        self.rows[..self.extents.1].iter().map(|row| &**row)
+    }
+
+    /// Returns an iterator over the rows of this view mutably, from top to bottom.
+    pub fn rows_mut(&mut self) -> impl ExactSizeIterator<Item = &mut [C]> + use<'_, 'a, H, C> {
+        self.rows[..self.extents.1].iter_mut().map(|row| &mut **row)
+    }
+
+    /// Sets every cell in this view to `cell`.
+    pub fn fill(&mut self, cell: C)
+    where
+        C: Clone,
+    {
+        for row in self.rows_mut() {
+            row.fill(cell.clone());
+        }
+    }
+
+    ///
+    /// Narrows this view down to the region `rect` of it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rect` does not lie inside of this view.
+    ///
+    #[must_use]
+    pub fn region(mut self, rect: Rect) -> Self {
+        let (Coord(x, y), Coord(w, h)) = (rect.origin, rect.extents);
+        assert!(
+            x + w <= self.extents.0 && y + h <= self.extents.1,
+            "region out of bounds"
+        );
+
+        Self {
+            rows: take_rows(&mut self.rows, y..y + h)
+                .map(|row| row.get_mut(x..x + w).unwrap_or_default()),
+            extents: rect.extents,
+        }
+    }
+
+    ///
+    /// Splits this view into four disjoint views at `at`, returning the
+    /// top-left, top-right, bottom-left and bottom-right parts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` lies outside of this view, other than on its right or
+    /// bottom edge.
+    ///
+    #[must_use]
+    pub fn split_at(self, at: Coord) -> [Self; 4] {
+        let Coord(x, y) = at;
+        let [top_left, top_right, bottom_left, bottom_right] = quadrants(self.extents, at);
+
+        let mut halves = self.rows.map(|row| {
+            let (left, right) = row.split_at_mut(x.min(row.len()));
+            [left, right]
+        });
+        let mut lefts = halves.each_mut().map(|[left, _]| core::mem::take(left));
+        let mut rights = halves.each_mut().map(|[_, right]| core::mem::take(right));
+
+        let h = self.extents.1;
+        [
+            (take_rows(&mut lefts, 0..y), top_left),
+            (take_rows(&mut rights, 0..y), top_right),
+            (take_rows(&mut lefts, y..h), bottom_left),
+            (take_rows(&mut rights, y..h), bottom_right),
+        ]
+        .map(|(rows, rect)| Self {
+            rows,
+            extents: rect.extents,
+        })
+    }
+}
+
+impl<const H: usize, C> Index<Coord> for GridViewMut<'_, H, C> {
+    type Output = C;
+
+    fn index(&self, index: Coord) -> &Self::Output {
+        self.get(index).expect("coordinate out of bounds")
+    }
+}
+
+impl<const H: usize, C> IndexMut<Coord> for GridViewMut<'_, H, C> {
+    fn index_mut(&mut self, index: Coord) -> &mut Self::Output {
+        self.get_mut(index).expect("coordinate out of bounds")
+    }
+}
+
+///
+/// Takes the rows in `range` out of `rows`, leaving them empty, and returns
+/// them at the start of an array padded with empty rows.
+///
+fn take_rows<'a, const H: usize, C>(
+    rows: &mut [&'a mut [C]; H],
+    range: Range<usize>,
+) -> [&'a mut [C]; H] {
+    core::array::from_fn(|i| match rows.get_mut(range.start + i) {
+        Some(row) if range.start + i < range.end => core::mem::take(row),
+        _ => Default::default(),
+    })
+}
+
+impl<const W: usize, const H: usize, C> Grid<W, H, C> {
+    /// Returns a view into the whole of this grid.
+    #[must_use]
+    pub fn view(&self) -> GridView<'_, C> {
+        GridView {
+            cells: self.cells.as_flattened(),
+            stride: W,
+            extents: Coord(W, H),
+        }
+    }
+
+    ///
+    /// Returns a view into the region `rect` of this grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rect` does not lie inside of this grid.
+    ///
+    #[must_use]
+    pub fn region(&self, rect: Rect) -> GridView<'_, C> {
+        self.view().region(rect)
+    }
+
+    ///
+    /// Splits this grid into views of its top-left, top-right, bottom-left
+    /// and bottom-right quadrants.
+    ///
+    /// With an odd width or height, the right or bottom quadrants are the
+    /// larger ones.
+    ///
+    #[must_use]
+    pub fn split_quadrants(&self) -> [GridView<'_, C>; 4] {
+        self.view().split_at(Coord(W / 2, H / 2))
+    }
+
+    /// Returns a mutable view into the whole of this grid.
+    #[must_use]
+    pub fn view_mut(&mut self) -> GridViewMut<'_, H, C> {
+        GridViewMut {
+            rows: self.cells.each_mut().map(|row| &mut row[..]),
+            extents: Coord(W, H),
+        }
+    }
+
+    ///
+    /// Returns a mutable view into the region `rect` of this grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rect` does not lie inside of this grid.
+    ///
+    #[must_use]
+    pub fn region_mut(&mut self, rect: Rect) -> GridViewMut<'_, H, C> {
+        self.view_mut().region(rect)
+    }
+
+    ///
+    /// Splits this grid into disjoint mutable views of its quadrants, like
+    /// [`Grid::split_quadrants()`].
+    ///
+    #[must_use]
+    pub fn split_quadrants_mut(&mut self) -> [GridViewMut<'_, H, C>; 4] {
+        self.view_mut().split_at(Coord(W / 2, H / 2))
+    }
+}
+
+/// Returns the rectangles of the quadrants of `extents` split at `at`.
+fn quadrants(Coord(w, h): Coord, Coord(x, y): Coord) -> [Rect; 4] {
+    assert!(x <= w && y <= h, "split out of bounds");
+    [
+        Rect::new(Coord(0, 0), Coord(x, y)),
+        Rect::new(Coord(x, 0), Coord(w - x, y)),
+        Rect::new(Coord(0, y), Coord(x, h - y)),
+        Rect::new(Coord(x, y), Coord(w - x, h - y)),
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn region() {
+        let mut grid = Grid::<6, 4>::new();
+        for coord in [Coord(1, 1), Coord(4, 1), Coord(5, 3)] {
+            grid[coord] = Cell::Alive;
+        }
+
+        let view = grid.region(Rect::new(Coord(1, 1), Coord(4, 3)));
+        assert_eq!(view.extents(), Coord(4, 3));
+        assert_eq!(view[Coord(0, 0)], Cell::Alive);
+        assert_eq!(view[Coord(3, 0)], Cell::Alive);
+        assert_eq!(view.get(Coord(4, 2)), None);
+        assert_eq!(view.population(), 2);
+        assert_eq!(view.rows().len(), 3);
+
+        let inner = view.region(Rect::new(Coord(3, 0), Coord(1, 1)));
+        assert_eq!(inner.population(), 1);
+        assert_eq!(
+            view.region(Rect::new(Coord(4, 3), Coord(0, 0)))
+                .population(),
+            0
+        );
+
+        let [top_left, top_right, bottom_left, bottom_right] = grid.split_quadrants();
+        assert_eq!(top_left.extents(), Coord(3, 2));
+        assert_eq!(
+            [top_left, top_right, bottom_left, bottom_right].map(|view| view.population()),
+            [1, 1, 0, 1]
+        );
+    }
+
+    #[test]
+    fn region_mut() {
+        let mut grid = Grid::<5, 3>::new();
+        let [mut top_left, _, _, mut bottom_right] = grid.split_quadrants_mut();
+        assert_eq!(bottom_right.extents(), Coord(3, 2));
+        top_left.fill(Cell::Alive);
+        bottom_right[Coord(2, 1)] = Cell::Alive;
+        assert_eq!(grid.population(), 3);
+        assert_eq!(grid[Coord(1, 0)], Cell::Alive);
+        assert_eq!(grid[Coord(4, 2)], Cell::Alive);
+
+        let mut region = grid.region_mut(Rect::new(Coord(1, 1), Coord(3, 2)));
+        region[Coord(0, 0)] = Cell::Alive;
+        assert_eq!(region.rows().len(), 2);
+        assert_eq!(grid[Coord(1, 1)], Cell::Alive);
+
+        let region = grid.region_mut(Rect::new(Coord(1, 0), Coord(4, 3)));
+        let [top_left, mut top_right, bottom_left, mut bottom_right] = region.split_at(Coord(1, 2));
+        assert_eq!(top_left.extents(), Coord(1, 2));
+        assert_eq!(bottom_left.rows().len(), 1);
+        top_right.fill(Cell::Alive);
+        bottom_right[Coord(0, 0)] = Cell::Alive;
+        assert_eq!(bottom_right.extents(), Coord(3, 1));
+        assert_eq!(grid.population(), 11);
+        assert_eq!(grid[Coord(2, 2)], Cell::Alive);
+        assert_eq!(grid[Coord(4, 1)], Cell::Alive);
+    }
+}
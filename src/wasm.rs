@@ -0,0 +1,227 @@
+//!
+//! Bindings for driving grids from JavaScript with `wasm-bindgen`.
+//!
+//! `wasm-bindgen` cannot export generic types, so grids of a given size are
+//! exported with [`wasm_grid!`](crate::wasm_grid), for example:
+//!
+//! ```
+//! lifeless::wasm_grid! {
+//!     /// A 64 by 48 grid.
+//!     pub struct Board(64, 48);
+//! }
+//!
+//! let mut board = Board::new();
+//! board.set(1, 0, true);
+//! board.set(1, 1, true);
+//! board.set(1, 2, true);
+//! board.step(1);
+//! assert!(board.get(0, 1) && board.get(2, 1));
+//! ```
+//!
+//! which can then be drawn onto a canvas from JavaScript:
+//!
+//! ```js
+//! const board = new Board();
+//! const frame = board.render(0xffffff, 0x000000);
+//! context.putImageData(new ImageData(frame, board.width(), board.height()), 0, 0);
+//! ```
+//!
+
+pub use wasm_bindgen;
+
+///
+/// Export a grid of a fixed size to JavaScript as a `wasm-bindgen` class.
+///
+/// The class has methods for construction, reading and writing cells,
+/// stepping, byte-level snapshots in the [`wire`](crate::io::wire) format,
+/// and rendering RGBA frames for canvas `ImageData`.
+///
+/// See the [module documentation](crate::wasm) for an example.
+///
+#[macro_export]
+macro_rules! wasm_grid {
+    ($(#[$attr:meta])* $vis:vis struct $name:ident($w:expr, $h:expr);) => {
+        $(#[$attr])*
+        #[$crate::wasm::wasm_bindgen::prelude::wasm_bindgen(
+            wasm_bindgen = $crate::wasm::wasm_bindgen
+        )]
+        $vis struct $name {
+            grid: $crate::Grid<{ $w }, { $h }>,
+        }
+
+        #[$crate::wasm::wasm_bindgen::prelude::wasm_bindgen(
+            wasm_bindgen = $crate::wasm::wasm_bindgen
+        )]
+        impl $name {
+            /// Construct a new empty grid.
+            #[wasm_bindgen(constructor)]
+            #[must_use]
+            pub fn new() -> Self {
+                Self {
+                    grid: $crate::Grid::new(),
+                }
+            }
+
+            /// Returns the width of the grid, in cells.
+            #[must_use]
+            pub fn width(&self) -> usize {
+                $w
+            }
+
+            /// Returns the height of the grid, in cells.
+            #[must_use]
+            pub fn height(&self) -> usize {
+                $h
+            }
+
+            /// Returns the generation of the grid.
+            #[must_use]
+            pub fn generation(&self) -> u64 {
+                self.grid.generation
+            }
+
+            /// Returns the number of live cells.
+            #[must_use]
+            pub fn population(&self) -> usize {
+                self.grid.population()
+            }
+
+            /// Returns whether a cell is alive, or `false` outside the grid.
+            #[must_use]
+            pub fn get(&self, x: usize, y: usize) -> bool {
+                self.grid
+                    .get($crate::Coord(x, y))
+                    .is_some_and(|&cell| cell.into())
+            }
+
+            /// Sets whether a cell is alive, doing nothing outside the grid.
+            pub fn set(&mut self, x: usize, y: usize, alive: bool) {
+                if let Some(cell) = self.grid.get_mut($crate::Coord(x, y)) {
+                    *cell = alive.into();
+                }
+            }
+
+            /// Steps the grid by `generations` generations.
+            pub fn step(&mut self, generations: u32) {
+                for _ in 0..generations {
+                    self.grid = self.grid.step();
+                }
+            }
+
+            /// Returns the grid in the wire format.
+            #[must_use]
+            pub fn snapshot(&self) -> $crate::wasm::__private::Vec<u8> {
+                $crate::wasm::__private::snapshot(&self.grid)
+            }
+
+            ///
+            /// Replaces the grid with one in the wire format, as returned
+            /// by `snapshot`.
+            ///
+            /// # Errors
+            ///
+            /// Throws if the bytes are not a valid grid of this size.
+            ///
+            pub fn restore(
+                &mut self,
+                bytes: &[u8],
+            ) -> Result<(), $crate::wasm::wasm_bindgen::JsError> {
+                self.grid = $crate::wasm::__private::restore(bytes)?;
+                Ok(())
+            }
+
+            ///
+            /// Returns an RGBA frame of the grid with one pixel per cell,
+            /// with `alive` and `dead` colors given as `0xRRGGBB`.
+            ///
+            #[must_use]
+            pub fn render(
+                &self,
+                alive: u32,
+                dead: u32,
+            ) -> $crate::wasm::wasm_bindgen::Clamped<$crate::wasm::__private::Vec<u8>> {
+                $crate::wasm::__private::render(&self.grid, alive, dead)
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec::Vec;
+
+    use alloc::{string::ToString, vec};
+
+    use wasm_bindgen::{Clamped, JsError};
+
+    use crate::{grid::Grid, io::Palette, render::to_rgba};
+
+    pub fn snapshot<const W: usize, const H: usize>(grid: &Grid<W, H>) -> Vec<u8> {
+        let mut buf = vec![0; Grid::<W, H>::ENCODED_LEN];
+        let len = grid
+            .to_bytes(&mut buf)
+            .expect("buffer is exactly large enough")
+            .len();
+        buf.truncate(len);
+        buf
+    }
+
+    pub fn restore<const W: usize, const H: usize>(bytes: &[u8]) -> Result<Grid<W, H>, JsError> {
+        Grid::from_bytes(bytes).map_err(|err| JsError::new(&err.to_string()))
+    }
+
+    pub fn render<const W: usize, const H: usize>(
+        grid: &Grid<W, H>,
+        alive: u32,
+        dead: u32,
+    ) -> Clamped<Vec<u8>> {
+        let rgb = |color: u32| {
+            let [_, r, g, b] = color.to_be_bytes();
+            [r, g, b]
+        };
+        let palette = Palette {
+            alive: rgb(alive),
+            dead: rgb(dead),
+        };
+
+        let mut buf = vec![0; W * H * 4];
+        to_rgba(grid, &mut buf, palette);
+        Clamped(buf)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    crate::wasm_grid! {
+        struct Board(4, 3);
+    }
+
+    #[test]
+    fn board() {
+        let mut board = Board::new();
+        assert_eq!((board.width(), board.height()), (4, 3));
+        for x in 0..3 {
+            board.set(x, 1, true);
+        }
+        board.set(9, 9, true);
+        assert_eq!(board.population(), 3);
+
+        let snapshot = board.snapshot();
+        board.step(1);
+        assert!(board.get(1, 0) && board.get(1, 2) && !board.get(0, 1));
+        assert_eq!(board.generation(), 1);
+
+        board.restore(&snapshot).unwrap();
+        assert!(board.get(0, 1) && board.generation() == 0);
+
+        let frame = board.render(0x102030, 0xffffff);
+        assert_eq!(frame.len(), 4 * 3 * 4);
+        assert_eq!(frame[16..20], [0x10, 0x20, 0x30, 0xff]);
+    }
+}